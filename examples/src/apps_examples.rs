@@ -447,7 +447,7 @@ fn get_app<ApiClientType: HerokuApiClient>(api_client: &ApiClientType, app_id: &
 }
 
 fn get_app_raw_response<ApiClientType: HerokuApiClient>(api_client: &ApiClientType, app_id: &str) {
-    // If successful, this returns a raw reqwest::blocking::response, do whatever with it!
+    // If successful, this returns the raw status, headers and body, do whatever with it!
     let response = api_client.request_raw(&apps::AppDetails::new(app_id));
     match response {
         Ok(res) => println!("Ok: {:?}", res),
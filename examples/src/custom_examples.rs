@@ -3,12 +3,14 @@ use super::print_response;
 use heroku_rs::endpoints::custom;
 use heroku_rs::framework::apiclient::HerokuApiClient;
 use heroku_rs::framework::endpoint::Method;
-use serde::Serialize;
+use heroku_rs::framework::response::ApiResult;
+use serde::{Deserialize, Serialize};
 
 pub fn run<ApiClientType: HerokuApiClient>(api_client: &ApiClientType) {
     let app_name = String::from("heroku-rs-tests");
 
     get_app_custom(api_client, app_name);
+    // get_app_features_custom_typed(api_client, app_name);
     // create_app_custom(api_client, app_name);
     // delete_app_custom(api_client, app_name); //Careful here :)
 }
@@ -58,3 +60,23 @@ fn get_app_custom<T: HerokuApiClient>(api_client: &T, app_id: String) {
     let response = api_client.request(&custom::CustomEndpointSimple::new(query, method));
     print_response(response);
 }
+
+#[derive(Deserialize, Debug)]
+pub struct AppFeature {
+    /// unique name of app feature
+    pub name: String,
+    /// whether or not app feature has been enabled
+    pub enabled: bool,
+}
+
+impl ApiResult for AppFeature {}
+
+// get the features of an app, parsed into our own struct
+fn get_app_features_custom_typed<T: HerokuApiClient>(api_client: &T, app_id: String) {
+    let response = api_client.request(
+        &custom::CustomEndpointTyped::<Vec<AppFeature>>::new(Method::Get, "apps/{app_id}/features")
+            .path_param("app_id", app_id)
+            .build(),
+    );
+    print_response(response);
+}
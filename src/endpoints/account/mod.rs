@@ -33,28 +33,14 @@ pub use password::PasswordResetResponse;
 pub use sms_number::SmsNumber;
 
 impl ApiResult for Account {}
-
 impl ApiResult for AccountFeature {}
-impl ApiResult for Vec<AccountFeature> {}
-
 impl ApiResult for AppTransfer {}
-impl ApiResult for Vec<AppTransfer> {}
-
 impl ApiResult for Credit {}
-impl ApiResult for Vec<Credit> {}
-
 impl ApiResult for PasswordResetResponse {}
-
 impl ApiResult for SmsNumber {}
-
 impl ApiResult for Invoice {}
-impl ApiResult for Vec<Invoice> {}
-
 impl ApiResult for InvoiceAddress {}
-impl ApiResult for Vec<InvoiceAddress> {}
-
 impl ApiResult for Key {}
-impl ApiResult for Vec<Key> {}
 
 /// # Account
 ///
@@ -64,6 +50,7 @@ impl ApiResult for Vec<Key> {}
 ///
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#account)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Account {
     /// whether to allow third party web activity tracking, default: true
     pub allow_tracking: bool,
@@ -99,10 +86,15 @@ pub struct Account {
     pub default_organization: Option<DefaultOrganization>,
     /// team selected by default
     pub default_team: Option<DefaultTeam>,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Identity Provider
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct IdentityProvider {
     /// unique identifier of this identity provider
     pub id: String,
@@ -112,24 +104,39 @@ pub struct IdentityProvider {
     pub organization: Organization,
     /// the owner that this identity provider belongs
     pub owner: Owner,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Team
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Team {
     /// unique name
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Organization
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Organization {
     /// unique name
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Owner
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Owner {
     /// unique identifier of the owner
     pub id: String,
@@ -138,24 +145,38 @@ pub struct Owner {
     /// type of the owner. One of:"team" or "enterprise-account"
     #[serde(rename = "type")]
     pub type_field: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// team selected by default
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct DefaultOrganization {
     /// unique identifier of team
     pub id: String,
     /// unique name of team
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Team selected by default
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct DefaultTeam {
     /// unique identifier of team
     pub id: String,
     /// unique name of team
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// # Account Feature
@@ -169,6 +190,7 @@ pub struct DefaultTeam {
 // TODO: (ben) inspect the nullable properties more. As of 21th March 2020, Heroku docs say that none of these properties can be nullable,
 //     but some are... and that's leading so an error decoding response body. e.g. invalid type: null, expected a string.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AccountFeature {
     /// when account feature was created
     pub created_at: String,
@@ -190,6 +212,10 @@ pub struct AccountFeature {
     pub display_name: Option<String>,
     /// e-mail to send feedback about the feature
     pub feedback_email: Option<String>,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// # Account App Transfer
@@ -200,6 +226,7 @@ pub struct AccountFeature {
 ///
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#app-transfer)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AppTransfer {
     /// the app struct containing the app id and name
     pub app: AppTransferApp,
@@ -215,33 +242,52 @@ pub struct AppTransfer {
     pub state: String,
     /// when app transfer was updated
     pub updated_at: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// AppTransferApp
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AppTransferApp {
     /// unique name of team
     pub name: String,
     /// unique identifier of team
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// AppTransferOwner
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AppTransferOwner {
     /// unique email address of account
     pub email: String,
     /// unique identifier of account
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// AppTransferRecipient
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AppTransferRecipient {
     /// unique email address of account
     pub email: String,
     /// unique identifier of an account
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// # Credit
@@ -252,6 +298,7 @@ pub struct AppTransferRecipient {
 ///
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#credit)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Credit {
     /// total value of credit in cents
     pub amount: i64,
@@ -267,6 +314,10 @@ pub struct Credit {
     pub title: String,
     /// when credit was updated
     pub updated_at: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 // password submodule, anything from /password-resets goes here.
@@ -282,19 +333,29 @@ mod password {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#passwordreset)
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct PasswordResetResponse {
         /// when password reset was created
         pub created_at: DateTime<Utc>,
         /// User account
         pub user: User,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct User {
         /// unique email address
         pub email: String,
         /// identifier of an account
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -307,9 +368,14 @@ mod sms_number {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#sms-number)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct SmsNumber {
         /// SMS number of account
         pub sms_number: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -325,6 +391,7 @@ mod invoice {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#invoice)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Invoice {
         /// total charges on this invoice
         pub charges_total: f64,
@@ -346,6 +413,10 @@ mod invoice {
         pub total: f64,
         /// when invoice was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -359,6 +430,7 @@ mod invoice_address {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#invoice-address)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct InvoiceAddress {
         /// invoice street address line 1
         pub address_1: Option<String>,
@@ -378,6 +450,10 @@ mod invoice_address {
         pub state: Option<String>,
         /// flag to use the invoice address for an account or not
         pub use_invoice_address: bool,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -393,6 +469,7 @@ mod key {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#key)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Key {
         /// comment on the key. Example: "username@host"
         pub comment: String,
@@ -408,5 +485,9 @@ mod key {
         pub public_key: String,
         /// when key was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
@@ -27,28 +27,13 @@ pub use post::{
 };
 
 impl ApiResult for Addon {}
-impl ApiResult for Vec<Addon> {}
-
 impl ApiResult for AddonAttachment {}
-impl ApiResult for Vec<AddonAttachment> {}
-
 impl ApiResult for AddonConfig {}
-impl ApiResult for Vec<AddonConfig> {}
-
 impl ApiResult for AddonRegionCapability {}
-impl ApiResult for Vec<AddonRegionCapability> {}
-
 impl ApiResult for AddonService {}
-impl ApiResult for Vec<AddonService> {}
-
 impl ApiResult for AddonWebhook {}
-impl ApiResult for Vec<AddonWebhook> {}
-
 impl ApiResult for AddonWebhookDelivery {}
-impl ApiResult for Vec<AddonWebhookDelivery> {}
-
 impl ApiResult for AddonWebhookEvent {}
-impl ApiResult for Vec<AddonWebhookEvent> {}
 
 pub use addon::Addon;
 pub use addon_attachment::AddonAttachment;
@@ -62,7 +47,6 @@ pub use addon_webhook_event::AddonWebhookEvent;
 mod addon {
     use chrono::offset::Utc;
     use chrono::DateTime;
-    use std::collections::HashMap;
 
     /// Add-on
     ///
@@ -72,6 +56,7 @@ mod addon {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#add-on)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Addon {
         /// Addon action
         pub actions: Actions,
@@ -84,7 +69,7 @@ mod addon {
         /// billed price
         pub billed_price: Option<BilledPrice>,
         /// config vars exposed to the owning app by this add-on
-        pub config_vars: Vec<HashMap<String, String>>,
+        pub config_vars: Vec<String>,
         ///when add-on was created
         pub created_at: DateTime<Utc>,
         /// unique identifier of add-on
@@ -103,8 +88,13 @@ mod addon {
         pub updated_at: DateTime<Utc>,
         /// URL for logging into web interface of add-on (e.g. a dashboard)
         pub web_url: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Actions {
         /// a unique identifier
         pub id: String,
@@ -116,15 +106,25 @@ mod addon {
         pub url: String,
         /// if the action requires the user to own the app
         pub requires_owner: bool,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AddonService {
         /// unique identifier of this add-on-service
         pub id: String,
         /// unique name of this add-on-service
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct BillingEntity {
         /// unique identifier of the billing entity
         pub id: String,
@@ -134,16 +134,26 @@ mod addon {
         ///  one of:"app" or "team"
         #[serde(rename = "type")]
         pub type_field: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct App {
         /// unique identifier of app
         pub id: String,
         /// unique name of app
         ///  pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct BilledPrice {
         /// price in cents per unit of plan
         pub cents: i64,
@@ -151,13 +161,22 @@ mod addon {
         pub contract: bool,
         /// unit of price for plan
         pub unit: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Plan {
         /// unique identifier of this plan
         pub id: String,
         /// unique name of this plan
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -173,6 +192,7 @@ mod addon_attachment {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#add-on-attachment)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AddonAttachment {
         /// addon
         pub addon: Addon,
@@ -192,8 +212,13 @@ mod addon_attachment {
         pub web_url: Option<String>,
         /// URL for add-on partners to write to an add-on’s logs
         pub log_input_url: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Addon {
         /// unique identifier of add-on
         pub id: String,
@@ -202,14 +227,23 @@ mod addon_attachment {
         pub name: String,
         /// addon app
         pub app: App,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct App {
         /// unique identifier of app
         pub id: String,
         /// unique name of app
         ///  pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -222,11 +256,16 @@ mod addon_config {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#add-on-config)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AddonConfig {
         /// unique name of the config
         pub name: String,
         /// value of the config
         pub value: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -242,6 +281,7 @@ mod addon_region {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#add-on-region-capability)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AddonRegionCapability {
         /// unique identifier of this add-on-region-capability
         pub id: String,
@@ -251,8 +291,13 @@ mod addon_region {
         pub addon_service: AddonService,
         /// region represents a geographic location in which your application may run.
         pub region: Region,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AddonService {
         /// npm package name of the add-on service’s Heroku CLI plugin
         pub cli_plugin_name: Option<String>,
@@ -273,8 +318,13 @@ mod addon_region {
         pub supports_sharing: bool,
         ///when add-on-service was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Region {
         /// country where the region exists
         pub country: String,
@@ -294,8 +344,13 @@ mod addon_region {
         pub provider: Provider,
         /// when region was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Provider {
         /// name of provider
         pub name: String,
@@ -303,6 +358,10 @@ mod addon_region {
         /// one of:"ap-south-1" or "eu-west-1" or "ap-southeast-1" or "ap-southeast-2" or "eu-central-1"
         ///   or "ap-northeast-2" or "ap-northeast-1" or "us-east-1" or "sa-east-1" or "us-west-1" or "us-west-2"
         pub region: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -318,6 +377,7 @@ mod addon_services {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#add-on-service)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AddonService {
         /// npm package name of the add-on service’s Heroku CLI plugin
         pub cli_plugin_name: Option<String>,
@@ -338,6 +398,10 @@ mod addon_services {
         pub supports_sharing: bool,
         /// when add-on-service was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -353,6 +417,7 @@ mod addon_webhook {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#add-on-service)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AddonWebhook {
         pub addon: Addon,
         /// when the webhook was created
@@ -369,14 +434,23 @@ mod addon_webhook {
         pub updated_at: DateTime<Utc>,
         /// the URL where the webhook’s notification requests are sent
         pub url: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Addon {
         /// unique identifier of add-on
         pub id: String,
         /// globally unique name of the add-on
         ///  pattern: ^[a-zA-Z][A-Za-z0-9_-]+$
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -392,6 +466,7 @@ mod addon_webhook_delivery {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#add-on-webhook-delivery)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AddonWebhookDelivery {
         /// when the delivery was created
         pub created_at: DateTime<Utc>,
@@ -412,15 +487,25 @@ mod addon_webhook_delivery {
         pub updated_at: DateTime<Utc>,
         /// the webhook which we get the deliveries for
         pub webhook: Webhook,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Event {
         /// the event’s unique identifier
         pub id: String,
         /// the type of entity that the event is related to
         pub include: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct LastAttempt {
         /// unique identifier of attempt
         pub id: String,
@@ -435,8 +520,13 @@ mod addon_webhook_delivery {
         pub created_at: DateTime<Utc>,
         /// when attempt was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Webhook {
         /// the webhook’s unique identifier
         pub id: String,
@@ -444,6 +534,10 @@ mod addon_webhook_delivery {
         /// If sync, Heroku attempts multiple deliveries until the request is successful or a limit is reached
         ///  one of:"notify" or "sync"
         pub level: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -460,6 +554,7 @@ mod addon_webhook_event {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#add-on-webhook-event)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AddonWebhookEvent {
         /// when event was created
         pub created_at: DateTime<Utc>,
@@ -471,8 +566,13 @@ mod addon_webhook_event {
         pub payload: Payload,
         /// when the event was last updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Payload {
         /// the type of event that occurred
         pub action: String,
@@ -486,12 +586,21 @@ mod addon_webhook_event {
         pub resource: String,
         /// the version of the details provided for the event
         pub version: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Actor {
         /// unique email address
         pub email: String,
         /// identifier of an account
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
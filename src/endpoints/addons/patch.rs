@@ -147,6 +147,8 @@ impl<'a> AddonConfigUpdate<'a> {
         self.params.config = Some(vec![AddonConfig {
             name: config_name.to_owned(),
             value: config_value.to_owned(),
+            #[cfg(feature = "extra_fields")]
+            extra: Default::default(),
         }]);
         self
     }
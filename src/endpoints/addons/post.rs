@@ -45,7 +45,7 @@ pub struct AddonCreate<'a> {
     /// unique app identifier, either app id or app name.
     pub app_id: &'a str,
     /// parameters to pass to the Heroku API
-    params: AddonCreateParams<'a>,
+    pub params: AddonCreateParams<'a>,
 }
 
 #[cfg(feature = "builder")]
@@ -0,0 +1,542 @@
+//! Cloning the configuration of an app into another app.
+use super::{
+    AppFeatureList, AppFeatureUpdate, AppFeatureUpdateParams, AppWebhookCreate,
+    AppWebhookCreateParams, AppWebhookList,
+};
+use crate::endpoints::addons::{
+    Addon, AddonAttachment, AddonCreate, AddonCreateParams, AddonListByApp, AttachmentCreate,
+    AttachmentCreateParams, AttachmentListByApp,
+};
+use crate::endpoints::builds::{
+    BuildPackInstallationList, BuildpackInstallationUpdate, BuildpackInstallationUpdateParams,
+    Update,
+};
+use crate::endpoints::config_vars::{AppConfigVarDetails, AppConfigVarUpdate};
+use crate::endpoints::domains::{DomainCreate, DomainCreateParams, DomainList};
+use crate::endpoints::formations::{FormationList, FormationUpdate, FormationUpdateParams};
+use crate::endpoints::logs::{LogDrainCreate, LogDrainCreateParams, LogDrainList};
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    response::{ApiResponse, HerokuApiFailure},
+    HttpApiClient,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// What an [`AppCloner`][cloner] can copy from an app to another.
+///
+/// [cloner]: struct.AppCloner.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CloneItem {
+    ConfigVars,
+    Addons,
+    Buildpacks,
+    Features,
+    Formation,
+    Domains,
+    LogDrains,
+    Webhooks,
+}
+
+impl CloneItem {
+    /// Every item, in the order they are copied.
+    pub const ALL: [CloneItem; 8] = [
+        CloneItem::ConfigVars,
+        CloneItem::Addons,
+        CloneItem::Buildpacks,
+        CloneItem::Features,
+        CloneItem::Formation,
+        CloneItem::Domains,
+        CloneItem::LogDrains,
+        CloneItem::Webhooks,
+    ];
+}
+
+impl fmt::Display for CloneItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            CloneItem::ConfigVars => "config vars",
+            CloneItem::Addons => "add-ons",
+            CloneItem::Formation => "formation",
+            CloneItem::Buildpacks => "buildpacks",
+            CloneItem::Domains => "domains",
+            CloneItem::Features => "features",
+            CloneItem::LogDrains => "log drains",
+            CloneItem::Webhooks => "webhooks",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Copies the configuration of a source app into a target app.
+///
+/// Everything is copied by default: config vars, add-ons, buildpacks, app features, formation sizes and quantities,
+/// custom domains, log drains and webhooks. Use [`only`][only] or [`exclude`][exclude] to narrow it down.
+///
+/// Add-ons owned by the source app are provisioned again on the target, with the same plan. Add-ons the source
+/// app is only attached to are attached to the target as well. The config vars set by add-on attachments are left
+/// to them, and the ones without a value are not copied.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::apps::{AppCloner, CloneItem};
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let cloner = AppCloner::new(&api_client, "STAGING_APP_ID", "REVIEW_APP_ID").exclude(CloneItem::Domains);
+///
+/// let plan = cloner.plan().unwrap();
+/// for action in &plan.actions {
+///     println!("will {}", action);
+/// }
+///
+/// let report = cloner.apply(plan);
+/// for (action, error) in &report.failed {
+///     println!("could not {}: {}", action, error);
+/// }
+/// ```
+///
+/// [only]: struct.AppCloner.html#method.only
+/// [exclude]: struct.AppCloner.html#method.exclude
+pub struct AppCloner<'a> {
+    client: &'a HttpApiClient,
+    source_app_id: &'a str,
+    target_app_id: &'a str,
+    items: Vec<CloneItem>,
+}
+
+impl<'a> AppCloner<'a> {
+    pub fn new(
+        client: &'a HttpApiClient,
+        source_app_id: &'a str,
+        target_app_id: &'a str,
+    ) -> AppCloner<'a> {
+        AppCloner {
+            client,
+            source_app_id,
+            target_app_id,
+            items: CloneItem::ALL.to_vec(),
+        }
+    }
+
+    /// # only: copy only these items
+    pub fn only(mut self, items: &[CloneItem]) -> Self {
+        self.items = items.to_vec();
+        self
+    }
+
+    /// # include: copy this item too
+    pub fn include(mut self, item: CloneItem) -> Self {
+        if !self.items.contains(&item) {
+            self.items.push(item);
+        }
+        self
+    }
+
+    /// # exclude: do not copy this item
+    pub fn exclude(mut self, item: CloneItem) -> Self {
+        self.items.retain(|included| *included != item);
+        self
+    }
+
+    /// Read the source app, and list what would be copied to the target, without changing anything.
+    pub fn plan(&self) -> ApiResponse<ClonePlan> {
+        let mut plan = ClonePlan {
+            actions: Vec::new(),
+            skipped: Vec::new(),
+        };
+        for item in CloneItem::ALL.iter() {
+            if !self.items.contains(item) {
+                plan.skip(*item, &item.to_string(), "excluded");
+            }
+        }
+
+        let app_id = self.source_app_id;
+        // Needed for the config vars too, to leave the ones managed by add-ons alone.
+        let addons = match self.includes(CloneItem::Addons) || self.includes(CloneItem::ConfigVars)
+        {
+            true => self.client.request(&AddonListByApp { app_id })?,
+            false => Vec::new(),
+        };
+
+        if self.includes(CloneItem::ConfigVars) {
+            let attachments = self.client.request(&AttachmentListByApp { app_id })?;
+            let managed = addon_config_vars(&addons, &attachments);
+            let mut vars = BTreeMap::new();
+            let config_vars: BTreeMap<String, Option<String>> = self
+                .client
+                .request(&AppConfigVarDetails { app_id })?
+                .into_iter()
+                .collect();
+            for (name, value) in config_vars {
+                match (managed.get(&name), value) {
+                    (Some(addon), _) => plan.skip(
+                        CloneItem::ConfigVars,
+                        &name,
+                        &format!("set by add-on {}", addon),
+                    ),
+                    (None, None) => plan.skip(CloneItem::ConfigVars, &name, "has no value"),
+                    (None, Some(value)) => {
+                        vars.insert(name, value);
+                    }
+                }
+            }
+            if !vars.is_empty() {
+                plan.actions.push(CloneAction::SetConfigVars(vars));
+            }
+        }
+
+        if self.includes(CloneItem::Addons) {
+            for addon in &addons {
+                plan.actions
+                    .push(match addon.app.id == app_id || addon.app.name == app_id {
+                        true => CloneAction::CreateAddon {
+                            plan: addon.plan.name.clone(),
+                        },
+                        false => CloneAction::AttachAddon {
+                            addon: addon.name.clone(),
+                        },
+                    });
+            }
+        }
+
+        if self.includes(CloneItem::Buildpacks) {
+            let mut installations = self.client.request(&BuildPackInstallationList { app_id })?;
+            installations.sort_by_key(|installation| installation.ordinal);
+            if !installations.is_empty() {
+                plan.actions.push(CloneAction::SetBuildpacks(
+                    installations
+                        .into_iter()
+                        .map(|installation| installation.buildpack.url)
+                        .collect(),
+                ));
+            }
+        }
+
+        if self.includes(CloneItem::Features) {
+            for feature in self.client.request(&AppFeatureList { app_id })? {
+                if feature.enabled {
+                    plan.actions
+                        .push(CloneAction::EnableFeature { name: feature.name });
+                }
+            }
+        }
+
+        if self.includes(CloneItem::Formation) {
+            for formation in self.client.request(&FormationList { app_id })? {
+                plan.actions.push(CloneAction::UpdateFormation {
+                    process_type: formation.r#type,
+                    quantity: formation.quantity,
+                    size: formation.size,
+                });
+            }
+        }
+
+        if self.includes(CloneItem::Domains) {
+            for domain in self.client.request(&DomainList { app_id })? {
+                match domain.kind.as_str() {
+                    "custom" => plan.actions.push(CloneAction::AddDomain {
+                        hostname: domain.hostname,
+                    }),
+                    _ => plan.skip(CloneItem::Domains, &domain.hostname, "managed by Heroku"),
+                }
+            }
+        }
+
+        if self.includes(CloneItem::LogDrains) {
+            for drain in self.client.request(&LogDrainList { app_id })? {
+                match drain.addon {
+                    Some(addon) => plan.skip(
+                        CloneItem::LogDrains,
+                        &drain.url,
+                        &format!("managed by add-on {}", addon.name),
+                    ),
+                    None => plan
+                        .actions
+                        .push(CloneAction::AddLogDrain { url: drain.url }),
+                }
+            }
+        }
+
+        if self.includes(CloneItem::Webhooks) {
+            for webhook in self.client.request(&AppWebhookList { app_id })? {
+                plan.actions.push(CloneAction::AddWebhook {
+                    url: webhook.url,
+                    level: webhook.level,
+                    include: webhook.include,
+                });
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Apply a plan to the target app.
+    ///
+    /// Every action is attempted, even after a failure. Failures are reported with their error.
+    pub fn apply(&self, plan: ClonePlan) -> CloneReport {
+        let mut report = CloneReport {
+            copied: Vec::new(),
+            skipped: plan.skipped,
+            failed: Vec::new(),
+        };
+        for action in plan.actions {
+            match self.apply_action(&action) {
+                Ok(()) => report.copied.push(action),
+                Err(e) => report.failed.push((action, e)),
+            }
+        }
+        report
+    }
+
+    /// Plan, then apply.
+    pub fn run(&self) -> ApiResponse<CloneReport> {
+        Ok(self.apply(self.plan()?))
+    }
+
+    fn includes(&self, item: CloneItem) -> bool {
+        self.items.contains(&item)
+    }
+
+    fn apply_action(&self, action: &CloneAction) -> Result<(), HerokuApiFailure> {
+        let client = self.client;
+        let app_id = self.target_app_id;
+        match action {
+            CloneAction::SetConfigVars(vars) => {
+                let params: HashMap<String, String> = vars.clone().into_iter().collect();
+                client.request(&AppConfigVarUpdate { app_id, params })?;
+            }
+            CloneAction::CreateAddon { plan } => {
+                client.request(&AddonCreate {
+                    app_id,
+                    params: AddonCreateParams {
+                        attachment: None,
+                        config: None,
+                        confirm: None,
+                        plan,
+                        name: None,
+                    },
+                })?;
+            }
+            CloneAction::AttachAddon { addon } => {
+                client.request(&AttachmentCreate {
+                    params: AttachmentCreateParams {
+                        addon,
+                        app: app_id,
+                        confirm: None,
+                        name: None,
+                        namespace: None,
+                    },
+                })?;
+            }
+            CloneAction::SetBuildpacks(buildpacks) => {
+                client.request(&BuildpackInstallationUpdate {
+                    app_id,
+                    params: BuildpackInstallationUpdateParams {
+                        updates: buildpacks
+                            .iter()
+                            .map(|buildpack| Update {
+                                buildpack: buildpack.clone(),
+                            })
+                            .collect(),
+                    },
+                })?;
+            }
+            CloneAction::EnableFeature { name } => {
+                client.request(&AppFeatureUpdate {
+                    app_id,
+                    feature_id: name,
+                    params: AppFeatureUpdateParams { enabled: true },
+                })?;
+            }
+            CloneAction::UpdateFormation {
+                process_type,
+                quantity,
+                size,
+            } => {
+                client.request(&FormationUpdate {
+                    app_id,
+                    formation_id: process_type,
+                    params: FormationUpdateParams {
+                        quantity: Some(*quantity),
+                        size: Some(size),
+                    },
+                })?;
+            }
+            CloneAction::AddDomain { hostname } => {
+                client.request(&DomainCreate {
+                    app_id,
+                    params: DomainCreateParams { hostname },
+                })?;
+            }
+            CloneAction::AddLogDrain { url } => {
+                client.request(&LogDrainCreate {
+                    app_id,
+                    params: LogDrainCreateParams { url },
+                })?;
+            }
+            CloneAction::AddWebhook {
+                url,
+                level,
+                include,
+            } => {
+                client.request(&AppWebhookCreate {
+                    app_id,
+                    params: AppWebhookCreateParams {
+                        authorization: None,
+                        include: include.iter().map(String::as_str).collect(),
+                        level,
+                        secret: None,
+                        url,
+                    },
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The config vars set on an app by its add-on attachments, with the name of the add-on setting each.
+///
+/// An add-on lists its config vars under the name of one of its attachments, e.g. `DATABASE_URL`. Every other
+/// attachment sets them under its own name as well, e.g. `HEROKU_POSTGRESQL_PINK_URL`.
+fn addon_config_vars(
+    addons: &[Addon],
+    attachments: &[AddonAttachment],
+) -> BTreeMap<String, String> {
+    let mut managed = BTreeMap::new();
+    for attachment in attachments {
+        let addon = match addons.iter().find(|addon| addon.id == attachment.addon.id) {
+            Some(addon) => addon,
+            None => continue,
+        };
+        let names: Vec<&str> = attachments
+            .iter()
+            .filter(|other| other.addon.id == addon.id)
+            .map(|other| other.name.as_str())
+            .collect();
+        for var in &addon.config_vars {
+            let suffix = names
+                .iter()
+                .find(|name| var.starts_with(&format!("{}_", name)))
+                .map(|name| &var[name.len()..]);
+            managed.insert(var.clone(), addon.name.clone());
+            if let Some(suffix) = suffix {
+                managed.insert(format!("{}{}", attachment.name, suffix), addon.name.clone());
+            }
+        }
+    }
+    managed
+}
+
+/// What an [`AppCloner`][cloner] would copy, and what it would leave out.
+///
+/// [cloner]: struct.AppCloner.html
+#[derive(Debug, Clone)]
+pub struct ClonePlan {
+    /// the changes to make to the target app
+    pub actions: Vec<CloneAction>,
+    /// what is not copied, and why
+    pub skipped: Vec<Skipped>,
+}
+
+impl ClonePlan {
+    fn skip(&mut self, item: CloneItem, name: &str, reason: &str) {
+        self.skipped.push(Skipped {
+            item,
+            name: name.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+}
+
+/// A change to the target app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CloneAction {
+    /// Set these config vars
+    SetConfigVars(BTreeMap<String, String>),
+    /// Provision an add-on with this plan
+    CreateAddon { plan: String },
+    /// Attach an existing add-on
+    AttachAddon { addon: String },
+    /// Replace the buildpacks, in this order
+    SetBuildpacks(Vec<String>),
+    /// Enable an app feature
+    EnableFeature { name: String },
+    /// Scale a process type
+    UpdateFormation {
+        process_type: String,
+        quantity: i32,
+        size: String,
+    },
+    /// Add a custom domain
+    AddDomain { hostname: String },
+    /// Add a log drain
+    AddLogDrain { url: String },
+    /// Add a webhook. Its secret and authorization cannot be read, so they are not copied.
+    AddWebhook {
+        url: String,
+        level: String,
+        include: Vec<String>,
+    },
+}
+
+impl fmt::Display for CloneAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CloneAction::SetConfigVars(vars) => {
+                let names: Vec<&str> = vars.keys().map(String::as_str).collect();
+                write!(f, "set config vars {}", names.join(", "))
+            }
+            CloneAction::CreateAddon { plan } => write!(f, "provision add-on {}", plan),
+            CloneAction::AttachAddon { addon } => write!(f, "attach add-on {}", addon),
+            CloneAction::SetBuildpacks(buildpacks) => {
+                write!(f, "set buildpacks {}", buildpacks.join(", "))
+            }
+            CloneAction::EnableFeature { name } => write!(f, "enable feature {}", name),
+            CloneAction::UpdateFormation {
+                process_type,
+                quantity,
+                size,
+            } => write!(f, "scale {} to {}:{}", process_type, quantity, size),
+            CloneAction::AddDomain { hostname } => write!(f, "add domain {}", hostname),
+            CloneAction::AddLogDrain { url } => write!(f, "add log drain {}", url),
+            CloneAction::AddWebhook {
+                url,
+                level,
+                include,
+            } => write!(
+                f,
+                "add {} webhook {} for {}",
+                level,
+                url,
+                include.join(", ")
+            ),
+        }
+    }
+}
+
+/// Something left out of a clone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Skipped {
+    /// the kind of thing
+    pub item: CloneItem,
+    /// its name, e.g. a config var name or a hostname
+    pub name: String,
+    /// why it is not copied
+    pub reason: String,
+}
+
+/// What an [`AppCloner`][cloner] copied, skipped, or failed to copy.
+///
+/// [cloner]: struct.AppCloner.html
+#[derive(Debug)]
+pub struct CloneReport {
+    /// the changes made to the target app
+    pub copied: Vec<CloneAction>,
+    /// what was not copied, and why
+    pub skipped: Vec<Skipped>,
+    /// the changes which failed, with their error
+    pub failed: Vec<(CloneAction, HerokuApiFailure)>,
+}
@@ -0,0 +1,755 @@
+//! The `app.json` manifest, describing how to set up an app.
+//!
+//! [See Heroku documentation for more information about the schema](https://devcenter.heroku.com/articles/app-json-schema)
+use super::post::{Buildpack, Overrides};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// An `app.json` manifest, as read by `AppSetupCreate` and review apps.
+///
+/// The shorthand forms of the schema, e.g. `"env": {"KEY": "value"}` or `"addons": ["heroku-redis"]`, are read
+/// into the full forms. Keys which are not in the schema, e.g. a misspelled `"requried"`, are rejected.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::apps::AppManifest;
+/// use heroku_rs::prelude::*;
+/// use std::collections::HashMap;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let manifest = AppManifest::from_file("app.json").unwrap();
+/// if let Err(errors) = manifest.validate() {
+///     for error in errors {
+///         println!("{}", error);
+///     }
+///     return;
+/// }
+///
+/// let mut env = HashMap::new();
+/// env.insert("API_TOKEN", "token");
+///
+/// let mut setup = AppSetupCreate::new("https://example.com/source.tgz");
+/// setup.params.overrides = manifest.overrides(&env).unwrap();
+/// let response = api_client.request(&setup);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AppManifest {
+    /// a clean name for the app, which is not necessarily the name of the created app
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// a brief summary of the app
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// keywords describing the app
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// the project's website
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    /// the location of the app's source code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// the URL of the app's logo image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo: Option<String>,
+    /// the path or URL to open once the app is set up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success_url: Option<String>,
+    /// scripts run at points of the app's lifecycle
+    #[serde(default, skip_serializing_if = "ManifestScripts::is_empty")]
+    pub scripts: ManifestScripts,
+    /// config vars, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, ManifestEnv>,
+    /// dynos to scale, by process type
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub formation: BTreeMap<String, ManifestFormation>,
+    /// add-ons to provision
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub addons: Vec<ManifestAddon>,
+    /// buildpacks, in the order they run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buildpacks: Vec<ManifestBuildpack>,
+    /// overrides for the `test` and `review` environments
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub environments: BTreeMap<String, ManifestEnvironment>,
+    /// the stack to build and run the app on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack: Option<String>,
+}
+
+/// Scripts run at points of the app's lifecycle.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestScripts {
+    /// run once, after the app is first deployed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postdeploy: Option<ManifestScript>,
+    /// run before a review app is destroyed
+    #[serde(rename = "pr-predestroy", skip_serializing_if = "Option::is_none")]
+    pub pr_predestroy: Option<ManifestScript>,
+    /// run on CI, in the `test` environment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test: Option<ManifestScript>,
+    /// run on CI before `test`, in the `test` environment
+    #[serde(rename = "test-setup", skip_serializing_if = "Option::is_none")]
+    pub test_setup: Option<ManifestScript>,
+}
+
+impl ManifestScripts {
+    fn is_empty(&self) -> bool {
+        self == &ManifestScripts::default()
+    }
+}
+
+/// A script, either a command or a command and the size of the dyno running it.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "Value")]
+pub struct ManifestScript {
+    /// the command to run
+    pub command: String,
+    /// the size of the dyno running the command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ScriptFields {
+    command: String,
+    size: Option<String>,
+}
+
+impl TryFrom<Value> for ManifestScript {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        read(
+            value,
+            &SCRIPT,
+            |command| ManifestScript {
+                command,
+                size: None,
+            },
+            |full: ScriptFields| ManifestScript {
+                command: full.command,
+                size: full.size,
+            },
+        )
+    }
+}
+
+/// A config var of the manifest, either a value or a description of how to set it.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(try_from = "Value")]
+pub struct ManifestEnv {
+    /// a human-readable explanation of the config var
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// the default value of the config var
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// whether the config var must be set, true by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// how Heroku generates the value, only `secret` is supported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generator: Option<String>,
+}
+
+impl ManifestEnv {
+    /// Whether a value must be given when setting the app up, i.e. it is required and has no default or generator.
+    pub fn needs_value(&self) -> bool {
+        self.required.unwrap_or(true) && self.value.is_none() && self.generator.is_none()
+    }
+}
+
+#[derive(Deserialize)]
+struct EnvFields {
+    description: Option<String>,
+    value: Option<String>,
+    required: Option<bool>,
+    generator: Option<String>,
+}
+
+impl TryFrom<Value> for ManifestEnv {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        read(
+            value,
+            &ENV,
+            |value| ManifestEnv {
+                value: Some(value),
+                ..ManifestEnv::default()
+            },
+            |full: EnvFields| ManifestEnv {
+                description: full.description,
+                value: full.value,
+                required: full.required,
+                generator: full.generator,
+            },
+        )
+    }
+}
+
+/// The dynos of a process type.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestFormation {
+    /// number of dynos
+    pub quantity: u32,
+    /// dyno size, e.g. `standard-1X`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+}
+
+/// An add-on of the manifest, either a plan or a plan and how to attach and configure it.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "Value")]
+pub struct ManifestAddon {
+    /// the add-on service and plan, e.g. `heroku-postgresql:hobby-dev`
+    pub plan: String,
+    /// the name of the attachment, which prefixes the config vars set by the add-on
+    #[serde(rename = "as", skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<String>,
+    /// provider-specific options
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+#[derive(Deserialize)]
+struct AddonFields {
+    plan: String,
+    #[serde(rename = "as")]
+    attachment: Option<String>,
+    options: Option<serde_json::Map<String, Value>>,
+}
+
+impl TryFrom<Value> for ManifestAddon {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        read(
+            value,
+            &ADDON,
+            |plan| ManifestAddon {
+                plan,
+                attachment: None,
+                options: None,
+            },
+            |full: AddonFields| ManifestAddon {
+                plan: full.plan,
+                attachment: full.attachment,
+                options: full.options,
+            },
+        )
+    }
+}
+
+/// A buildpack of the manifest.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestBuildpack {
+    /// the buildpack URL or registry name, e.g. `heroku/ruby`
+    pub url: String,
+}
+
+/// The overrides of an environment, e.g. `test` on Heroku CI, or `review` for review apps.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestEnvironment {
+    /// scripts run in this environment
+    #[serde(default, skip_serializing_if = "ManifestScripts::is_empty")]
+    pub scripts: ManifestScripts,
+    /// config vars, added to the ones of the manifest
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, ManifestEnv>,
+    /// dynos to scale, instead of the ones of the manifest
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub formation: BTreeMap<String, ManifestFormation>,
+    /// add-ons to provision, instead of the ones of the manifest
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub addons: Vec<ManifestAddon>,
+    /// buildpacks, instead of the ones of the manifest
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buildpacks: Vec<ManifestBuildpack>,
+}
+
+/// The environments an `app.json` can override.
+const ENVIRONMENTS: [&str; 2] = ["test", "review"];
+
+/// The JSON shape of a part of the manifest, to report where a document does not follow the schema.
+enum Shape {
+    /// any JSON value
+    Any,
+    String,
+    Bool,
+    /// a non-negative integer
+    Count,
+    /// an object with these keys, and whether each is required
+    Fields(&'static [(&'static str, bool, Shape)]),
+    /// an object with any keys, whose values have this shape
+    Map(&'static Shape),
+    List(&'static Shape),
+    /// a shorthand string, or the full form
+    Shorthand(&'static Shape),
+}
+
+const SCRIPT: Shape = Shape::Shorthand(&Shape::Fields(&[
+    ("command", true, Shape::String),
+    ("size", false, Shape::String),
+]));
+
+const SCRIPTS: Shape = Shape::Fields(&[
+    ("postdeploy", false, SCRIPT),
+    ("pr-predestroy", false, SCRIPT),
+    ("test", false, SCRIPT),
+    ("test-setup", false, SCRIPT),
+]);
+
+const ENV: Shape = Shape::Shorthand(&Shape::Fields(&[
+    ("description", false, Shape::String),
+    ("value", false, Shape::String),
+    ("required", false, Shape::Bool),
+    ("generator", false, Shape::String),
+]));
+
+const FORMATION: Shape = Shape::Fields(&[
+    ("quantity", true, Shape::Count),
+    ("size", false, Shape::String),
+]);
+
+const ADDON: Shape = Shape::Shorthand(&Shape::Fields(&[
+    ("plan", true, Shape::String),
+    ("as", false, Shape::String),
+    ("options", false, Shape::Map(&Shape::Any)),
+]));
+
+const BUILDPACK: Shape = Shape::Fields(&[("url", true, Shape::String)]);
+
+const ENVIRONMENT: Shape = Shape::Fields(&[
+    ("scripts", false, SCRIPTS),
+    ("env", false, Shape::Map(&ENV)),
+    ("formation", false, Shape::Map(&FORMATION)),
+    ("addons", false, Shape::List(&ADDON)),
+    ("buildpacks", false, Shape::List(&BUILDPACK)),
+]);
+
+const MANIFEST: Shape = Shape::Fields(&[
+    ("name", false, Shape::String),
+    ("description", false, Shape::String),
+    ("keywords", false, Shape::List(&Shape::String)),
+    ("website", false, Shape::String),
+    ("repository", false, Shape::String),
+    ("logo", false, Shape::String),
+    ("success_url", false, Shape::String),
+    ("scripts", false, SCRIPTS),
+    ("env", false, Shape::Map(&ENV)),
+    ("formation", false, Shape::Map(&FORMATION)),
+    ("addons", false, Shape::List(&ADDON)),
+    ("buildpacks", false, Shape::List(&BUILDPACK)),
+    ("environments", false, Shape::Map(&ENVIRONMENT)),
+    ("stack", false, Shape::String),
+]);
+
+impl Shape {
+    /// Check that `value`, at `path`, has this shape, and report every place where it does not.
+    fn check(&self, value: &Value, path: &str, errors: &mut Errors) {
+        match (self, value) {
+            (Shape::Any, _) | (Shape::String, Value::String(_)) | (Shape::Bool, Value::Bool(_)) => {
+            }
+            (Shape::Count, Value::Number(number)) if number.is_u64() => {}
+            (Shape::Fields(fields), Value::Object(object)) => {
+                for (key, value) in object {
+                    let path = join(path, key);
+                    match fields.iter().find(|(name, _, _)| name == key) {
+                        // Optional keys can be null, like `Option` fields.
+                        Some((_, false, _)) if value.is_null() => {}
+                        Some((_, _, shape)) => shape.check(value, &path, errors),
+                        None => {
+                            let names: Vec<&str> =
+                                fields.iter().map(|(name, _, _)| *name).collect();
+                            errors.push(
+                                &path,
+                                format!("unknown key, expected one of {}", names.join(", ")),
+                            )
+                        }
+                    }
+                }
+                for (name, _, _) in fields.iter().filter(|(_, required, _)| *required) {
+                    if object.get(*name).unwrap_or(&Value::Null).is_null() {
+                        errors.push(&join(path, name), "is required");
+                    }
+                }
+            }
+            (Shape::Map(shape), Value::Object(object)) => {
+                for (key, value) in object {
+                    shape.check(value, &join(path, key), errors);
+                }
+            }
+            (Shape::List(shape), Value::Array(array)) => {
+                for (index, value) in array.iter().enumerate() {
+                    shape.check(value, &format!("{}[{}]", path, index), errors);
+                }
+            }
+            (Shape::Shorthand(_), Value::String(_)) => {}
+            (Shape::Shorthand(full), Value::Object(_)) => full.check(value, path, errors),
+            (shape, value) => errors.push(
+                path,
+                format!("expected {}, got {}", shape.describe(), describe(value)),
+            ),
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            Shape::Any => "anything",
+            Shape::String => "a string",
+            Shape::Bool => "true or false",
+            Shape::Count => "a non-negative integer",
+            Shape::Fields(_) | Shape::Map(_) => "an object",
+            Shape::List(_) => "an array",
+            Shape::Shorthand(_) => "a string or an object",
+        }
+    }
+}
+
+/// What kind of JSON value `value` is, for error messages.
+fn describe(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// The path of `key` in the object at `path`.
+fn join(path: &str, key: &str) -> String {
+    match path.is_empty() {
+        true => key.to_string(),
+        false => format!("{}.{}", path, key),
+    }
+}
+
+/// Read a part of the manifest which has a shorthand form, once it is checked against its `shape`.
+fn read<T, F>(
+    value: Value,
+    shape: &Shape,
+    shorthand: impl FnOnce(String) -> T,
+    full: impl FnOnce(F) -> T,
+) -> Result<T, String>
+where
+    F: DeserializeOwned,
+{
+    let mut errors = Errors::default();
+    shape.check(&value, "", &mut errors);
+    if !errors.0.is_empty() {
+        return Err(errors.to_string());
+    }
+    match value {
+        Value::String(value) => Ok(shorthand(value)),
+        value => serde_json::from_value(value)
+            .map(full)
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// A problem in a manifest, at a JSON path such as `env.SECRET_KEY.generator`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestError {
+    /// where the problem is
+    pub path: String,
+    /// what the problem is
+    pub message: String,
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl FromStr for AppManifest {
+    type Err = serde_json::Error;
+
+    /// Read a manifest from JSON, reporting where it does not follow the schema, e.g.
+    /// `env.KEY.required: expected true or false, got a string`.
+    fn from_str(json: &str) -> Result<Self, Self::Err> {
+        AppManifest::from_value(serde_json::from_str(json)?)
+            .map_err(|errors| serde::de::Error::custom(Errors(errors)))
+    }
+}
+
+impl AppManifest {
+    /// Read an `app.json` file.
+    ///
+    /// Malformed JSON, or JSON which does not follow the schema, is reported as an `InvalidData` error.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<AppManifest> {
+        let json = fs::read_to_string(path)?;
+        Ok(json.parse()?)
+    }
+
+    /// Read a manifest from a JSON document, and report every place where it does not follow the schema,
+    /// e.g. a value of the wrong type or an unknown key.
+    pub fn from_value(value: Value) -> Result<AppManifest, Vec<ManifestError>> {
+        let mut errors = Errors::default();
+        MANIFEST.check(&value, "", &mut errors);
+        if !errors.0.is_empty() {
+            return Err(errors.0);
+        }
+        serde_json::from_value(value).map_err(|e| {
+            vec![ManifestError {
+                path: String::new(),
+                message: e.to_string(),
+            }]
+        })
+    }
+
+    /// Check the manifest the way Heroku would, and report every problem found.
+    pub fn validate(&self) -> Result<(), Vec<ManifestError>> {
+        let mut errors = Errors::default();
+        if let Some(name) = &self.name {
+            if name.trim().is_empty() {
+                errors.push("name", "must not be empty");
+            }
+        }
+        for (field, url) in [
+            ("website", &self.website),
+            ("repository", &self.repository),
+            ("logo", &self.logo),
+        ] {
+            if let Some(url) = url {
+                if let Err(e) = url::Url::parse(url) {
+                    errors.push(field, format!("invalid URL {:?}: {}", url, e));
+                }
+            }
+        }
+        if let Some(stack) = &self.stack {
+            if !is_name(stack, |c| {
+                c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'
+            }) {
+                errors.push("stack", format!("invalid stack name {:?}", stack));
+            }
+        }
+        errors.scripts("scripts", &self.scripts);
+        errors.env("env", &self.env);
+        errors.formation("formation", &self.formation);
+        errors.addons("addons", &self.addons);
+        errors.buildpacks("buildpacks", &self.buildpacks);
+        for (name, environment) in &self.environments {
+            let path = format!("environments.{}", name);
+            if !ENVIRONMENTS.contains(&name.as_str()) {
+                errors.push(
+                    &path,
+                    format!(
+                        "unknown environment, expected one of {}",
+                        ENVIRONMENTS.join(", ")
+                    ),
+                );
+            }
+            errors.scripts(&format!("{}.scripts", path), &environment.scripts);
+            errors.env(&format!("{}.env", path), &environment.env);
+            errors.formation(&format!("{}.formation", path), &environment.formation);
+            errors.addons(&format!("{}.addons", path), &environment.addons);
+            errors.buildpacks(&format!("{}.buildpacks", path), &environment.buildpacks);
+        }
+
+        match errors.0.is_empty() {
+            true => Ok(()),
+            false => Err(errors.0),
+        }
+    }
+
+    /// The overrides to set up an app from a source whose `app.json` is not this manifest.
+    ///
+    /// The config vars are the values of the manifest, replaced by the ones of `env`. Config vars which need a
+    /// value, but are not in `env`, are reported as errors.
+    pub fn overrides<'a>(
+        &'a self,
+        env: &HashMap<&'a str, &'a str>,
+    ) -> Result<Overrides<'a>, Vec<ManifestError>> {
+        let mut vars: HashMap<&'a str, &'a str> = self
+            .env
+            .iter()
+            .filter_map(|(name, var)| Some((name.as_str(), var.value.as_deref()?)))
+            .collect();
+        vars.extend(env);
+
+        let missing: Vec<ManifestError> = self
+            .env
+            .iter()
+            .filter(|(name, var)| var.needs_value() && !vars.contains_key(name.as_str()))
+            .map(|(name, _)| ManifestError {
+                path: format!("env.{}", name),
+                message: String::from("is required, but has no value"),
+            })
+            .collect();
+        if !missing.is_empty() {
+            return Err(missing);
+        }
+
+        Ok(Overrides {
+            buildpacks: match self.buildpacks.is_empty() {
+                true => None,
+                false => Some(
+                    self.buildpacks
+                        .iter()
+                        .map(|buildpack| Buildpack {
+                            url: &buildpack.url,
+                        })
+                        .collect(),
+                ),
+            },
+            env: match vars.is_empty() {
+                true => None,
+                false => Some(vars),
+            },
+        })
+    }
+}
+
+/// The problems found while reading or validating a manifest.
+#[derive(Default)]
+struct Errors(Vec<ManifestError>);
+
+impl fmt::Display for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let errors: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        write!(f, "{}", errors.join("; "))
+    }
+}
+
+impl Errors {
+    fn push<M: Into<String>>(&mut self, path: &str, message: M) {
+        self.0.push(ManifestError {
+            path: path.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn scripts(&mut self, path: &str, scripts: &ManifestScripts) {
+        for (name, script) in [
+            ("postdeploy", &scripts.postdeploy),
+            ("pr-predestroy", &scripts.pr_predestroy),
+            ("test", &scripts.test),
+            ("test-setup", &scripts.test_setup),
+        ] {
+            if let Some(script) = script {
+                if script.command.trim().is_empty() {
+                    self.push(&format!("{}.{}", path, name), "command must not be empty");
+                }
+            }
+        }
+    }
+
+    fn env(&mut self, path: &str, env: &BTreeMap<String, ManifestEnv>) {
+        for (name, var) in env {
+            let path = format!("{}.{}", path, name);
+            if name.starts_with(|c: char| c.is_ascii_digit())
+                || !is_name(name, |c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                self.push(
+                    &path,
+                    "invalid config var name, only letters, digits and underscores are allowed, and it must not start with a digit",
+                );
+            }
+            match var.generator.as_deref() {
+                Some("secret") | None => {}
+                Some(generator) => self.push(
+                    &format!("{}.generator", path),
+                    format!(
+                        "unknown generator {:?}, only \"secret\" is supported",
+                        generator
+                    ),
+                ),
+            }
+            if var.value.is_some() && var.generator.is_some() {
+                self.push(&path, "value and generator are mutually exclusive");
+            }
+        }
+    }
+
+    fn formation(&mut self, path: &str, formation: &BTreeMap<String, ManifestFormation>) {
+        for (process_type, dynos) in formation {
+            let path = format!("{}.{}", path, process_type);
+            if !is_name(process_type, |c| {
+                c.is_ascii_alphanumeric() || c == '_' || c == '-'
+            }) {
+                self.push(
+                    &path,
+                    "invalid process type, only letters, digits, dashes and underscores are allowed",
+                );
+            }
+            if let Some(size) = &dynos.size {
+                if size.trim().is_empty() {
+                    self.push(&format!("{}.size", path), "must not be empty");
+                }
+            }
+        }
+    }
+
+    fn addons(&mut self, path: &str, addons: &[ManifestAddon]) {
+        let mut attachments = HashSet::new();
+        for (index, addon) in addons.iter().enumerate() {
+            let path = format!("{}[{}]", path, index);
+            let mut plan = addon.plan.splitn(2, ':');
+            let service = plan.next().unwrap_or_default();
+            if service.is_empty() || plan.next() == Some("") {
+                self.push(
+                    &format!("{}.plan", path),
+                    format!(
+                        "invalid plan {:?}, expected service or service:plan",
+                        addon.plan
+                    ),
+                );
+            }
+            if let Some(attachment) = &addon.attachment {
+                if !attachment.starts_with(|c: char| c.is_ascii_uppercase())
+                    || !is_name(attachment, |c| {
+                        c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'
+                    })
+                {
+                    self.push(
+                        &format!("{}.as", path),
+                        "invalid attachment name, only uppercase letters, digits and underscores are allowed, and it must start with a letter",
+                    );
+                } else if !attachments.insert(attachment.as_str()) {
+                    self.push(
+                        &format!("{}.as", path),
+                        format!("attachment name {} is already used", attachment),
+                    );
+                }
+            }
+        }
+    }
+
+    fn buildpacks(&mut self, path: &str, buildpacks: &[ManifestBuildpack]) {
+        for (index, buildpack) in buildpacks.iter().enumerate() {
+            if buildpack.url.trim().is_empty() {
+                self.push(&format!("{}[{}].url", path, index), "must not be empty");
+            }
+        }
+    }
+}
+
+/// Whether `name` is not empty, and only made of characters matching `allowed`.
+fn is_name<F: Fn(char) -> bool>(name: &str, allowed: F) -> bool {
+    !name.is_empty() && name.chars().all(allowed)
+}
@@ -1,18 +1,41 @@
 use crate::framework::response::ApiResult;
 use serde::Deserialize;
 
+#[cfg(all(
+    feature = "addons",
+    feature = "builds",
+    feature = "config_vars",
+    feature = "domains",
+    feature = "formations",
+    feature = "logs"
+))]
+pub mod clone;
 pub mod delete;
 pub mod get;
+pub mod manifest;
 pub mod patch;
 pub mod post;
 pub mod put;
 
+#[cfg(all(
+    feature = "addons",
+    feature = "builds",
+    feature = "config_vars",
+    feature = "domains",
+    feature = "formations",
+    feature = "logs"
+))]
+pub use clone::{AppCloner, CloneAction, CloneItem, ClonePlan, CloneReport, Skipped};
 pub use delete::{AppDelete, AppDisableAcm, AppWebhookDelete, SNIDelete, SSLDelete};
 pub use get::{
     AccountAppList, AppDetails, AppFeatureDetails, AppFeatureList, AppList, AppSetupDetails,
     AppWebhookDeliveryDetails, AppWebhookDeliveryList, AppWebhookDetails, AppWebhookList,
     SNIDetails, SNIList, SSLDetails, SSLList, WebhookEventDetails, WebhookEventList,
 };
+pub use manifest::{
+    AppManifest, ManifestAddon, ManifestBuildpack, ManifestEnv, ManifestEnvironment, ManifestError,
+    ManifestFormation, ManifestScript, ManifestScripts,
+};
 pub use patch::{
     AppFeatureUpdate, AppFeatureUpdateParams, AppRefreshAcm, AppUpdate, AppUpdateParams,
     AppWebhookUpdate, AppWebhookUpdateParams, SNIUpdate, SNIUpdateParams, SSLUpdate,
@@ -25,28 +48,13 @@ pub use post::{
 };
 
 impl ApiResult for App {}
-impl ApiResult for Vec<App> {}
-
 impl ApiResult for AppFeature {}
-impl ApiResult for Vec<AppFeature> {}
-
 impl ApiResult for AppWebhook {}
-impl ApiResult for Vec<AppWebhook> {}
-
 impl ApiResult for AppWebhookDelivery {}
-impl ApiResult for Vec<AppWebhookDelivery> {}
-
 impl ApiResult for AppSetup {}
-impl ApiResult for Vec<AppSetup> {}
-
 impl ApiResult for SNI {}
-impl ApiResult for Vec<SNI> {}
-
 impl ApiResult for SSL {}
-impl ApiResult for Vec<SSL> {}
-
 impl ApiResult for WebhookEvent {}
-impl ApiResult for Vec<WebhookEvent> {}
 
 pub use app_setup::AppSetup;
 pub use sni_endpoints::SNI;
@@ -61,6 +69,7 @@ pub use webhook_event::WebhookEvent;
 ///
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#app)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct App {
     /// ACM status of this app
     pub acm: bool,
@@ -104,55 +113,85 @@ pub struct App {
     pub updated_at: String,
     /// web URL of app
     pub web_url: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// BuildStack struct containing identifier of stack and stack name.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct BuildStack {
     /// identifier of stack
     pub id: String,
     /// stack name
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Owner struct containing email and name or the account.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Owner {
     /// email of owner
     pub email: String,
     /// unique identifier of owner
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Organization struct containing id, name allows you to manage access to a shared group of applications and other resources.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Organization {
     /// unique identifier of organization
     pub id: String,
     /// unique identifier of organization
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Teams struct containing id, name allows you to manage access to a shared group of applications and other resources.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Team {
     /// unique identifier of team
     pub id: String,
     /// unique name of team
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Region struct containing id, name related to the geographic location in which your application may run.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Region {
     /// unique identifier
     pub id: String,
     /// name of region
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Space struct containing id, name and shield related to the app execution environment.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Space {
     /// unique identifier of space
     pub id: String,
@@ -160,15 +199,24 @@ pub struct Space {
     pub name: String,
     /// true if this space has shield enabled
     pub shield: bool,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Stacks are the different application execution environments available in the Heroku platform.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Stack {
     /// unique identifier
     pub id: String,
     /// name of stack
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// App Feature
@@ -182,6 +230,7 @@ pub struct Stack {
 // TODO: (ben) inspect the nullable properties more. As of 20th March 2020, Heroku docs say that none of these properties can be nullable,
 //     but some are... and that's leading so an error decoding response body. e.g. invalid type: null, expected a string.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AppFeature {
     /// when app feature was created
     pub created_at: String,
@@ -203,6 +252,10 @@ pub struct AppFeature {
     pub display_name: Option<String>,
     /// e-mail to send feedback about the feature
     pub feedback_email: Option<String>,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// App Webhook
@@ -213,6 +266,7 @@ pub struct AppFeature {
 ///
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#app-webhook)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AppWebhook {
     /// the app that has the webhook
     pub app: WebhookApp,
@@ -230,15 +284,24 @@ pub struct AppWebhook {
     pub updated_at: String,
     /// the URL where the webhook’s notification requests are sent
     pub url: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// WebhookApp
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct WebhookApp {
     /// unique identifier
     pub id: String,
     /// name of app
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// App Webhook Delivery
@@ -249,6 +312,7 @@ pub struct WebhookApp {
 ///
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#app-webhook-delivery)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AppWebhookDelivery {
     /// when the delivery was created
     pub created_at: String,
@@ -268,19 +332,29 @@ pub struct AppWebhookDelivery {
     pub updated_at: String,
     /// the webhook which we get the deliveries for
     pub webhook: WebhookDeliveryWebhook,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// WebhookDeliveryEvent
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct WebhookDeliveryEvent {
     /// the event’s unique identifier
     pub id: String,
     /// the type of entity that the event is related to
     pub include: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// WebhookDeliveryLastAttempt
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct WebhookDeliveryLastAttempt {
     /// unique identifier of attempt
     pub id: String,
@@ -294,10 +368,15 @@ pub struct WebhookDeliveryLastAttempt {
     pub created_at: String,
     /// when attempt was updated
     pub updated_at: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// WebhookDeliveryWebhook
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct WebhookDeliveryWebhook {
     /// the webhook’s unique identifier
     pub id: String,
@@ -305,6 +384,10 @@ pub struct WebhookDeliveryWebhook {
     /// If sync, Heroku attempts multiple deliveries until the request is successful or a limit is reached.
     /// One of:"notify" or "sync"
     pub level: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 mod app_setup {
@@ -322,6 +405,7 @@ mod app_setup {
     // TODO: (ben) inspect the nullable properties more. As of 20th March 2020, Heroku docs say that none of these properties can be nullable,
     //     but some are... and that's leading so an error decoding response body. e.g. invalid type: null, expected a string.
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AppSetup {
         /// unique identifier of app setup
         pub id: String,
@@ -344,18 +428,28 @@ mod app_setup {
         pub postdeploy: Option<Postdeploy>,
         /// fully qualified success url
         pub resolved_success_url: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct App {
         /// unique identifier
         pub id: String,
         /// name of app
         ///  pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Build {
         /// unique identifier of build
         pub id: String,
@@ -365,14 +459,23 @@ mod app_setup {
         /// Build process output will be available from this URL as a stream. The stream is available as either text/plain or text/event-stream.
         /// Clients should be prepared to handle disconnects and can resume the stream by sending a Range header (for text/plain) or a Last-Event-Id header (for text/event-stream).
         pub output_stream_url: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Postdeploy {
         /// output of the postdeploy script
         pub output: String,
         /// The exit code of the postdeploy script
         pub exit_code: i64,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -388,6 +491,7 @@ mod sni_endpoints {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#sni-endpoint)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct SNI {
         /// raw contents of the public certificate chain (eg: .crt or .pem file)
         pub certificate_chain: String,
@@ -402,6 +506,10 @@ mod sni_endpoints {
         pub name: String,
         /// when SNI endpoint was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -417,6 +525,7 @@ mod ssl_endpoints {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#sni-endpoint)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct SSL {
         /// app
         pub app: App,
@@ -433,14 +542,23 @@ mod ssl_endpoints {
         pub name: String,
         /// when endpoint was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct App {
         /// unique identifier
         pub id: String,
         /// name of app
         ///  pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -457,6 +575,7 @@ mod webhook_event {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#app-webhook-event)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct WebhookEvent {
         /// when event was created
         pub created_at: DateTime<Utc>,
@@ -468,9 +587,14 @@ mod webhook_event {
         pub payload: Payload,
         /// when the event was last updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Payload {
         /// the type of event that occurred
         pub action: String,
@@ -484,13 +608,22 @@ mod webhook_event {
         pub resource: String,
         /// the version of the details provided for the event
         pub version: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Actor {
         /// unique email address
         pub email: String,
         /// identifier of an account
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
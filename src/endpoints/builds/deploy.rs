@@ -0,0 +1,246 @@
+//! Deploying a local directory: source upload, build and release.
+use super::{Build, BuildCreate, BuildCreateParams, BuildDetails, BuildOutputStream};
+use super::{BuildpackParam, SourceBlobParam};
+use crate::endpoints::misc::SourceCreate;
+use crate::endpoints::releases::{Release, ReleaseInfo};
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    response::{match_failure, ApiResponse, HerokuApiFailure},
+    transport::HttpRequest,
+    waiter::{WaitError, Waiter},
+    HttpApiClient,
+};
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Deploy a local directory to an app, like `git push heroku`, without git.
+///
+/// The directory is archived as a gzipped tarball, without the files matched by its `.gitignore` and
+/// `.slugignore` files, nor `.git`. The tarball is uploaded to a new source, built, and released.
+///
+/// Returns the final build, and the release it created if it succeeded. A failed build is not an error:
+/// check `build.status`. Neither is a successful build whose release is still not attached to it after
+/// 30 more poll intervals, in which case `release` is `None` as well.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::builds::{deploy_directory, DeployOptions};
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let options = DeployOptions::new()
+///     .version("v1.4.2")
+///     .on_output(|line| println!("remote: {}", line));
+/// let deploy = deploy_directory(&api_client, "APP_ID", "./my-app", options).unwrap();
+///
+/// match deploy.release {
+///     Some(release) => println!("Released v{}", release.version),
+///     None => println!("Build {}", deploy.build.status),
+/// }
+/// ```
+pub fn deploy_directory<P: AsRef<Path>>(
+    client: &HttpApiClient,
+    app_id: &str,
+    path: P,
+    mut options: DeployOptions,
+) -> ApiResponse<Deploy> {
+    let tarball = archive(path.as_ref())?;
+
+    let source = client.request(&SourceCreate {})?.source_blob;
+    let url = url::Url::parse(&source.put_url)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut upload = HttpRequest::new(http::Method::PUT, url);
+    upload.body = Some(tarball);
+    let response = client.transport().send(upload)?;
+    if !response.status.is_success() {
+        return Err(match_failure(response));
+    }
+
+    let build = client.request(&BuildCreate {
+        app_id,
+        params: BuildCreateParams {
+            buildpacks: match options.buildpacks.is_empty() {
+                true => None,
+                false => Some(
+                    options
+                        .buildpacks
+                        .iter()
+                        .map(|url| BuildpackParam { url, name: "" })
+                        .collect(),
+                ),
+            },
+            source_blob: SourceBlobParam {
+                checksum: None,
+                url: &source.get_url,
+                version: options.version.as_deref(),
+            },
+        },
+    })?;
+
+    let poll_interval = options.poll_interval;
+    let output = &mut options.on_output;
+    BuildOutputStream::for_build(client, &build)
+        .retry_delay(poll_interval)
+        .for_each_line(|line| output(line))?;
+
+    let details = BuildDetails {
+        app_id,
+        build_id: &build.id,
+    };
+    let build = Waiter::for_build(client, &details)
+        .poll_interval(poll_interval)
+        .wait()
+        .map_err(failure)?;
+    // The release of a successful build is attached to it shortly after.
+    let build = match build.status == "succeeded" && build.release.is_none() {
+        true => match Waiter::new(client, &details, |build| build.release.is_some())
+            .poll_interval(poll_interval)
+            .timeout(poll_interval * RELEASE_POLLS)
+            .wait()
+        {
+            Ok(build) | Err(WaitError::TimedOut(build)) => build,
+            Err(e) => return Err(failure(e)),
+        },
+        false => build,
+    };
+    let release_id = match (&build.release, build.status.as_str()) {
+        (Some(release), "succeeded") => release.id.clone(),
+        _ => {
+            return Ok(Deploy {
+                build,
+                release: None,
+            })
+        }
+    };
+
+    let release_info = ReleaseInfo {
+        app_id,
+        release_id: &release_id,
+    };
+    let release = client.request(&release_info)?;
+    if let Some(stream) = BuildOutputStream::for_release(client, &release) {
+        stream
+            .retry_delay(poll_interval)
+            .for_each_line(|line| output(line))?;
+    }
+    let release = Waiter::for_release(client, &release_info)
+        .poll_interval(poll_interval)
+        .wait()
+        .map_err(failure)?;
+
+    Ok(Deploy {
+        build,
+        release: Some(release),
+    })
+}
+
+/// How many poll intervals a successful build is waited on for its release, before giving up on it.
+const RELEASE_POLLS: u32 = 30;
+
+/// The failed request of a wait, which is neither timed out nor cancelled.
+fn failure<T>(error: WaitError<T>) -> HerokuApiFailure {
+    match error {
+        WaitError::Api(error) => error,
+        WaitError::TimedOut(_) | WaitError::Cancelled(_) => {
+            unreachable!("the build and release waits have no timeout")
+        }
+    }
+}
+
+/// Archive a directory as a gzipped tarball, without the files ignored by `.gitignore` and `.slugignore`.
+///
+/// Paths in the archive are relative to the directory, as Heroku expects.
+pub fn archive(path: &Path) -> io::Result<Vec<u8>> {
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut tar = tar::Builder::new(encoder);
+    tar.follow_symlinks(false);
+
+    let walk = ignore::WalkBuilder::new(path)
+        .hidden(false)
+        .parents(false)
+        .git_global(false)
+        .git_exclude(false)
+        .require_git(false)
+        .add_custom_ignore_filename(".slugignore")
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    for entry in walk {
+        let entry = entry.map_err(io::Error::other)?;
+        let relative = entry
+            .path()
+            .strip_prefix(path)
+            .expect("walked paths are inside the directory");
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        tar.append_path_with_name(entry.path(), relative)?;
+    }
+
+    tar.into_inner()?.finish()
+}
+
+/// Options of [`deploy_directory`][deploy].
+///
+/// [deploy]: fn.deploy_directory.html
+pub struct DeployOptions {
+    version: Option<String>,
+    buildpacks: Vec<String>,
+    on_output: Box<dyn FnMut(&str)>,
+    poll_interval: Duration,
+}
+
+impl Default for DeployOptions {
+    fn default() -> Self {
+        DeployOptions {
+            version: None,
+            buildpacks: Vec::new(),
+            on_output: Box::new(|_| {}),
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+impl DeployOptions {
+    pub fn new() -> DeployOptions {
+        DeployOptions::default()
+    }
+
+    /// # version: the version label of the source, e.g. a git sha or a release tag
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// # buildpack: a buildpack to build with instead of the app's, can be repeated
+    pub fn buildpack(mut self, url: &str) -> Self {
+        self.buildpacks.push(url.to_string());
+        self
+    }
+
+    /// # on_output: called with every line of the build and release phase output
+    pub fn on_output<F: FnMut(&str) + 'static>(mut self, on_output: F) -> Self {
+        self.on_output = Box::new(on_output);
+        self
+    }
+
+    /// # poll_interval: how long to wait between two checks of the build and release, 2 seconds by default
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+/// The result of [`deploy_directory`][deploy].
+///
+/// [deploy]: fn.deploy_directory.html
+#[derive(Debug, Clone)]
+pub struct Deploy {
+    /// the build, once done
+    pub build: Build,
+    /// the release created by the build, once done, or `None` if the build failed, or if its release was
+    /// still not attached to it after 30 poll intervals
+    pub release: Option<Release>,
+}
@@ -1,22 +1,25 @@
 use crate::framework::response::ApiResult;
 use serde::Deserialize;
 
+#[cfg(feature = "deploy")]
+pub mod deploy;
 pub mod delete;
 pub mod get;
+pub mod output;
 pub mod patch;
 pub mod post;
 pub mod put;
 
 pub use delete::BuildDelete;
+#[cfg(feature = "deploy")]
+pub use deploy::{deploy_directory, Deploy, DeployOptions};
 pub use get::{BuildDetails, BuildList, BuildPackInstallationList};
+pub use output::BuildOutputStream;
 pub use post::{BuildCreate, BuildCreateParams, BuildpackParam, SourceBlobParam};
 pub use put::{BuildpackInstallationUpdate, BuildpackInstallationUpdateParams};
 
 impl ApiResult for Build {}
-impl ApiResult for Vec<Build> {}
-
 impl ApiResult for BuildpackInstallation {}
-impl ApiResult for Vec<BuildpackInstallation> {}
 
 /// Build
 ///
@@ -27,6 +30,7 @@ impl ApiResult for Vec<BuildpackInstallation> {}
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#build)
 //  TODO: (ben) inspect the nullable properties more. As of 22th March 2020, Heroku docs do not properly reflect the nullable properties of this struct.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Build {
     /// App struct used for the Build
     pub app: App,
@@ -39,6 +43,7 @@ pub struct Build {
     /// Build process output will be available from this URL as a stream.
     /// The stream is available as either text/plain or text/event-stream.
     /// Clients should be prepared to handle disconnects and can resume the stream by sending a Range header (for text/plain) or a Last-Event-Id header (for text/event-stream).
+    /// Follow it with `BuildOutputStream` for text/plain, or `framework::sse::EventStream` for text/event-stream.
     pub output_stream_url: String,
     /// Source blob struct containing url, checksum and version
     pub source_blob: SourceBlob,
@@ -54,26 +59,41 @@ pub struct Build {
     pub updated_at: String,
     /// identifier of an account
     pub user: User,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// App struct used for the Build
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct App {
     /// unique identifier
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Buildpack
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Buildpack {
     /// location of the buildpack for the app. Either a url (unofficial buildpacks) or an internal urn (heroku official buildpacks).
     pub url: String,
     /// either the Buildpack Registry name or a URL of the buildpack for the app
     pub name: Option<String>,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// SourceBlob
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SourceBlob {
     /// an optional checksum of the gzipped tarball for verifying its integrity
     pub checksum: Option<String>,
@@ -81,29 +101,48 @@ pub struct SourceBlob {
     pub url: String,
     /// Version of the gzipped tarball.
     pub version: Option<String>,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// release resulting from the build
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Release {
     /// unique identifier of release
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// slug created by this build
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Slug {
     /// unique identifier of slug
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Account
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct User {
     /// identifier of an account
     pub id: String,
     /// unique email address
     pub email: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Buildpack Installations
@@ -112,11 +151,16 @@ pub struct User {
 ///
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#buildpack-installations)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct BuildpackInstallation {
     /// determines the order in which the buildpacks will execute
     pub ordinal: i64,
     /// The buildpack that will be executed
     pub buildpack: Buildpack,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// The struct to update BuildpackInstallation
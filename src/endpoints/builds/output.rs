@@ -0,0 +1,229 @@
+//! Following the output stream of a build, or of a release phase.
+use super::{Build, BuildDetails};
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    reconnect::{self, LineBuffer, Reconnect},
+    response::{match_failure, ApiResponse},
+    transport::HttpResponse,
+    HttpApiClient,
+};
+use std::io::{self, BufReader, Read};
+use std::time::Duration;
+
+/// BuildOutputStream
+///
+/// An iterator over the lines of an `output_stream_url`, which follows the stream until the build finishes.
+///
+/// Heroku may close the stream before the build is done. When that happens the stream reconnects,
+/// and resumes where it stopped with a `Range` header, so no line is lost nor repeated.
+///
+/// Works for `Build.output_stream_url`, the build `output_stream_url` of an app setup, and `Release.output_stream_url`.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::builds::BuildOutputStream;
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let build = api_client.request(&BuildDetails::new("APP_ID", "BUILD_ID")).unwrap();
+///
+/// for line in BuildOutputStream::for_build(&api_client, &build) {
+///     match line {
+///         Ok(line) => println!("{}", line),
+///         Err(e) => println!("Error: {}", e),
+///     }
+/// }
+/// ```
+pub struct BuildOutputStream<'a> {
+    client: &'a HttpApiClient,
+    url: String,
+    /// bytes of the stream received so far
+    offset: u64,
+    reader: Option<BufReader<Box<dyn Read + Send>>>,
+    line: LineBuffer,
+    reconnect: Reconnect<'a>,
+}
+
+impl<'a> BuildOutputStream<'a> {
+    /// Follow any output stream URL. The stream ends as soon as Heroku closes it.
+    ///
+    /// Use [`until`][until] to keep following it until the build or release is done.
+    ///
+    /// [until]: #method.until
+    pub fn new(client: &'a HttpApiClient, output_stream_url: &str) -> BuildOutputStream<'a> {
+        BuildOutputStream {
+            client,
+            url: output_stream_url.to_string(),
+            offset: 0,
+            reader: None,
+            line: LineBuffer::default(),
+            reconnect: Reconnect::new(Duration::from_secs(2)),
+        }
+    }
+
+    /// Follow the output of a build, until its status is no longer `pending`.
+    pub fn for_build(client: &'a HttpApiClient, build: &Build) -> BuildOutputStream<'a> {
+        let app_id = build.app.id.clone();
+        let build_id = build.id.clone();
+        BuildOutputStream::new(client, &build.output_stream_url).until(move || {
+            let build = client.request(&BuildDetails {
+                app_id: &app_id,
+                build_id: &build_id,
+            })?;
+            Ok(build.status != "pending")
+        })
+    }
+
+    /// Follow the release phase output of a release, until its status is no longer `pending`.
+    ///
+    /// Returns `None` if the release has no release phase.
+    #[cfg(feature = "releases")]
+    pub fn for_release(
+        client: &'a HttpApiClient,
+        release: &crate::endpoints::releases::Release,
+    ) -> Option<BuildOutputStream<'a>> {
+        let app_id = release.app.id.clone();
+        let release_id = release.id.clone();
+        let url = release.output_stream_url.as_ref()?;
+        Some(BuildOutputStream::new(client, url).until(move || {
+            let release = client.request(&crate::endpoints::releases::ReleaseInfo {
+                app_id: &app_id,
+                release_id: &release_id,
+            })?;
+            Ok(release.status != "pending")
+        }))
+    }
+
+    /// # until: tells whether the output is complete
+    ///
+    /// Called every time Heroku closes the stream. While it returns `false`, the stream reconnects and resumes.
+    pub fn until<F>(mut self, finished: F) -> Self
+    where
+        F: FnMut() -> ApiResponse<bool> + 'a,
+    {
+        self.reconnect.until(finished);
+        self
+    }
+
+    /// # max_reconnects: how many times in a row to reconnect after a failure, 10 by default
+    pub fn max_reconnects(mut self, max_reconnects: u32) -> Self {
+        self.reconnect.max_reconnects = max_reconnects;
+        self
+    }
+
+    /// # retry_delay: how long to wait before reconnecting, 2 seconds by default
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.reconnect.retry_delay = retry_delay;
+        self
+    }
+
+    /// Call `callback` with every line of the output, until the build finishes.
+    ///
+    /// # Example:
+    ///
+    /// ```rust,no_run
+    /// use heroku_rs::endpoints::builds::BuildOutputStream;
+    /// use heroku_rs::prelude::*;
+    ///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+    ///
+    /// let build = api_client.request(&BuildDetails::new("APP_ID", "BUILD_ID")).unwrap();
+    ///
+    /// BuildOutputStream::for_build(&api_client, &build)
+    ///     .for_each_line(|line| println!("{}", line))
+    ///     .unwrap();
+    /// ```
+    pub fn for_each_line<F: FnMut(&str)>(self, mut callback: F) -> ApiResponse<()> {
+        for line in self {
+            callback(&line?);
+        }
+        Ok(())
+    }
+
+    fn connect(&mut self) -> ApiResponse<()> {
+        let mut request = reconnect::get(&self.url)?;
+        if self.offset > 0 {
+            let range = format!("bytes={}-", self.offset);
+            request.headers.insert(
+                http::header::RANGE,
+                http::HeaderValue::from_str(&range).expect("a byte range is a valid header"),
+            );
+        }
+
+        let response = self.client.transport().send(request)?;
+        let mut body = response.body;
+        match response.status {
+            http::StatusCode::PARTIAL_CONTENT => {}
+            // The server ignored the range and sent everything again, skip what was already read.
+            status if status.is_success() => {
+                io::copy(&mut (&mut body).take(self.offset), &mut io::sink())?;
+            }
+            // Nothing was written since the last connection.
+            http::StatusCode::RANGE_NOT_SATISFIABLE => body = Box::new(io::empty()),
+            status => {
+                return Err(match_failure(HttpResponse::new(
+                    status,
+                    response.headers,
+                    body,
+                )))
+            }
+        }
+        self.reader = Some(BufReader::new(body));
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for BuildOutputStream<'a> {
+    type Item = ApiResponse<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.reconnect.is_done() {
+                return None;
+            }
+            if self.reader.is_none() {
+                if let Err(e) = self.connect() {
+                    match self.reconnect.retry(e) {
+                        Some(e) => return Some(Err(e)),
+                        None => continue,
+                    }
+                }
+            }
+
+            let reader = self.reader.as_mut().expect("connected above");
+            let before = self.line.len();
+            let result = self.line.read_from(reader);
+            // Bytes read before a failure are kept in the line, so they count as received too.
+            self.offset += (self.line.len() - before) as u64;
+            match result {
+                Ok(0) => {
+                    self.reader = None;
+                    match self.reconnect.closed() {
+                        Ok(true) => {
+                            if !self.line.is_empty() {
+                                return Some(Ok(self.line.take()));
+                            }
+                            return None;
+                        }
+                        // Still running, reconnect for more output.
+                        Ok(false) => {}
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                Ok(_) => {
+                    self.reconnect.received();
+                    if self.line.is_complete() {
+                        return Some(Ok(self.line.take()));
+                    }
+                }
+                // A partial line stays buffered and is completed after reconnecting.
+                Err(e) => {
+                    self.reader = None;
+                    if let Some(e) = self.reconnect.retry(e.into()) {
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+    }
+}
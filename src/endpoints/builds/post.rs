@@ -17,7 +17,7 @@ use crate::framework::endpoint::{HerokuEndpoint, Method};
 ///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
 ///
 /// let blob_url = "https://example.com/source.tgz?token=xyz";
-/// 
+///
 ///  let response = api_client.request(
 ///      &BuildCreate::new("APP_ID", blob_url)
 ///          .checksum("SHA256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
@@ -123,7 +123,8 @@ pub struct SourceBlobParam<'a> {
 pub struct BuildpackParam<'a> {
     /// location of the buildpack for the app. Either a url (unofficial buildpacks) or an internal urn (heroku official buildpacks).
     pub url: &'a str,
-    /// either the Buildpack Registry name or a URL of the buildpack for the app
+    /// either the Buildpack Registry name or a URL of the buildpack for the app, not sent when empty
+    #[serde(skip_serializing_if = "str::is_empty")]
     pub name: &'a str,
 }
 
@@ -19,10 +19,7 @@ pub use post::{
 pub use patch::{TeamCollaboratorUpdate, TeamCollaboratorUpdateParams};
 
 impl ApiResult for Collaborator {}
-impl ApiResult for Vec<Collaborator> {}
-
 impl ApiResult for TeamCollaborator {}
-impl ApiResult for Vec<TeamCollaborator> {}
 
 pub use team_collaborator::TeamCollaborator;
 
@@ -37,6 +34,7 @@ pub use team_collaborator::TeamCollaborator;
 // TODO: (ben) inspect the nullable properties more. As of 22th March 2020, Heroku docs do not properly reflect nullable properties but some are,
 //     and that's leading to an error decoding response body. e.g.  missing field `permissions` at line 1 column 297
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Collaborator {
     /// App struct
     pub app: App,
@@ -52,28 +50,43 @@ pub struct Collaborator {
     pub updated_at: String,
     /// Account struct
     pub user: User,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// App struct
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct App {
     /// name of app. pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
     pub name: String,
     /// unique identifier
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Permission struct
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Permission {
     /// permission name
     pub name: String,
     /// brief description about the permission
     pub description: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Account struct
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct User {
     /// unique email address of account
     pub email: String,
@@ -81,6 +94,10 @@ pub struct User {
     pub federated: bool,
     // unique identifier of an account
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 mod team_collaborator {
@@ -98,6 +115,7 @@ mod team_collaborator {
     // TODO: (ben) inspect the nullable properties more. As of 5th April 2020, Heroku docs do not properly reflect nullable properties.
     //     and that's leading to an error decoding response body. e.g.  missing field `permissions`
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TeamCollaborator {
         /// App struct
         pub app: App,
@@ -113,27 +131,42 @@ mod team_collaborator {
         pub updated_at: DateTime<Utc>,
         /// Account struct
         pub user: User,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     /// App struct
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct App {
         /// name of app. pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
         pub name: String,
         /// unique identifier
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// Permission struct
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Permission {
         /// permission name
         pub name: String,
         /// brief description about the permission
         pub description: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// Account struct
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct User {
         /// unique email address of account
         pub email: String,
@@ -141,5 +174,9 @@ mod team_collaborator {
         pub federated: bool,
         // unique identifier of an account
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
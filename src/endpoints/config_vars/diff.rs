@@ -0,0 +1,248 @@
+//! Comparing config vars with a desired configuration, and applying only the difference.
+use super::{
+    AppConfigVarDelete, AppConfigVarDetails, PipelineConfigVarDelete, PipelineConfigVarDetails,
+};
+use crate::framework::{apiclient::HerokuApiClient, response::ApiResponse, HttpApiClient};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// The difference between the config vars of an app, or of a pipeline stage, and a desired configuration.
+///
+/// Changes are sorted by key, and values are masked when displayed so a diff can be logged safely.
+/// Applying a diff sends a single PATCH with the added and changed keys, and `null` for the removed ones.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::config_vars::ConfigDiff;
+/// use heroku_rs::prelude::*;
+/// use std::collections::HashMap;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let mut desired = HashMap::new();
+/// desired.insert(String::from("RAILS_ENV"), String::from("production"));
+///
+/// let diff = ConfigDiff::for_app(&api_client, "APP_ID", &desired)
+///     .and_then(|diff| diff.protect_addon_keys(&api_client, "APP_ID"))
+///     .unwrap();
+///
+/// println!("{}", diff);
+/// diff.apply_to_app(&api_client, "APP_ID").unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfigDiff {
+    /// the changes to make, sorted by key
+    pub changes: Vec<ConfigChange>,
+    /// the keys which would have been removed, but are protected
+    pub protected: Vec<String>,
+    current: HashMap<String, Option<String>>,
+}
+
+/// A change of a single config var.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigChange {
+    Added {
+        key: String,
+        value: String,
+    },
+    Changed {
+        key: String,
+        from: String,
+        to: String,
+    },
+    Removed {
+        key: String,
+        value: String,
+    },
+}
+
+impl ConfigChange {
+    /// The key of the config var.
+    pub fn key(&self) -> &str {
+        match self {
+            ConfigChange::Added { key, .. }
+            | ConfigChange::Changed { key, .. }
+            | ConfigChange::Removed { key, .. } => key,
+        }
+    }
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigChange::Added { key, value } => write!(f, "+ {}={}", key, mask(value)),
+            ConfigChange::Changed { key, from, to } => {
+                write!(f, "~ {}={} -> {}", key, mask(from), mask(to))
+            }
+            ConfigChange::Removed { key, value } => write!(f, "- {}={}", key, mask(value)),
+        }
+    }
+}
+
+impl ConfigDiff {
+    /// Compare `current` config vars, as returned by the Heroku API, with `desired`.
+    ///
+    /// Keys whose current value is `null` are considered unset.
+    pub fn new(
+        current: HashMap<String, Option<String>>,
+        desired: &HashMap<String, String>,
+    ) -> ConfigDiff {
+        let desired: BTreeMap<&String, &String> = desired.iter().collect();
+        let mut changes = Vec::new();
+        for (key, value) in &desired {
+            match current.get(*key) {
+                Some(Some(old)) if old == *value => {}
+                Some(Some(old)) => changes.push(ConfigChange::Changed {
+                    key: key.to_string(),
+                    from: old.clone(),
+                    to: value.to_string(),
+                }),
+                _ => changes.push(ConfigChange::Added {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                }),
+            }
+        }
+        for (key, value) in current.iter().collect::<BTreeMap<_, _>>() {
+            if let (Some(value), false) = (value, desired.contains_key(key)) {
+                changes.push(ConfigChange::Removed {
+                    key: key.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+        changes.sort_by(|a, b| a.key().cmp(b.key()));
+
+        ConfigDiff {
+            changes,
+            protected: Vec::new(),
+            current,
+        }
+    }
+
+    /// Compare the config vars of an app with `desired`.
+    pub fn for_app(
+        client: &HttpApiClient,
+        app_id: &str,
+        desired: &HashMap<String, String>,
+    ) -> ApiResponse<ConfigDiff> {
+        let current = client.request(&AppConfigVarDetails { app_id })?;
+        Ok(ConfigDiff::new(current, desired))
+    }
+
+    /// Compare the config vars of a pipeline stage with `desired`.
+    pub fn for_pipeline_stage(
+        client: &HttpApiClient,
+        pipeline_id: &str,
+        stage_id: &str,
+        desired: &HashMap<String, String>,
+    ) -> ApiResponse<ConfigDiff> {
+        let current = client.request(&PipelineConfigVarDetails {
+            pipeline_id,
+            stage_id,
+        })?;
+        Ok(ConfigDiff::new(current, desired))
+    }
+
+    /// Never remove these keys. They can still be added or changed.
+    pub fn protect<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for key in keys {
+            let key = key.as_ref();
+            let removed = self.changes.iter().position(|change| match change {
+                ConfigChange::Removed { key: removed, .. } => removed == key,
+                _ => false,
+            });
+            if let Some(position) = removed {
+                self.changes.remove(position);
+                self.protected.push(key.to_string());
+            }
+        }
+        self.protected.sort();
+        self
+    }
+
+    /// Protect the keys set by the add-ons attached to the app, e.g. `DATABASE_URL`, from removal.
+    #[cfg(feature = "addons")]
+    pub fn protect_addon_keys(self, client: &HttpApiClient, app_id: &str) -> ApiResponse<Self> {
+        let addons = client.request(&crate::endpoints::addons::AddonListByApp { app_id })?;
+        Ok(self.protect(addons.iter().flat_map(|addon| addon.config_vars.iter())))
+    }
+
+    /// Whether there is nothing to change.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The body of the PATCH request applying the diff, where `None` removes a key.
+    pub fn patch(&self) -> HashMap<String, Option<String>> {
+        self.changes
+            .iter()
+            .map(|change| match change {
+                ConfigChange::Added { key, value } => (key.clone(), Some(value.clone())),
+                ConfigChange::Changed { key, to, .. } => (key.clone(), Some(to.clone())),
+                ConfigChange::Removed { key, .. } => (key.clone(), None),
+            })
+            .collect()
+    }
+
+    /// Apply the diff to the config vars of an app, and return the resulting config vars.
+    ///
+    /// No request is made when the diff is empty.
+    pub fn apply_to_app(
+        &self,
+        client: &HttpApiClient,
+        app_id: &str,
+    ) -> ApiResponse<HashMap<String, Option<String>>> {
+        if self.is_empty() {
+            return Ok(self.current.clone());
+        }
+        client.request(&AppConfigVarDelete {
+            app_id,
+            params: self.patch(),
+        })
+    }
+
+    /// Apply the diff to the config vars of a pipeline stage, and return the resulting config vars.
+    ///
+    /// No request is made when the diff is empty.
+    pub fn apply_to_pipeline_stage(
+        &self,
+        client: &HttpApiClient,
+        pipeline_id: &str,
+        stage_id: &str,
+    ) -> ApiResponse<HashMap<String, Option<String>>> {
+        if self.is_empty() {
+            return Ok(self.current.clone());
+        }
+        client.request(&PipelineConfigVarDelete {
+            pipeline_id,
+            stage_id,
+            params: self.patch(),
+        })
+    }
+}
+
+impl fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        for key in &self.protected {
+            writeln!(f, "= {} (protected)", key)?;
+        }
+        Ok(())
+    }
+}
+
+/// Hide a config var value, keeping the first characters of long values so changes can be told apart.
+fn mask(value: &str) -> String {
+    match value.chars().count() {
+        0 => String::new(),
+        count if count < 12 => String::from("********"),
+        _ => format!("{}********", value.chars().take(4).collect::<String>()),
+    }
+}
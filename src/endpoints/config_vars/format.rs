@@ -0,0 +1,289 @@
+//! Reading and writing config vars as `.env`, JSON and YAML files.
+//!
+//! Config vars are written sorted by key, and keys whose value is `null` are left out.
+//! Parsed files give the update maps expected by [`AppConfigVarUpdate`][update].
+//!
+//! # Example:
+//!
+//! ```rust,no_run
+//! use heroku_rs::endpoints::config_vars::format;
+//! use heroku_rs::prelude::*;
+//!#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+//!
+//! let config = api_client.request(&AppConfigVarDetails::new("STAGING_APP_ID")).unwrap();
+//! std::fs::write(".env", format::to_env(&config)).unwrap();
+//!
+//! let params = format::from_env(&std::fs::read_to_string(".env").unwrap()).unwrap();
+//! api_client.request(&AppConfigVarUpdate::new("REVIEW_APP_ID", params)).unwrap();
+//! ```
+//!
+//! [update]: ../struct.AppConfigVarUpdate.html
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The file formats config vars can be read from and written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `KEY=value` lines, as read by `heroku local` and most dotenv libraries
+    Env,
+    /// a JSON object of strings
+    Json,
+    /// a YAML mapping of strings
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl Format {
+    /// Guess the format of a file from its name, e.g. `.env`, `staging.env`, `config.json` or `config.yml`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Format> {
+        let path = path.as_ref();
+        if path.file_name()? == ".env" {
+            return Some(Format::Env);
+        }
+        path.extension()?.to_str()?.parse().ok()
+    }
+
+    /// Write config vars in this format.
+    pub fn serialize(self, vars: &HashMap<String, Option<String>>) -> String {
+        match self {
+            Format::Env => to_env(vars),
+            Format::Json => to_json(vars),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => to_yaml(vars),
+        }
+    }
+
+    /// Read config vars in this format.
+    pub fn parse(self, input: &str) -> Result<HashMap<String, String>, FormatError> {
+        match self {
+            Format::Env => from_env(input),
+            Format::Json => from_json(input),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => from_yaml(input),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_ascii_lowercase().as_str() {
+            "env" | "dotenv" => Ok(Format::Env),
+            "json" => Ok(Format::Json),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Ok(Format::Yaml),
+            _ => Err(FormatError::UnknownFormat(format.to_string())),
+        }
+    }
+}
+
+/// Why config vars could not be parsed.
+#[derive(Debug)]
+pub enum FormatError {
+    /// A line of a `.env` file is invalid
+    Env { line: usize, message: String },
+    /// The JSON is invalid, or is not an object
+    Json(serde_json::Error),
+    /// The YAML is invalid, or is not a mapping
+    #[cfg(feature = "yaml")]
+    Yaml(serde_yaml::Error),
+    /// The value of this key is a list or an object
+    InvalidValue(String),
+    /// The format is not supported
+    UnknownFormat(String),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormatError::Env { line, message } => write!(f, "Line {}: {}", line, message),
+            FormatError::Json(err) => write!(f, "{}", err),
+            #[cfg(feature = "yaml")]
+            FormatError::Yaml(err) => write!(f, "{}", err),
+            FormatError::InvalidValue(key) => write!(f, "The value of {} is not a string", key),
+            FormatError::UnknownFormat(format) => write!(f, "Unknown format {}", format),
+        }
+    }
+}
+
+impl Error for FormatError {}
+
+/// Write config vars as `KEY=value` lines, double-quoting and escaping the values which need it.
+pub fn to_env(vars: &HashMap<String, Option<String>>) -> String {
+    let mut env = String::new();
+    for (key, value) in sorted(vars) {
+        env.push_str(key);
+        env.push('=');
+        if value.chars().all(is_unquoted) {
+            env.push_str(value);
+        } else {
+            env.push('"');
+            for c in value.chars() {
+                match c {
+                    '\n' => env.push_str("\\n"),
+                    '\r' => env.push_str("\\r"),
+                    '\t' => env.push_str("\\t"),
+                    '\\' | '"' | '$' | '`' => {
+                        env.push('\\');
+                        env.push(c);
+                    }
+                    _ => env.push(c),
+                }
+            }
+            env.push('"');
+        }
+        env.push('\n');
+    }
+    env
+}
+
+/// Read `KEY=value` lines.
+///
+/// Blank lines, `# comments` and `export` prefixes are ignored. Values can be unquoted, single-quoted and
+/// taken literally, or double-quoted with `\n`, `\r`, `\t`, `\\`, `\"` and `\$` escapes. Quoted values can
+/// span several lines.
+pub fn from_env(input: &str) -> Result<HashMap<String, String>, FormatError> {
+    let mut vars = HashMap::new();
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line));
+    while let Some((number, line)) = lines.next() {
+        let error = |message: &str| FormatError::Env {
+            line: number,
+            message: message.to_string(),
+        };
+        let line = line.trim_start();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected KEY=value"))?;
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(error("invalid key"));
+        }
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ '"') | Some(quote @ '\'') => {
+                let (value, rest) = quoted(quote, &value[1..], &mut lines)
+                    .ok_or_else(|| error("unterminated quoted value"))?;
+                let rest = rest.trim_start();
+                if !rest.is_empty() && !rest.starts_with('#') {
+                    return Err(error("unexpected characters after the quoted value"));
+                }
+                value
+            }
+            _ => match value.find(" #").or_else(|| value.find("\t#")) {
+                Some(comment) => value[..comment].trim_end().to_string(),
+                None => value.trim_end().to_string(),
+            },
+        };
+        vars.insert(key.to_string(), value);
+    }
+    Ok(vars)
+}
+
+/// Read a quoted value starting right after its opening quote, and pulling more lines until it is closed.
+///
+/// Returns the value and what follows the closing quote on its line.
+fn quoted<'a, I>(quote: char, mut line: &'a str, lines: &mut I) -> Option<(String, &'a str)>
+where
+    I: Iterator<Item = (usize, &'a str)>,
+{
+    let mut value = String::new();
+    loop {
+        let mut chars = line.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                c if c == quote => return Some((value, &line[index + 1..])),
+                '\\' if quote == '"' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some(c @ '\\') | Some(c @ '"') | Some(c @ '$') | Some(c @ '`') => value.push(c),
+                    Some(c) => {
+                        value.push('\\');
+                        value.push(c);
+                    }
+                    None => value.push('\\'),
+                },
+                c => value.push(c),
+            }
+        }
+        line = lines.next()?.1;
+        value.push('\n');
+    }
+}
+
+/// Write config vars as a JSON object.
+pub fn to_json(vars: &HashMap<String, Option<String>>) -> String {
+    let vars: BTreeMap<&str, &str> = sorted(vars).collect();
+    let mut json = serde_json::to_string_pretty(&vars).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+/// Read a JSON object. Numbers and booleans are read as strings, and `null` values are ignored.
+pub fn from_json(input: &str) -> Result<HashMap<String, String>, FormatError> {
+    let vars: BTreeMap<String, serde_json::Value> =
+        serde_json::from_str(input).map_err(FormatError::Json)?;
+    let mut params = HashMap::new();
+    for (key, value) in vars {
+        let value = match value {
+            serde_json::Value::Null => continue,
+            serde_json::Value::String(value) => value,
+            serde_json::Value::Bool(value) => value.to_string(),
+            serde_json::Value::Number(value) => value.to_string(),
+            _ => return Err(FormatError::InvalidValue(key)),
+        };
+        params.insert(key, value);
+    }
+    Ok(params)
+}
+
+/// Write config vars as a YAML mapping.
+#[cfg(feature = "yaml")]
+pub fn to_yaml(vars: &HashMap<String, Option<String>>) -> String {
+    let vars: BTreeMap<&str, &str> = sorted(vars).collect();
+    serde_yaml::to_string(&vars).unwrap_or_default()
+}
+
+/// Read a YAML mapping. Numbers and booleans are read as strings, and `null` values are ignored.
+#[cfg(feature = "yaml")]
+pub fn from_yaml(input: &str) -> Result<HashMap<String, String>, FormatError> {
+    let vars: BTreeMap<String, serde_yaml::Value> =
+        serde_yaml::from_str(input).map_err(FormatError::Yaml)?;
+    let mut params = HashMap::new();
+    for (key, value) in vars {
+        let value = match value {
+            serde_yaml::Value::Null => continue,
+            serde_yaml::Value::String(value) => value,
+            serde_yaml::Value::Bool(value) => value.to_string(),
+            serde_yaml::Value::Number(value) => value.to_string(),
+            _ => return Err(FormatError::InvalidValue(key)),
+        };
+        params.insert(key, value);
+    }
+    Ok(params)
+}
+
+/// The set config vars, sorted by key.
+fn sorted(vars: &HashMap<String, Option<String>>) -> impl Iterator<Item = (&str, &str)> {
+    let vars: BTreeMap<&str, &str> = vars
+        .iter()
+        .filter_map(|(key, value)| Some((key.as_str(), value.as_deref()?)))
+        .collect();
+    vars.into_iter()
+}
+
+/// Whether `c` can appear in a value written without quotes.
+fn is_unquoted(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "_-.,:/@+=%".contains(c)
+}
@@ -2,12 +2,15 @@ use crate::framework::response::ApiResult;
 use std::collections::HashMap;
 
 pub mod delete;
+pub mod diff;
+pub mod format;
 pub mod get;
 pub mod patch;
 pub mod post;
 pub mod put;
 
 pub use delete::{AppConfigVarDelete, PipelineConfigVarDelete};
+pub use diff::{ConfigChange, ConfigDiff};
 /// Config Vars
 ///
 /// Stability: production
@@ -19,7 +22,4 @@ pub use get::{AppConfigVarDetails, PipelineConfigVarDetails, ReleaseConfigVarDet
 pub use patch::{AppConfigVarUpdate, PipelineConfigVarUpdate};
 
 impl ApiResult for HashMap<String, Option<String>> {}
-impl ApiResult for Vec<HashMap<String, Option<String>>> {}
-
 impl ApiResult for HashMap<String, String> {}
-impl ApiResult for Vec<HashMap<String, String>> {}
@@ -1,10 +1,27 @@
 use crate::framework::endpoint::{HerokuEndpoint, Method};
 use crate::framework::response::ApiResult;
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 use serde::Serialize;
 use serde_json::Value;
+use std::fmt::Display;
+use std::marker::PhantomData;
 
 impl ApiResult for Value {}
-impl ApiResult for Vec<Value> {}
+
+/// The characters encoded in a path parameter, so that its value stays within one path segment.
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
 
 /// CustomEndpoint
 /// 
@@ -107,3 +124,205 @@ impl HerokuEndpoint<Value> for CustomEndpointSimple {
         format!("{}", self.query)
     }
 }
+
+/// CustomEndpointTyped
+///
+/// CustomEndpointTyped is way to make typed calls to Heroku endpoints that have not been supported by the library yet.
+///
+/// Unlike [`CustomEndpoint`][custom], the response is parsed into a `ResultType` of your choice,
+/// and the query, body, headers and Accept variant can all be set.
+///
+/// The path is a template, e.g. `apps/{app_id}/features/{feature_id}`, whose parameters are filled in with `path_param`.
+/// The values are percent-encoded, and a request whose path still has a parameter without value fails before it is sent.
+///
+/// The `ResultType` can be any type which implements `Deserialize` and `Debug`, once it is marked as an [`ApiResult`][apiresult]
+/// with a one line `impl ApiResult for MyType {}`. A `Vec<MyType>` is then marked as well, for list endpoints.
+/// Every response struct of this crate is already marked.
+///
+/// # Example:
+///
+/// ```rust
+/// use heroku_rs::prelude::*;
+/// use heroku_rs::framework::response::ApiResult;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize, Debug)]
+/// struct Feature {
+///     name: String,
+///     enabled: bool,
+/// }
+/// impl ApiResult for Feature {}
+///
+/// #[derive(Serialize, Clone)]
+/// struct FeatureUpdate {
+///     enabled: bool,
+/// }
+///
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+/// //This example does a PATCH request on `https://api.heroku.com/apps/APP_ID/features/FEATURE_ID`
+/// let response = api_client.request(
+///     &CustomEndpointTyped::<Feature, (), FeatureUpdate>::new(Method::Patch, "apps/{app_id}/features/{feature_id}")
+///         .path_param("app_id", "APP_ID")
+///         .path_param("feature_id", "FEATURE_ID")
+///         .params(FeatureUpdate { enabled: true })
+///         .build(),
+/// );
+///
+///match response {
+///     Ok(success) => println!("Success: {:#?}", success),
+///     Err(e) => println!("Error: {}", e),
+///}
+/// ```
+/// See how to create the Heroku [`api_client`][httpApiClientConfig].
+///
+/// [httpApiClientConfig]: ../../../framework/struct.HttpApiClient.html
+/// [custom]: struct.CustomEndpoint.html
+/// [apiresult]: ../../framework/response/trait.ApiResult.html
+pub struct CustomEndpointTyped<ResultType, QueryType = (), BodyType = ()> {
+    /// the path template you want
+    /// e.g apps/{app_id}/features
+    pub path: String,
+    /// the values of the path template parameters, by name
+    pub path_params: Vec<(String, String)>,
+    /// the API method to use
+    /// One of: Get, Post, Put, Delete, Patch
+    pub method: Method,
+    /// Query parameters to pass to the Heroku API
+    pub query: Option<QueryType>,
+    /// Parameters to pass to the Heroku API
+    pub params: Option<BodyType>,
+    /// Additional headers to pass to the Heroku API
+    pub headers: HeaderMap,
+    /// The Accept header, which carries the API version and variant
+    pub accept: String,
+    marker: PhantomData<ResultType>,
+}
+
+impl<ResultType, QueryType, BodyType> CustomEndpointTyped<ResultType, QueryType, BodyType> {
+    pub fn new(method: Method, path: &str) -> CustomEndpointTyped<ResultType, QueryType, BodyType> {
+        CustomEndpointTyped {
+            path: path.to_string(),
+            path_params: Vec::new(),
+            method,
+            query: None,
+            params: None,
+            headers: HeaderMap::new(),
+            accept: String::from("application/vnd.heroku+json; version=3"),
+            marker: PhantomData,
+        }
+    }
+
+    /// The `{name}` parameters of the path template which have no value.
+    pub fn unresolved_params(&self) -> Vec<&str> {
+        let mut unresolved = Vec::new();
+        let mut rest = self.path.as_str();
+        while let Some(open) = rest.find('{') {
+            let close = match rest[open..].find('}') {
+                Some(close) => open + close,
+                None => break,
+            };
+            let name = &rest[open + 1..close];
+            if !self.path_params.iter().any(|(param, _)| param == name) {
+                unresolved.push(name);
+            }
+            rest = &rest[close + 1..];
+        }
+        unresolved
+    }
+}
+
+#[cfg(feature = "builder")]
+impl<ResultType, QueryType, BodyType> CustomEndpointTyped<ResultType, QueryType, BodyType>
+where
+    QueryType: Clone,
+    BodyType: Clone,
+{
+    /// # path_param: the value of the `{name}` parameter of the path template
+    pub fn path_param<T: Display>(&mut self, name: &str, value: T) -> &mut Self {
+        self.path_params.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// # query_params: query parameters to pass to the Heroku API
+    pub fn query_params(&mut self, query: QueryType) -> &mut Self {
+        self.query = Some(query);
+        self
+    }
+
+    /// # params: parameters to pass to the Heroku API, as the request body
+    pub fn params(&mut self, params: BodyType) -> &mut Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// # header: an additional header to pass to the Heroku API
+    pub fn header(&mut self, name: HeaderName, value: HeaderValue) -> &mut Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// # variant: the variant of the API version 3 to request
+    ///
+    /// e.g. `pipelines` to request `application/vnd.heroku+json; version=3.pipelines`
+    pub fn variant(&mut self, variant: &str) -> &mut Self {
+        self.accept = format!("application/vnd.heroku+json; version=3.{}", variant);
+        self
+    }
+
+    pub fn build(&self) -> CustomEndpointTyped<ResultType, QueryType, BodyType> {
+        CustomEndpointTyped {
+            path: self.path.clone(),
+            path_params: self.path_params.clone(),
+            method: self.method,
+            query: self.query.clone(),
+            params: self.params.clone(),
+            headers: self.headers.clone(),
+            accept: self.accept.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<ResultType, QueryType, BodyType> HerokuEndpoint<ResultType, QueryType, BodyType>
+    for CustomEndpointTyped<ResultType, QueryType, BodyType>
+where
+    ResultType: ApiResult,
+    QueryType: Serialize + Clone,
+    BodyType: Serialize + Clone,
+{
+    fn method(&self) -> Method {
+        self.method
+    }
+    fn path(&self) -> String {
+        self.path_params
+            .iter()
+            .fold(self.path.clone(), |path, (name, value)| {
+                path.replace(
+                    &format!("{{{}}}", name),
+                    &utf8_percent_encode(value, PATH_SEGMENT).to_string(),
+                )
+            })
+    }
+    fn validate(&self) -> Result<(), String> {
+        match self.unresolved_params().as_slice() {
+            [] => Ok(()),
+            unresolved => Err(format!(
+                "The path {} has no value for {{{}}}",
+                self.path,
+                unresolved.join("}, {")
+            )),
+        }
+    }
+    fn query(&self) -> Option<QueryType> {
+        self.query.clone()
+    }
+    fn body(&self) -> Option<BodyType> {
+        self.params.clone()
+    }
+    fn version(&self) -> &str {
+        &self.accept
+    }
+    fn headers(&self) -> HeaderMap {
+        self.headers.clone()
+    }
+}
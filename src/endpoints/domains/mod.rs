@@ -12,7 +12,6 @@ pub use get::{DomainDetails, DomainList};
 pub use post::{DomainCreate, DomainCreateParams};
 
 impl ApiResult for Domain {}
-impl ApiResult for Vec<Domain> {}
 
 /// Domain
 ///
@@ -22,6 +21,7 @@ impl ApiResult for Vec<Domain> {}
 ///
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#domain)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Domain {
     /// status of this record’s ACM
     pub acm_status: Option<String>,
@@ -43,13 +43,22 @@ pub struct Domain {
     pub updated_at: String,
     /// status of this record’s cname
     pub status: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// An app represents the program that you would like to deploy and run on Heroku.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct App {
     /// name of app. pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
     pub name: String,
     /// unique identifier
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
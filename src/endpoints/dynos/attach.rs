@@ -0,0 +1,205 @@
+//! Attaching to one-off dynos over the rendezvous protocol.
+use std::io::{self, Read, Write};
+use url::Url;
+
+/// The marker of the line holding the exit status, see [`with_exit_status`][with_exit_status].
+///
+/// [with_exit_status]: fn.with_exit_status.html
+pub const EXIT_STATUS_MARKER: &str = "\u{FFFF} heroku-command-exit-status:";
+
+/// Append an `echo` of the exit status to a command, as `heroku run` does, for [`Rendezvous`][rendezvous] to report it.
+///
+/// [rendezvous]: struct.Rendezvous.html
+pub fn with_exit_status(command: &str) -> String {
+    format!("{}; echo \"{} $?\"", command, EXIT_STATUS_MARKER)
+}
+
+/// Rendezvous
+///
+/// A connection to an attached one-off dyno, created with [`DynoCreate`][create] and `attach(true)`.
+///
+/// Connecting sends the secret of the dyno's `attach_url`, then reading returns the output of the dyno,
+/// and writing sends to its input.
+///
+/// Dynos don't report their exit status: run the command through [`with_exit_status`][with_exit_status],
+/// and the line echoing it is removed from the output and available through `exit_status` once read.
+///
+/// # Example:
+///
+/// Running a one-off command, like `heroku run`. Requires the `native-tls` feature.
+/// ```rust,no_run
+/// use heroku_rs::endpoints::dynos::{attach, Rendezvous};
+/// use heroku_rs::prelude::*;
+/// use std::io;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let command = attach::with_exit_status("rake db:migrate");
+/// let dyno = api_client
+///     .request(&DynoCreate::new("APP_ID", &command).attach(true).build())
+///     .unwrap();
+///
+/// let mut rendezvous = Rendezvous::connect(&dyno.attach_url.unwrap()).unwrap();
+/// io::copy(&mut rendezvous, &mut io::stdout()).unwrap();
+///
+/// println!("exited with {:?}", rendezvous.exit_status());
+/// ```
+///
+/// [create]: struct.DynoCreate.html
+/// [with_exit_status]: attach/fn.with_exit_status.html
+pub struct Rendezvous<S> {
+    stream: S,
+    buffer: Vec<u8>,
+    exit_status: Option<i32>,
+    eof: bool,
+}
+
+#[cfg(feature = "native-tls")]
+impl Rendezvous<native_tls::TlsStream<std::net::TcpStream>> {
+    /// Connect to the `rendezvous://` attach URL of a dyno over TLS, and send its secret.
+    pub fn connect(attach_url: &str) -> io::Result<Self> {
+        let url = parse_attach_url(attach_url)?;
+        let host = url.host_str().unwrap_or_default();
+        let tcp = std::net::TcpStream::connect((host, url.port().unwrap_or(5000)))?;
+        let tls = native_tls::TlsConnector::new()
+            .map_err(io::Error::other)?
+            .connect(host, tcp)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        Rendezvous::handshake(tls, attach_url)
+    }
+}
+
+impl<S: Read + Write> Rendezvous<S> {
+    /// Send the secret of the attach URL over an established connection, and wait for the
+    /// `rendezvous` acknowledgement.
+    ///
+    /// Use it with a stream of your own, e.g. through a proxy, or with a stand-in server in tests.
+    pub fn handshake(mut stream: S, attach_url: &str) -> io::Result<Self> {
+        let url = parse_attach_url(attach_url)?;
+        let secret = url.path().trim_start_matches('/');
+        stream.write_all(format!("{}\r\n", secret).as_bytes())?;
+        stream.flush()?;
+
+        let mut line = Vec::new();
+        let mut byte = [0; 1];
+        while !line.ends_with(b"\n") {
+            if stream.read(&mut byte)? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "rendezvous closed before acknowledging the secret",
+                ));
+            }
+            line.push(byte[0]);
+        }
+        if String::from_utf8_lossy(&line).trim() != "rendezvous" {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "unexpected rendezvous acknowledgement: {}",
+                    String::from_utf8_lossy(&line).trim()
+                ),
+            ));
+        }
+
+        Ok(Rendezvous {
+            stream,
+            buffer: Vec::new(),
+            exit_status: None,
+            eof: false,
+        })
+    }
+
+    /// The exit status of the command, once the line echoing it has been read.
+    pub fn exit_status(&self) -> Option<i32> {
+        self.exit_status
+    }
+
+    /// The underlying stream.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// The underlying stream, e.g. to shut down its writing half.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Read more of the stream into the buffer, and remove the exit status line from it.
+    /// Returns how many bytes at the start of the buffer can be returned to the reader.
+    fn fill(&mut self) -> io::Result<usize> {
+        loop {
+            let marker = EXIT_STATUS_MARKER.as_bytes();
+            if let Some(start) = find(&self.buffer, marker) {
+                let newline = self.buffer[start..].iter().position(|b| *b == b'\n');
+                let end = match newline {
+                    Some(newline) => Some(start + newline + 1),
+                    None if self.eof => Some(self.buffer.len()),
+                    None => None,
+                };
+                if let Some(end) = end {
+                    let status = String::from_utf8_lossy(&self.buffer[start + marker.len()..end]);
+                    self.exit_status = status.trim().parse().ok();
+                    self.buffer.drain(start..end);
+                    continue;
+                }
+                if start > 0 {
+                    return Ok(start);
+                }
+            } else {
+                // keep what could be the start of the marker, until the next read
+                let keep = (1..marker.len().min(self.buffer.len() + 1))
+                    .rev()
+                    .find(|len| self.buffer.ends_with(&marker[..*len]))
+                    .unwrap_or(0);
+                let ready = self.buffer.len() - keep;
+                if ready > 0 || self.eof {
+                    return Ok(if self.eof { self.buffer.len() } else { ready });
+                }
+            }
+
+            let mut chunk = [0; 8192];
+            let read = self.stream.read(&mut chunk)?;
+            if read == 0 {
+                self.eof = true;
+            }
+            self.buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+}
+
+impl<S: Read + Write> Read for Rendezvous<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let ready = self.fill()?;
+        let len = ready.min(buf.len());
+        buf[..len].copy_from_slice(&self.buffer[..len]);
+        self.buffer.drain(..len);
+        Ok(len)
+    }
+}
+
+impl<S: Read + Write> Write for Rendezvous<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+fn parse_attach_url(attach_url: &str) -> io::Result<Url> {
+    let url = Url::parse(attach_url)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    if url.scheme() != "rendezvous" || url.host_str().is_none() || url.path().len() < 2 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("not a rendezvous attach url: {}", attach_url),
+        ));
+    }
+    Ok(url)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
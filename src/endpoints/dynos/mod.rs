@@ -1,21 +1,24 @@
 use crate::framework::response::ApiResult;
 use serde::Deserialize;
 
+pub mod attach;
 pub mod delete;
 pub mod get;
 pub mod patch;
 pub mod post;
 pub mod put;
+#[cfg(feature = "logs")]
+pub mod run;
 
+pub use attach::Rendezvous;
 pub use delete::{DynoAllRestart, DynoRestart};
 pub use get::{DynoDetails, DynoList, DynoSizeDetails, DynoSizeList};
 pub use post::{DynoActionStop, DynoCreate, DynoCreateParams};
+#[cfg(feature = "logs")]
+pub use run::{run_detached_and_wait, DetachedRun, RunOutput};
 
 impl ApiResult for Dyno {}
-impl ApiResult for Vec<Dyno> {}
-
 impl ApiResult for DynoSize {}
-impl ApiResult for Vec<DynoSize> {}
 
 pub use dyno_size::DynoSize;
 /// Heroku Dyno
@@ -26,6 +29,7 @@ pub use dyno_size::DynoSize;
 ///
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#dyno)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Dyno {
     /// An app represents the program that you would like to deploy and run on Heroku.
     pub app: App,
@@ -49,24 +53,38 @@ pub struct Dyno {
     pub r#type: String, //type is a keyword in Rust
     /// when process last changed state
     pub updated_at: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// An app represents the program that you would like to deploy and run on Heroku.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct App {
     /// unique identifier
     pub id: String,
     /// name of app pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A release represents a combination of code, config vars and add-ons for an app on Heroku.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Release {
     /// unique identifier of release
     pub id: String,
     /// unique version assigned to the release
     pub version: i64,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 mod dyno_size {
@@ -78,6 +96,7 @@ mod dyno_size {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#dyno-size)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct DynoSize {
         /// minimum vCPUs, non-dedicated may get more depending on load
         pub compute: i64,
@@ -95,10 +114,19 @@ mod dyno_size {
         pub name: String,
         /// whether this dyno can only be provisioned in a private space
         pub private_space_only: bool,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Cost {
         pub cents: Option<i64>,
         pub unit: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
@@ -0,0 +1,229 @@
+//! Running one-off dynos detached, and waiting for them to finish.
+use super::{Dyno, DynoActionStop, DynoCreate, DynoCreateParams, DynoDetails};
+use crate::endpoints::logs::{LogLine, LogTail};
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    response::{ApiResponse, HerokuApiFailure},
+    HttpApiClient,
+};
+use chrono::DateTime;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Run a one-off dyno detached, wait until it is gone, and collect its output.
+///
+/// Stops the dyno if it is still running after `timeout`. See [`DetachedRun`][run] for more options.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::dynos::run_detached_and_wait;
+/// use heroku_rs::prelude::*;
+/// use std::time::Duration;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let run = run_detached_and_wait(
+///     &api_client,
+///     &DynoCreate::new("APP_ID", "rake db:migrate").size("standard-2X").build(),
+///     Duration::from_secs(600),
+/// )
+/// .unwrap();
+///
+/// for line in &run.output {
+///     println!("{}", line);
+/// }
+/// assert_eq!(run.exit_status(), Some(0));
+/// ```
+///
+/// [run]: struct.DetachedRun.html
+pub fn run_detached_and_wait(
+    client: &HttpApiClient,
+    dyno: &DynoCreate,
+    timeout: Duration,
+) -> ApiResponse<RunOutput> {
+    DetachedRun::new(client, dyno).timeout(timeout).wait()
+}
+
+/// DetachedRun
+///
+/// Runs a one-off dyno detached, and polls [`DynoDetails`][details] until the dyno is gone. Meanwhile, its output
+/// is streamed from a tailing log session filtered to the dyno's name, opened as soon as the dyno is created.
+///
+/// Streaming stops once Heroku logs that the process exited, so [`wait`][wait] returns after the dyno is gone and
+/// its exit line was read. If the exit line doesn't come within a grace period after the dyno is gone, streaming is
+/// cancelled and `wait` returns without it. A failure to check the dyno cancels streaming too, and is returned.
+///
+/// [wait]: struct.DetachedRun.html#method.wait
+///
+/// [details]: struct.DynoDetails.html
+pub struct DetachedRun<'a> {
+    client: &'a HttpApiClient,
+    dyno: &'a DynoCreate<'a>,
+    timeout: Duration,
+    poll_interval: Duration,
+    grace: Duration,
+    lines: i64,
+}
+
+impl<'a> DetachedRun<'a> {
+    /// Prepare a detached run of `dyno`. Its `attach` parameter is ignored.
+    pub fn new(client: &'a HttpApiClient, dyno: &'a DynoCreate<'a>) -> DetachedRun<'a> {
+        DetachedRun {
+            client,
+            dyno,
+            timeout: Duration::from_secs(3600),
+            poll_interval: Duration::from_secs(5),
+            grace: Duration::from_secs(5),
+            lines: 1500,
+        }
+    }
+
+    /// # timeout: how long the dyno may run before it is stopped, 1 hour by default
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// # poll_interval: how long to wait between two checks of the dyno, 5 seconds by default
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// # grace: how long to keep streaming once the dyno is gone, for its last lines and exit line, 5 seconds by default
+    pub fn grace(mut self, grace: Duration) -> Self {
+        self.grace = grace;
+        self
+    }
+
+    /// # lines: how many lines the dyno logged before its log session connected to include, 1500 by default
+    pub fn lines(mut self, lines: i64) -> Self {
+        self.lines = lines;
+        self
+    }
+
+    /// Start the dyno, and wait until it is gone.
+    pub fn wait(self) -> ApiResponse<RunOutput> {
+        let params = &self.dyno.params;
+        let dyno = self.client.request(&DynoCreate {
+            app_id: self.dyno.app_id,
+            params: DynoCreateParams {
+                attach: Some(false),
+                ..params.clone()
+            },
+        })?;
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        thread::scope(|scope| {
+            let logs = scope.spawn(|| self.read_output(&dyno, cancel.clone()));
+            let timed_out = self.poll_until_gone(&dyno);
+            if timed_out.is_ok() {
+                let until = Instant::now() + self.grace;
+                while !logs.is_finished() && Instant::now() < until {
+                    thread::sleep(GRACE_CHECK_INTERVAL);
+                }
+            }
+            // The scope joins the stream when it returns, so it must end even without an exit line.
+            cancel.store(true, Ordering::SeqCst);
+            let timed_out = timed_out?;
+            let (output, exit_line) = logs.join().expect("reading the dyno's output panicked")?;
+
+            Ok(RunOutput {
+                dyno: dyno.clone(),
+                output,
+                exit_line,
+                timed_out,
+            })
+        })
+    }
+
+    /// Poll the dyno until it is gone, stopping it once it runs longer than the timeout. Returns whether it was stopped.
+    fn poll_until_gone(&self, dyno: &Dyno) -> ApiResponse<bool> {
+        let app_id = self.dyno.app_id;
+        let started = Instant::now();
+        let mut timed_out = false;
+        loop {
+            thread::sleep(self.poll_interval);
+            match self.client.request(&DynoDetails {
+                app_id,
+                dyno_id: &dyno.id,
+            }) {
+                Ok(_) => {}
+                Err(HerokuApiFailure::Error(status, _))
+                    if status == http::StatusCode::NOT_FOUND =>
+                {
+                    return Ok(timed_out)
+                }
+                Err(e) => return Err(e),
+            }
+
+            if !timed_out && started.elapsed() >= self.timeout {
+                timed_out = true;
+                self.client.request(&DynoActionStop {
+                    app_id,
+                    dyno_id: &dyno.id,
+                })?;
+            }
+        }
+    }
+
+    /// Stream the lines of the dyno until its exit is logged, or until streaming is cancelled.
+    fn read_output(
+        &self,
+        dyno: &Dyno,
+        cancel: Arc<AtomicBool>,
+    ) -> ApiResponse<(Vec<String>, Option<LogLine>)> {
+        // Lines replayed from an earlier dyno of the same name are skipped.
+        let created = DateTime::parse_from_rfc3339(&dyno.created_at).ok();
+        let mut output = Vec::new();
+        let lines = LogTail::new(self.client, self.dyno.app_id)
+            .dyno(&dyno.name)
+            .lines(self.lines)
+            .cancel(cancel);
+        for line in lines {
+            let line = match LogLine::parse(&line?) {
+                Ok(line) => line,
+                Err(_) => continue,
+            };
+            if matches!(created, Some(created) if line.timestamp < created) {
+                continue;
+            }
+            if line.source == "app" {
+                output.push(line.message);
+            } else if line.message.starts_with(EXITED) {
+                return Ok((output, Some(line)));
+            }
+        }
+        Ok((output, None))
+    }
+}
+
+const EXITED: &str = "Process exited with status ";
+
+/// How often to check whether the output was read, during the grace period.
+const GRACE_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The result of a [`DetachedRun`][run].
+///
+/// [run]: struct.DetachedRun.html
+#[derive(Debug, Clone)]
+pub struct RunOutput {
+    /// the dyno as it was created
+    pub dyno: Dyno,
+    /// the lines the process printed
+    pub output: Vec<String>,
+    /// the `Process exited with status 0` line logged by Heroku, if it was still in the logs
+    pub exit_line: Option<LogLine>,
+    /// whether the dyno was stopped because it ran longer than the timeout
+    pub timed_out: bool,
+}
+
+impl RunOutput {
+    /// The exit status of the process, from its exit line.
+    pub fn exit_status(&self) -> Option<i32> {
+        let exit_line = self.exit_line.as_ref()?;
+        exit_line.message[EXITED.len()..].trim().parse().ok()
+    }
+}
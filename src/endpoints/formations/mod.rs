@@ -5,13 +5,23 @@ pub mod delete;
 pub mod get;
 pub mod patch;
 pub mod post;
+pub mod procfile;
 pub mod put;
+#[cfg(all(feature = "releases", feature = "slugs"))]
+pub mod reconcile;
 
 pub use get::{FormationDetails, FormationList};
-pub use patch::{FormationUpdate, FormationUpdateParams};
+pub use patch::{
+    BatchUpdateParam, FormationBatchUpdate, FormationBatchUpdateParams, FormationUpdate,
+    FormationUpdateParams,
+};
+pub use procfile::{Procfile, ProcfileError};
+#[cfg(all(feature = "releases", feature = "slugs"))]
+pub use reconcile::{
+    FormationChange, FormationIssue, FormationReconciler, ReconcileError, ReconcilePlan,
+};
 
 impl ApiResult for Formation {}
-impl ApiResult for Vec<Formation> {}
 
 /// Heroku Formation
 ///
@@ -22,6 +32,7 @@ impl ApiResult for Vec<Formation> {}
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#formation)
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Formation {
     /// An app represents the program that you would like to deploy and run on Heroku
     pub app: App,
@@ -39,13 +50,22 @@ pub struct Formation {
     pub r#type: String,
     /// when dyno type was updated
     pub updated_at: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// An app represents the program that you would like to deploy and run on Heroku.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct App {
     /// unique identifier
     pub id: String,
     /// name of app pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
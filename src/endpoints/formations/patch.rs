@@ -73,7 +73,7 @@ impl<'a> FormationUpdate<'a> {
     pub fn build(&self) -> FormationUpdate<'a> {
         FormationUpdate {
             app_id: self.app_id,
-            formation_id: self.app_id,
+            formation_id: self.formation_id,
             params: FormationUpdateParams {
                 quantity: self.params.quantity,
                 size: self.params.size,
@@ -105,3 +105,107 @@ impl<'a> HerokuEndpoint<Formation, (), FormationUpdateParams<'a>> for FormationU
         Some(self.params.clone())
     }
 }
+
+/// Formation Batch Update
+///
+/// Batch update process types, in a single atomic call which does not create a release.
+///
+/// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#formation-batch-update)
+///
+/// # Example:
+///
+/// FormationBatchUpdate takes one required parameter, app_id, and returns the updated [`Formations`][response].
+/// ```rust
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let response = api_client.request(
+///         &formations::FormationBatchUpdate::new("APP_ID")
+///         .update("web", Some(2), Some("standard-2X"))
+///         .update("worker", Some(1), None)
+///         .build(),
+/// );
+///
+///match response {
+///     Ok(success) => println!("Success: {:#?}", success),
+///     Err(e) => println!("Error: {}", e),
+///}
+//
+/// ```
+/// See how to create the Heroku [`api_client`][httpApiClientConfig].
+///
+/// [httpApiClientConfig]: ../../../framework/struct.HttpApiClient.html
+/// [response]: ../struct.Formation.html
+pub struct FormationBatchUpdate<'a> {
+    /// app_id can be the app name or the app id
+    pub app_id: &'a str,
+    /// params are the parameters sent to the API to patch the Formations
+    pub params: FormationBatchUpdateParams<'a>,
+}
+
+#[cfg(feature = "builder")]
+impl<'a> FormationBatchUpdate<'a> {
+    pub fn new(app_id: &'a str) -> FormationBatchUpdate<'a> {
+        FormationBatchUpdate {
+            app_id,
+            params: FormationBatchUpdateParams {
+                updates: Vec::new(),
+            },
+        }
+    }
+
+    /// # update: the quantity and size of a process type, either can be left unchanged
+    ///
+    /// `type`: the formation id or process type, e.g. `web`
+    pub fn update(
+        &mut self,
+        r#type: &'a str,
+        quantity: Option<i32>,
+        size: Option<&'a str>,
+    ) -> &mut Self {
+        self.params.updates.push(BatchUpdateParam {
+            r#type,
+            quantity,
+            size,
+        });
+        self
+    }
+
+    pub fn build(&self) -> FormationBatchUpdate<'a> {
+        FormationBatchUpdate {
+            app_id: self.app_id,
+            params: self.params.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct FormationBatchUpdateParams<'a> {
+    /// Array with formation updates. Each element must have "type", the id or name of the process type to be updated, and can optionally update its "quantity" or "size".
+    pub updates: Vec<BatchUpdateParam<'a>>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug)]
+pub struct BatchUpdateParam<'a> {
+    /// the formation id or process type to update
+    pub r#type: &'a str,
+    /// number of processes to maintain
+    pub quantity: Option<i32>,
+    /// dyno size
+    pub size: Option<&'a str>,
+}
+
+impl<'a> HerokuEndpoint<Vec<Formation>, (), FormationBatchUpdateParams<'a>>
+    for FormationBatchUpdate<'a>
+{
+    fn method(&self) -> Method {
+        Method::Patch
+    }
+    fn path(&self) -> String {
+        format!("apps/{}/formation", self.app_id)
+    }
+    fn body(&self) -> Option<FormationBatchUpdateParams<'a>> {
+        Some(self.params.clone())
+    }
+}
@@ -0,0 +1,120 @@
+//! Reading the process types of an app from its Procfile.
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The process types of an app, and their commands, in the order of the Procfile.
+///
+/// Every line is a `<process type>: <command>` pair. Blank lines and `#` comments are ignored.
+///
+/// # Example:
+///
+/// ```rust
+/// use heroku_rs::endpoints::formations::Procfile;
+///
+/// let procfile: Procfile = "web: bundle exec puma -C config/puma.rb\nworker: bundle exec sidekiq"
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(procfile.command("worker"), Some("bundle exec sidekiq"));
+/// assert_eq!(procfile.process_types().collect::<Vec<_>>(), vec!["web", "worker"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Procfile {
+    processes: Vec<(String, String)>,
+}
+
+impl Procfile {
+    /// Read a Procfile.
+    ///
+    /// A malformed Procfile is reported as an `InvalidData` error.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Procfile> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// The command of a process type.
+    pub fn command(&self, process_type: &str) -> Option<&str> {
+        self.iter()
+            .find(|(name, _)| *name == process_type)
+            .map(|(_, command)| command)
+    }
+
+    /// The process types, in the order of the Procfile.
+    pub fn process_types(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(|(name, _)| name)
+    }
+
+    /// The process types and their commands, in the order of the Procfile.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.processes
+            .iter()
+            .map(|(name, command)| (name.as_str(), command.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.processes.is_empty()
+    }
+}
+
+impl FromStr for Procfile {
+    type Err = ProcfileError;
+
+    fn from_str(procfile: &str) -> Result<Self, Self::Err> {
+        let mut processes: Vec<(String, String)> = Vec::new();
+        for (index, line) in procfile.lines().enumerate() {
+            let error = |message: String| ProcfileError {
+                line: index + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, command) = line
+                .split_once(':')
+                .ok_or_else(|| error(String::from("expected <process type>: <command>")))?;
+            let (name, command) = (name.trim(), command.trim());
+            if name.is_empty()
+                || name.len() > 128
+                || !name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(error(format!(
+                    "invalid process type {:?}, only letters, digits, dashes and underscores are allowed",
+                    name
+                )));
+            }
+            if command.is_empty() {
+                return Err(error(format!("process type {} has no command", name)));
+            }
+            if processes.iter().any(|(declared, _)| declared == name) {
+                return Err(error(format!("process type {} is declared twice", name)));
+            }
+            processes.push((name.to_string(), command.to_string()));
+        }
+        Ok(Procfile { processes })
+    }
+}
+
+/// Why a Procfile could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcfileError {
+    /// the line of the problem, starting at 1
+    pub line: usize,
+    /// what the problem is
+    pub message: String,
+}
+
+impl fmt::Display for ProcfileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ProcfileError {}
@@ -0,0 +1,377 @@
+//! Reconciling the formation of an app with its Procfile and the scaling declared in code.
+use super::{
+    BatchUpdateParam, Formation, FormationBatchUpdate, FormationBatchUpdateParams, FormationList,
+    Procfile,
+};
+use crate::endpoints::releases::ReleaseList;
+use crate::endpoints::slugs::SlugDetails;
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    response::{ApiResponse, HerokuApiFailure},
+    HttpApiClient,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+
+/// Compares the desired quantities and sizes of process types with the formation of an app, and with the
+/// process types its slug defines.
+///
+/// The slug is the one of the current release, unless another one is given with [`slug`][slug]. Process types
+/// the slug does not define are never scaled, and the ones the formation still has are flagged.
+///
+/// When a Procfile is given, its process types are compared with the slug's, to flag the ones which are missing
+/// from the slug, the ones the Procfile does not declare, and the commands which differ.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::formations::{FormationReconciler, Procfile};
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let procfile = Procfile::from_file("Procfile").unwrap();
+/// let plan = FormationReconciler::new(&api_client, "APP_ID")
+///     .procfile(&procfile)
+///     .scale("web", 2)
+///     .size("web", "standard-2X")
+///     .scale("worker", 1)
+///     .plan()
+///     .unwrap();
+///
+/// for issue in &plan.issues {
+///     println!("Warning: {}", issue);
+/// }
+/// for change in &plan.changes {
+///     println!("{}", change);
+/// }
+/// ```
+///
+/// [slug]: struct.FormationReconciler.html#method.slug
+pub struct FormationReconciler<'a> {
+    client: &'a HttpApiClient,
+    app_id: &'a str,
+    procfile: Option<&'a Procfile>,
+    slug_id: Option<&'a str>,
+    desired: BTreeMap<&'a str, Desired<'a>>,
+}
+
+#[derive(Default)]
+struct Desired<'a> {
+    quantity: Option<i32>,
+    size: Option<&'a str>,
+}
+
+impl<'a> FormationReconciler<'a> {
+    pub fn new(client: &'a HttpApiClient, app_id: &'a str) -> Self {
+        FormationReconciler {
+            client,
+            app_id,
+            procfile: None,
+            slug_id: None,
+            desired: BTreeMap::new(),
+        }
+    }
+
+    /// # procfile: the declared process types, to compare with the slug's
+    pub fn procfile(mut self, procfile: &'a Procfile) -> Self {
+        self.procfile = Some(procfile);
+        self
+    }
+
+    /// # slug: the slug whose process types can be scaled, the one of the current release by default
+    pub fn slug(mut self, slug_id: &'a str) -> Self {
+        self.slug_id = Some(slug_id);
+        self
+    }
+
+    /// # scale: the desired number of dynos of a process type
+    pub fn scale(mut self, process_type: &'a str, quantity: i32) -> Self {
+        self.desired.entry(process_type).or_default().quantity = Some(quantity);
+        self
+    }
+
+    /// # size: the desired dyno size of a process type
+    pub fn size(mut self, process_type: &'a str, size: &'a str) -> Self {
+        self.desired.entry(process_type).or_default().size = Some(size);
+        self
+    }
+
+    /// Compare the desired formation with the app's, without changing anything.
+    ///
+    /// Fails if a desired process type is not defined by the slug.
+    pub fn plan(&self) -> Result<ReconcilePlan, ReconcileError> {
+        let slug_types = self.slug_process_types()?;
+        let undefined: Vec<String> = self
+            .desired
+            .keys()
+            .filter(|process_type| !slug_types.contains_key(**process_type))
+            .map(|process_type| process_type.to_string())
+            .collect();
+        if !undefined.is_empty() {
+            return Err(ReconcileError::UndefinedProcessTypes(undefined));
+        }
+
+        let mut plan = ReconcilePlan {
+            formation: self.client.request(&FormationList {
+                app_id: self.app_id,
+            })?,
+            ..ReconcilePlan::default()
+        };
+        let formation: HashMap<&str, &Formation> = plan
+            .formation
+            .iter()
+            .map(|formation| (formation.r#type.as_str(), formation))
+            .collect();
+
+        if let Some(procfile) = self.procfile {
+            for (process_type, command) in procfile.iter() {
+                match slug_types.get(process_type) {
+                    None => plan.issues.push(FormationIssue::MissingFromSlug {
+                        process_type: process_type.to_string(),
+                    }),
+                    Some(defined) if defined != command => {
+                        plan.issues.push(FormationIssue::CommandChanged {
+                            process_type: process_type.to_string(),
+                            procfile: command.to_string(),
+                            slug: defined.clone(),
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+            let known: BTreeSet<&str> = slug_types.keys().map(String::as_str).collect();
+            for process_type in known {
+                if procfile.command(process_type).is_none() {
+                    plan.issues.push(FormationIssue::Undeclared {
+                        process_type: process_type.to_string(),
+                    });
+                }
+            }
+        }
+        let stale: BTreeSet<&str> = formation
+            .keys()
+            .copied()
+            .filter(|process_type| !slug_types.contains_key(*process_type))
+            .filter(|process_type| {
+                !matches!(self.procfile, Some(procfile) if procfile.command(process_type).is_some())
+            })
+            .collect();
+        for process_type in stale {
+            plan.issues.push(FormationIssue::NotInSlug {
+                process_type: process_type.to_string(),
+            });
+        }
+
+        for (process_type, desired) in &self.desired {
+            let current = formation.get(process_type);
+            let from_quantity = current.map_or(0, |formation| formation.quantity);
+            let from_size = current.map(|formation| formation.size.clone());
+            let to_quantity = desired.quantity.unwrap_or(from_quantity);
+            let resize = match (desired.size, &from_size) {
+                (Some(size), Some(from)) => !size.eq_ignore_ascii_case(from),
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if to_quantity != from_quantity || resize {
+                plan.changes.push(FormationChange {
+                    process_type: process_type.to_string(),
+                    from_quantity,
+                    to_quantity,
+                    from_size,
+                    to_size: match resize {
+                        true => desired.size.map(String::from),
+                        false => None,
+                    },
+                });
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Apply the changes of a plan in a single atomic call, and return the updated formation.
+    ///
+    /// No request is made when the plan is empty, and the formation the plan was made from is returned.
+    pub fn apply(&self, plan: &ReconcilePlan) -> ApiResponse<Vec<Formation>> {
+        if plan.is_empty() {
+            return Ok(plan.formation.clone());
+        }
+        self.client.request(&FormationBatchUpdate {
+            app_id: self.app_id,
+            params: FormationBatchUpdateParams {
+                updates: plan
+                    .changes
+                    .iter()
+                    .map(|change| BatchUpdateParam {
+                        r#type: &change.process_type,
+                        quantity: Some(change.to_quantity),
+                        size: change.to_size.as_deref(),
+                    })
+                    .collect(),
+            },
+        })
+    }
+
+    /// Plan and apply the changes, and return the plan with the issues found.
+    pub fn run(&self) -> Result<ReconcilePlan, ReconcileError> {
+        let plan = self.plan()?;
+        self.apply(&plan)?;
+        Ok(plan)
+    }
+
+    /// The process types of the slug, and their commands.
+    fn slug_process_types(&self) -> Result<HashMap<String, String>, ReconcileError> {
+        let slug_id = match self.slug_id {
+            Some(slug_id) => slug_id.to_string(),
+            None => self.current_slug()?.ok_or(ReconcileError::NoSlug)?,
+        };
+        let slug = self.client.request(&SlugDetails {
+            app_id: self.app_id,
+            slug_id: &slug_id,
+        })?;
+        Ok(slug.process_types.unwrap_or_default())
+    }
+
+    /// The slug of the current release, if it has one.
+    fn current_slug(&self) -> ApiResponse<Option<String>> {
+        let releases = ReleaseList {
+            app_id: self.app_id,
+        };
+        for release in self
+            .client
+            .request_stream(&releases)
+            .range("version ..; order=desc")
+        {
+            let release = release?;
+            if release.current {
+                return Ok(release.slug.map(|slug| slug.id));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// The changes needed to reach the desired formation, and the drift found between the Procfile, the slug and
+/// the formation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReconcilePlan {
+    /// the process types to scale or resize
+    pub changes: Vec<FormationChange>,
+    /// the differences between the Procfile, the slug and the formation
+    pub issues: Vec<FormationIssue>,
+    /// the formation of the app when the plan was made
+    pub formation: Vec<Formation>,
+}
+
+impl ReconcilePlan {
+    /// Whether the formation is already as desired.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A process type to scale or resize.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormationChange {
+    pub process_type: String,
+    pub from_quantity: i32,
+    pub to_quantity: i32,
+    /// the current size, if the process type was scaled already
+    pub from_size: Option<String>,
+    /// the new size, if it changes
+    pub to_size: Option<String>,
+}
+
+impl fmt::Display for FormationChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "scale {} from {} to {}",
+            self.process_type, self.from_quantity, self.to_quantity
+        )?;
+        match (&self.from_size, &self.to_size) {
+            (Some(from), Some(to)) => write!(f, ", resize from {} to {}", from, to),
+            (None, Some(to)) => write!(f, ", resize to {}", to),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A difference between the Procfile, the slug and the formation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormationIssue {
+    /// The Procfile declares a process type which the slug does not define, e.g. it was not deployed yet
+    MissingFromSlug { process_type: String },
+    /// The slug has a process type which the Procfile does not declare
+    Undeclared { process_type: String },
+    /// The formation has a process type which the slug does not define, e.g. it was removed from the Procfile
+    NotInSlug { process_type: String },
+    /// The command of a process type differs between the Procfile and the slug
+    CommandChanged {
+        process_type: String,
+        procfile: String,
+        slug: String,
+    },
+}
+
+impl fmt::Display for FormationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormationIssue::MissingFromSlug { process_type } => write!(
+                f,
+                "process type {} is in the Procfile, but not in the slug",
+                process_type
+            ),
+            FormationIssue::Undeclared { process_type } => {
+                write!(f, "process type {} is not in the Procfile", process_type)
+            }
+            FormationIssue::NotInSlug { process_type } => write!(
+                f,
+                "process type {} is in the formation, but not in the slug",
+                process_type
+            ),
+            FormationIssue::CommandChanged {
+                process_type,
+                procfile,
+                slug,
+            } => write!(
+                f,
+                "process type {} runs {:?} in the Procfile, but {:?} in the slug",
+                process_type, procfile, slug
+            ),
+        }
+    }
+}
+
+/// Why the formation could not be reconciled.
+#[derive(Debug)]
+pub enum ReconcileError {
+    /// A request failed
+    Api(HerokuApiFailure),
+    /// No slug was given, and the current release has none
+    NoSlug,
+    /// These process types are not defined by the slug, so they cannot be scaled
+    UndefinedProcessTypes(Vec<String>),
+}
+
+impl From<HerokuApiFailure> for ReconcileError {
+    fn from(error: HerokuApiFailure) -> Self {
+        ReconcileError::Api(error)
+    }
+}
+
+impl fmt::Display for ReconcileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReconcileError::Api(err) => write!(f, "{}", err),
+            ReconcileError::NoSlug => write!(f, "The current release has no slug"),
+            ReconcileError::UndefinedProcessTypes(process_types) => write!(
+                f,
+                "The slug does not define the process types {}",
+                process_types.join(", ")
+            ),
+        }
+    }
+}
+
+impl Error for ReconcileError {}
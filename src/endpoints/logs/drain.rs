@@ -0,0 +1,267 @@
+//! Decoding the logs Heroku POSTs to HTTPS log drains.
+use super::line::{parse_fields, LogLine};
+use super::LogDrain;
+use chrono::{DateTime, FixedOffset};
+use std::error::Error;
+use std::fmt;
+
+/// Header with the number of messages of a batch
+pub const LOGPLEX_MSG_COUNT: &str = "Logplex-Msg-Count";
+/// Header with the unique identifier of a batch, the same for retries of the batch
+pub const LOGPLEX_FRAME_ID: &str = "Logplex-Frame-Id";
+/// Header with the token of the drain the batch was sent to
+pub const LOGPLEX_DRAIN_TOKEN: &str = "Logplex-Drain-Token";
+
+/// DrainBatch
+///
+/// A batch of log messages POSTed by Logplex to an [HTTPS drain](https://devcenter.heroku.com/articles/log-drains#https-drains),
+/// created with [`LogDrainCreate`][create].
+///
+/// The `application/logplex-1` body holds octet counted syslog frames, e.g.
+/// `83 <40>1 2012-11-30T06:45:29+00:00 host app web.3 - State changed from starting to up`
+///
+/// Decoding doesn't depend on any HTTP server: pass it the headers and the body of the request.
+/// With the `drain_server` feature, [`DrainServer`][server] receives the batches itself.
+///
+/// # Example:
+///
+/// ```rust
+/// use heroku_rs::endpoints::logs::DrainBatch;
+///
+/// let mut headers = http::HeaderMap::new();
+/// headers.insert("Logplex-Msg-Count", "1".parse().unwrap());
+/// headers.insert("Logplex-Drain-Token", "d.01234567-89ab-cdef-0123-456789abcdef".parse().unwrap());
+/// let body = "83 <40>1 2012-11-30T06:45:29+00:00 host app web.3 - State changed from starting to up\n";
+///
+/// let batch = DrainBatch::decode(&headers, body.as_bytes()).unwrap();
+///
+/// assert!(batch.verify_token("d.01234567-89ab-cdef-0123-456789abcdef"));
+/// assert_eq!(batch.messages[0].proc_id, "web.3");
+/// assert_eq!(batch.messages[0].message, "State changed from starting to up");
+/// ```
+///
+/// [create]: struct.LogDrainCreate.html
+/// [server]: struct.DrainServer.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrainBatch {
+    /// the `Logplex-Msg-Count` header
+    pub msg_count: Option<usize>,
+    /// the `Logplex-Frame-Id` header
+    pub frame_id: Option<String>,
+    /// the `Logplex-Drain-Token` header
+    pub drain_token: Option<String>,
+    /// the messages of the body
+    pub messages: Vec<DrainMessage>,
+}
+
+impl DrainBatch {
+    /// Decode the headers and the body of a request to the drain.
+    ///
+    /// Fails if a frame is malformed, or if the body doesn't hold `Logplex-Msg-Count` messages.
+    pub fn decode(headers: &http::HeaderMap, body: &[u8]) -> Result<DrainBatch, DrainError> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        let msg_count = match header(LOGPLEX_MSG_COUNT) {
+            Some(count) => Some(count.trim().parse().map_err(|_| {
+                DrainError::new(format!("invalid {} header: {}", LOGPLEX_MSG_COUNT, count))
+            })?),
+            None => None,
+        };
+
+        let messages = decode_frames(body)?;
+        if let Some(count) = msg_count {
+            if count != messages.len() {
+                return Err(DrainError::new(format!(
+                    "expected {} messages, got {}",
+                    count,
+                    messages.len()
+                )));
+            }
+        }
+
+        Ok(DrainBatch {
+            msg_count,
+            frame_id: header(LOGPLEX_FRAME_ID),
+            drain_token: header(LOGPLEX_DRAIN_TOKEN),
+            messages,
+        })
+    }
+
+    /// Whether the batch was sent to the drain with this token.
+    pub fn verify_token(&self, token: &str) -> bool {
+        match &self.drain_token {
+            Some(drain_token) => constant_time_eq(drain_token.as_bytes(), token.as_bytes()),
+            None => false,
+        }
+    }
+
+    /// Whether the batch was sent to this drain.
+    pub fn verify(&self, drain: &LogDrain) -> bool {
+        self.verify_token(&drain.token)
+    }
+}
+
+/// DrainMessage
+///
+/// A syslog message of a [`DrainBatch`][batch].
+///
+/// [batch]: struct.DrainBatch.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrainMessage {
+    /// syslog priority, the facility times 8 plus the severity
+    pub priority: u8,
+    /// syslog protocol version, always 1
+    pub version: u8,
+    /// when the message was logged
+    pub timestamp: DateTime<FixedOffset>,
+    /// hostname, `host` for Logplex
+    pub hostname: String,
+    /// where the message comes from, `app` for the output of your dynos or `heroku` for the platform
+    pub app_name: String,
+    /// the dyno or process which logged the message, e.g. `web.1` or `router`
+    pub proc_id: String,
+    /// message identifier, `None` when Logplex sent `-`
+    pub msg_id: Option<String>,
+    /// the text of the message
+    pub message: String,
+}
+
+impl DrainMessage {
+    /// Parse a syslog message, without its octet count.
+    pub fn parse(message: &str) -> Result<DrainMessage, DrainError> {
+        let invalid = |reason: &str| DrainError::new(format!("{}: {}", reason, message));
+        let message = message.trim_end_matches(['\n', '\r']);
+
+        let rest = message
+            .strip_prefix('<')
+            .ok_or_else(|| invalid("missing the priority"))?;
+        let (priority, rest) = rest
+            .split_once('>')
+            .ok_or_else(|| invalid("missing the priority"))?;
+        let priority = priority.parse().map_err(|_| invalid("invalid priority"))?;
+
+        let mut parts = rest.splitn(7, ' ');
+        let mut next = |name: &str| {
+            parts
+                .next()
+                .filter(|part| !part.is_empty())
+                .ok_or_else(|| invalid(&format!("missing the {}", name)))
+        };
+        let version = next("version")?
+            .parse()
+            .map_err(|_| invalid("invalid version"))?;
+        let timestamp = DateTime::parse_from_rfc3339(next("timestamp")?)
+            .map_err(|e| invalid(&format!("invalid timestamp, {}", e)))?;
+        let hostname = next("hostname")?.to_string();
+        let app_name = next("app name")?.to_string();
+        let proc_id = next("proc id")?.to_string();
+        let msg_id = Some(next("msg id")?)
+            .filter(|msg_id| *msg_id != "-")
+            .map(String::from);
+        let text = parts.next().unwrap_or("").to_string();
+
+        Ok(DrainMessage {
+            priority,
+            version,
+            timestamp,
+            hostname,
+            app_name,
+            proc_id,
+            msg_id,
+            message: text,
+        })
+    }
+
+    /// syslog facility of the message
+    pub fn facility(&self) -> u8 {
+        self.priority / 8
+    }
+
+    /// syslog severity of the message, from 0 (emergency) to 7 (debug)
+    pub fn severity(&self) -> u8 {
+        self.priority % 8
+    }
+
+    /// The message as a [`LogLine`][line], as a log session would have streamed it.
+    ///
+    /// [line]: struct.LogLine.html
+    pub fn to_log_line(&self) -> LogLine {
+        LogLine {
+            timestamp: self.timestamp,
+            source: self.app_name.clone(),
+            dyno: self.proc_id.clone(),
+            message: self.message.clone(),
+            fields: parse_fields(&self.message),
+        }
+    }
+}
+
+/// Decode the octet counted frames of an `application/logplex-1` body.
+pub fn decode_frames(body: &[u8]) -> Result<Vec<DrainMessage>, DrainError> {
+    let mut messages = Vec::new();
+    let mut rest = body;
+
+    loop {
+        while let Some((first, tail)) = rest.split_first() {
+            if !first.is_ascii_whitespace() {
+                break;
+            }
+            rest = tail;
+        }
+        if rest.is_empty() {
+            return Ok(messages);
+        }
+
+        let space = rest
+            .iter()
+            .position(|byte| *byte == b' ')
+            .ok_or_else(|| DrainError::new(String::from("missing the length of a frame")))?;
+        let length: usize = std::str::from_utf8(&rest[..space])
+            .ok()
+            .and_then(|length| length.parse().ok())
+            .ok_or_else(|| {
+                DrainError::new(format!(
+                    "invalid frame length: {}",
+                    String::from_utf8_lossy(&rest[..space])
+                ))
+            })?;
+        let frame = rest
+            .get(space + 1..space + 1 + length)
+            .ok_or_else(|| DrainError::new(format!("truncated frame of {} bytes", length)))?;
+        let frame = std::str::from_utf8(frame)
+            .map_err(|_| DrainError::new(String::from("frame is not valid UTF-8")))?;
+
+        messages.push(DrainMessage::parse(frame)?);
+        rest = &rest[space + 1 + length..];
+    }
+}
+
+/// Compare tokens without leaking how much of them matches through timing.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// The error of a drain request which isn't a valid Logplex batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrainError {
+    /// what is wrong with the request
+    pub reason: String,
+}
+
+impl DrainError {
+    fn new(reason: String) -> DrainError {
+        DrainError { reason }
+    }
+}
+
+impl fmt::Display for DrainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid logplex batch, {}", self.reason)
+    }
+}
+
+impl Error for DrainError {}
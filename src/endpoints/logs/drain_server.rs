@@ -0,0 +1,114 @@
+//! A small HTTP server receiving the batches of an HTTPS log drain.
+use super::drain::DrainBatch;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+
+/// DrainServer
+///
+/// An embedded HTTP server receiving the [`DrainBatch`][batch]es Logplex POSTs to a log drain.
+///
+/// Valid batches are answered with `204 No Content`. Requests which are not POSTs, which are malformed,
+/// or whose drain token doesn't match the one to verify, are answered with an error status and skipped.
+///
+/// The server speaks plain HTTP: put it behind a TLS terminating proxy, as drains must use HTTPS.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::logs::DrainServer;
+///
+/// let server = DrainServer::bind("0.0.0.0:8080")
+///     .unwrap()
+///     .verify_token("d.01234567-89ab-cdef-0123-456789abcdef");
+///
+/// for batch in server.incoming() {
+///     for message in batch.messages {
+///         println!("{}", message.to_log_line());
+///     }
+/// }
+/// ```
+///
+/// [batch]: struct.DrainBatch.html
+pub struct DrainServer {
+    server: tiny_http::Server,
+    token: Option<String>,
+}
+
+impl DrainServer {
+    /// Listen on `addr`, use port 0 to pick any free port.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<DrainServer> {
+        let server = tiny_http::Server::http(addr).map_err(io::Error::other)?;
+        Ok(DrainServer {
+            server,
+            token: None,
+        })
+    }
+
+    /// # verify_token: reject the batches which were not sent with this drain token
+    pub fn verify_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// The address the server listens on.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    /// Wait for the next valid batch.
+    ///
+    /// A request which fails midway, e.g. because the connection dropped, is skipped. Only a failure of the server
+    /// itself is returned.
+    pub fn recv(&self) -> io::Result<DrainBatch> {
+        loop {
+            let request = self.server.recv()?;
+            if let Ok(Some(batch)) = self.handle(request) {
+                return Ok(batch);
+            }
+        }
+    }
+
+    /// Iterate over the valid batches, until the server fails.
+    pub fn incoming(&self) -> impl Iterator<Item = DrainBatch> + '_ {
+        std::iter::from_fn(move || self.recv().ok())
+    }
+
+    /// Answer a request, and return its batch if it is a valid one.
+    fn handle(&self, mut request: tiny_http::Request) -> io::Result<Option<DrainBatch>> {
+        if *request.method() != tiny_http::Method::Post {
+            request.respond(tiny_http::Response::empty(405))?;
+            return Ok(None);
+        }
+
+        let mut headers = http::HeaderMap::new();
+        for header in request.headers() {
+            let name = http::header::HeaderName::from_bytes(header.field.as_str().as_bytes());
+            let value = http::header::HeaderValue::from_str(header.value.as_str());
+            if let (Ok(name), Ok(value)) = (name, value) {
+                headers.append(name, value);
+            }
+        }
+        let mut body = Vec::new();
+        request.as_reader().read_to_end(&mut body)?;
+
+        let batch = match DrainBatch::decode(&headers, &body) {
+            Ok(batch) => batch,
+            Err(e) => {
+                request.respond(
+                    tiny_http::Response::from_string(e.to_string()).with_status_code(400),
+                )?;
+                return Ok(None);
+            }
+        };
+        if let Some(token) = &self.token {
+            if !batch.verify_token(token) {
+                request.respond(tiny_http::Response::empty(403))?;
+                return Ok(None);
+            }
+        }
+
+        // The batch was received in full, even if the acknowledgement doesn't reach Logplex.
+        let _ = request.respond(tiny_http::Response::empty(204));
+        Ok(Some(batch))
+    }
+}
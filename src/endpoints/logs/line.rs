@@ -0,0 +1,182 @@
+//! Parsing the log lines streamed by log sessions.
+use chrono::{DateTime, FixedOffset};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// LogLine
+///
+/// A log line as streamed by a [`LogSession`][session], e.g.
+/// `2020-10-16T10:00:00.000000+00:00 heroku[web.1]: source=web.1 dyno=heroku.1234 sample#memory_total=21.00MB`
+///
+/// The `key=value` pairs of the message are parsed into `fields`. Values may be quoted, e.g. `desc="Request timeout"`.
+///
+/// # Example:
+///
+/// ```rust
+/// use heroku_rs::endpoints::logs::LogLine;
+///
+/// let line: LogLine = "2020-10-16T10:00:00+00:00 app[web.1]: at=info count=3".parse().unwrap();
+///
+/// assert_eq!(line.source, "app");
+/// assert_eq!(line.dyno, "web.1");
+/// assert_eq!(line.message, "at=info count=3");
+/// assert_eq!(line.field("count"), Some("3"));
+/// ```
+///
+/// [session]: struct.LogSession.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    /// when the line was logged
+    pub timestamp: DateTime<FixedOffset>,
+    /// where the line comes from, `app` for the output of your dynos or `heroku` for the platform
+    pub source: String,
+    /// the dyno or process which logged the line, e.g. `web.1`, `router` or `api`
+    pub dyno: String,
+    /// the text of the line, after the `source[dyno]: ` prefix
+    pub message: String,
+    /// the `key=value` pairs of the message
+    pub fields: BTreeMap<String, String>,
+}
+
+impl LogLine {
+    /// Parse a log line, see [`LogLine`][LogLine] for its format.
+    ///
+    /// [LogLine]: struct.LogLine.html
+    pub fn parse(line: &str) -> Result<LogLine, LogLineError> {
+        let line = line.trim_end_matches(['\n', '\r']);
+        let (timestamp, rest) = line
+            .split_once(' ')
+            .ok_or_else(|| LogLineError::new(line, "missing the source"))?;
+        let timestamp = DateTime::parse_from_rfc3339(timestamp)
+            .map_err(|e| LogLineError::new(line, &format!("invalid timestamp: {}", e)))?;
+
+        let (process, message) = match rest.split_once(": ") {
+            Some((process, message)) => (process, message),
+            None => (rest.strip_suffix(':').unwrap_or(rest), ""),
+        };
+        let (source, dyno) = match process.split_once('[') {
+            Some((source, dyno)) if dyno.ends_with(']') => (source, &dyno[..dyno.len() - 1]),
+            _ => return Err(LogLineError::new(line, "expected `source[dyno]:`")),
+        };
+
+        Ok(LogLine {
+            timestamp,
+            source: source.to_string(),
+            dyno: dyno.to_string(),
+            message: message.to_string(),
+            fields: parse_fields(message),
+        })
+    }
+
+    /// The value of a `key=value` pair of the message.
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(String::as_str)
+    }
+}
+
+impl FromStr for LogLine {
+    type Err = LogLineError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        LogLine::parse(line)
+    }
+}
+
+impl fmt::Display for LogLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}[{}]: {}",
+            self.timestamp.to_rfc3339(),
+            self.source,
+            self.dyno,
+            self.message
+        )
+    }
+}
+
+/// Parse the `key=value` pairs of a message. Words without `=` are skipped.
+///
+/// Values may be double quoted, and contain spaces and `\"` escapes then.
+pub fn parse_fields(message: &str) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    let mut chars = message.chars().peekable();
+
+    loop {
+        while chars.peek() == Some(&' ') {
+            chars.next();
+        }
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let mut key = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == ' ' || c == '=' {
+                break;
+            }
+            key.push(c);
+            chars.next();
+        }
+        if chars.peek() != Some(&'=') {
+            continue;
+        }
+        chars.next();
+
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => {
+                        if let Some(escaped) = chars.next() {
+                            value.push(escaped);
+                        }
+                    }
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c == ' ' {
+                    break;
+                }
+                value.push(c);
+                chars.next();
+            }
+        }
+        if !key.is_empty() {
+            fields.insert(key, value);
+        }
+    }
+    fields
+}
+
+/// The error of a log line which doesn't have the format of Heroku logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLineError {
+    /// the line which could not be parsed
+    pub line: String,
+    /// what is wrong with it
+    pub reason: String,
+}
+
+impl LogLineError {
+    fn new(line: &str, reason: &str) -> LogLineError {
+        LogLineError {
+            line: line.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LogLineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid log line, {}: {}", self.reason, self.line)
+    }
+}
+
+impl Error for LogLineError {}
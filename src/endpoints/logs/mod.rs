@@ -1,19 +1,29 @@
 use crate::framework::response::ApiResult;
 
 pub mod delete;
+pub mod drain;
+#[cfg(feature = "drain_server")]
+pub mod drain_server;
 pub mod get;
+pub mod line;
 pub mod patch;
 pub mod post;
 pub mod put;
+pub mod router;
+pub mod tail;
 
 pub use delete::LogDrainDelete;
+pub use drain::{DrainBatch, DrainError, DrainMessage};
+#[cfg(feature = "drain_server")]
+pub use drain_server::DrainServer;
 pub use get::{LogDrainDetails, LogDrainList, LogDrainListByAddon};
+pub use line::{LogLine, LogLineError};
 pub use post::{LogDrainCreate, LogDrainCreateParams, LogSessionCreate, LogSessionCreateParams};
 pub use put::{LogDrainUpdate, LogDrainUpdateParams};
+pub use router::{ErrorCode, RouterLog};
+pub use tail::LogTail;
 
 impl ApiResult for LogDrain {}
-impl ApiResult for Vec<LogDrain> {}
-
 impl ApiResult for LogSession {}
 
 pub use log_drains::LogDrain;
@@ -35,6 +45,7 @@ mod log_drains {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#log-drain)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct LogDrain {
         /// add-on that created the drain
         pub addon: Option<Addon>,
@@ -48,14 +59,23 @@ mod log_drains {
         pub updated_at: DateTime<Utc>,
         /// url associated with the log drain
         pub url: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Addon {
         /// unique identifier
         pub id: String,
         /// globally name of the add-on
         ///  pattern: ^[a-zA-Z][A-Za-z0-9_-]+$
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -71,6 +91,7 @@ mod log_sessions {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#log-session)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct LogSession {
         /// when log connection was created
         pub created_at: DateTime<Utc>,
@@ -80,5 +101,9 @@ mod log_sessions {
         pub logplex_url: String,
         /// when log session was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
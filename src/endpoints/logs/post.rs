@@ -158,6 +158,7 @@ impl<'a> LogSessionCreate<'a> {
 /// Create a new log drain with parameters.
 ///
 /// [See Heroku documentation for more information about these paramters](https://devcenter.heroku.com/articles/platform-api-reference#log-session-create-optional-parameters)
+#[serde_with::skip_serializing_none]
 #[derive(Serialize, Clone, Debug)]
 pub struct LogSessionCreateParams<'a> {
     /// dyno to limit results to
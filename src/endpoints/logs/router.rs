@@ -0,0 +1,240 @@
+//! Parsing the lines of the Heroku router, and Heroku error codes.
+use super::line::{LogLine, LogLineError};
+use chrono::{DateTime, FixedOffset};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// RouterLog
+///
+/// A request served by the Heroku router, parsed from a `heroku[router]` log line, e.g.
+///
+/// `2020-10-16T10:00:00+00:00 heroku[router]: at=info method=GET path="/" host=example.herokuapp.com request_id=8601b555-6a83-4c12-8269-97c8e32cdb22 fwd="204.204.204.204" dyno=web.1 connect=1ms service=18ms status=200 bytes=13 protocol=https`
+///
+/// # Example:
+///
+/// Computing the 95th percentile of the service times, and the error rate, of a tail of the router logs.
+/// ```rust,no_run
+/// use heroku_rs::endpoints::logs::{LogLine, LogTail, RouterLog};
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let requests: Vec<RouterLog> = LogTail::new(&api_client, "APP_ID")
+///     .dyno("router")
+///     .lines(1500)
+///     .tail(false)
+///     .filter_map(|line| line.ok()?.parse::<LogLine>().ok())
+///     .filter_map(|line| RouterLog::from_line(&line))
+///     .collect();
+///
+/// let mut service: Vec<_> = requests.iter().filter_map(|request| request.service).collect();
+/// service.sort();
+/// let p95 = service.get(service.len() * 95 / 100);
+/// let errors = requests.iter().filter(|request| request.code.is_some()).count();
+///
+/// println!("p95: {:?}, error rate: {}", p95, errors as f64 / requests.len() as f64);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouterLog {
+    /// when the request was logged
+    pub timestamp: DateTime<FixedOffset>,
+    /// `info` for served requests, `error` for failed ones
+    pub at: String,
+    /// the error code of a failed request, e.g. `H12`
+    pub code: Option<ErrorCode>,
+    /// the description of the error, as logged
+    pub desc: Option<String>,
+    /// HTTP method of the request
+    pub method: Option<String>,
+    /// path and query of the request
+    pub path: Option<String>,
+    /// host the request was sent to
+    pub host: Option<String>,
+    /// the `X-Request-ID` of the request
+    pub request_id: Option<String>,
+    /// the `X-Forwarded-For` of the request
+    pub fwd: Option<String>,
+    /// the dyno which served the request
+    pub dyno: Option<String>,
+    /// time spent establishing a connection to the dyno
+    pub connect: Option<Duration>,
+    /// time spent proxying data between the client and the dyno
+    pub service: Option<Duration>,
+    /// HTTP status of the response
+    pub status: Option<u16>,
+    /// size of the response
+    pub bytes: Option<u64>,
+    /// `http` or `https`
+    pub protocol: Option<String>,
+}
+
+impl RouterLog {
+    /// Parse a log line of the router. Returns `None` if the line doesn't come from `heroku[router]`.
+    pub fn from_line(line: &LogLine) -> Option<RouterLog> {
+        if line.source != "heroku" || line.dyno != "router" {
+            return None;
+        }
+        let field = |key: &str| line.field(key).map(String::from);
+
+        Some(RouterLog {
+            timestamp: line.timestamp,
+            at: field("at").unwrap_or_default(),
+            code: line.field("code").and_then(|code| code.parse().ok()),
+            desc: field("desc"),
+            method: field("method"),
+            path: field("path"),
+            host: field("host"),
+            request_id: field("request_id"),
+            fwd: field("fwd"),
+            dyno: field("dyno"),
+            connect: line.field("connect").and_then(parse_duration),
+            service: line.field("service").and_then(parse_duration),
+            status: line.field("status").and_then(|status| status.parse().ok()),
+            bytes: line.field("bytes").and_then(|bytes| bytes.parse().ok()),
+            protocol: field("protocol"),
+        })
+    }
+
+    /// Whether the router logged the request as an error.
+    pub fn is_error(&self) -> bool {
+        self.at == "error" || self.code.is_some()
+    }
+}
+
+impl FromStr for RouterLog {
+    type Err = LogLineError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        RouterLog::from_line(&line.parse()?).ok_or_else(|| LogLineError {
+            line: line.to_string(),
+            reason: String::from("not a line of heroku[router]"),
+        })
+    }
+}
+
+/// Parse a router duration, e.g. `18ms`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let millis: f64 = value.strip_suffix("ms").unwrap_or(value).parse().ok()?;
+    if millis < 0.0 {
+        return None;
+    }
+    Some(Duration::from_micros((millis * 1000.0).round() as u64))
+}
+
+macro_rules! error_codes {
+    ($($variant:ident => $code:expr, $description:expr;)*) => {
+        /// Heroku error codes
+        ///
+        /// `H` codes are logged by the router, `R` codes by the runtime and `L` codes by Logplex.
+        ///
+        /// [See Heroku documentation for more information about these codes](https://devcenter.heroku.com/articles/error-codes)
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $(
+                #[doc = $description]
+                $variant,
+            )*
+            /// A code unknown to this crate
+            Other(String),
+        }
+
+        impl ErrorCode {
+            /// The code, e.g. `H12`
+            pub fn code(&self) -> &str {
+                match self {
+                    $(ErrorCode::$variant => $code,)*
+                    ErrorCode::Other(code) => code,
+                }
+            }
+
+            /// What the code means, e.g. `Request timeout`
+            pub fn description(&self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $description,)*
+                    ErrorCode::Other(_) => "Unknown error",
+                }
+            }
+        }
+
+        impl FromStr for ErrorCode {
+            type Err = std::convert::Infallible;
+
+            fn from_str(code: &str) -> Result<Self, Self::Err> {
+                Ok(match code {
+                    $($code => ErrorCode::$variant,)*
+                    code => ErrorCode::Other(code.to_string()),
+                })
+            }
+        }
+    };
+}
+
+error_codes! {
+    H10 => "H10", "App crashed";
+    H11 => "H11", "Backlog too deep";
+    H12 => "H12", "Request timeout";
+    H13 => "H13", "Connection closed without response";
+    H14 => "H14", "No web dynos running";
+    H15 => "H15", "Idle connection";
+    H16 => "H16", "Redirect to herokuapp.com";
+    H17 => "H17", "Poorly formatted HTTP response";
+    H18 => "H18", "Server request interrupted";
+    H19 => "H19", "Backend connection timeout";
+    H20 => "H20", "App boot timeout";
+    H21 => "H21", "Backend connection refused";
+    H22 => "H22", "Connection limit reached";
+    H23 => "H23", "Endpoint misconfigured";
+    H24 => "H24", "Forced close";
+    H25 => "H25", "HTTP restriction";
+    H26 => "H26", "Request error";
+    H27 => "H27", "Client request interrupted";
+    H28 => "H28", "Client connection idle";
+    H31 => "H31", "Misdirected request";
+    H80 => "H80", "Maintenance mode";
+    H81 => "H81", "Blank app";
+    H82 => "H82", "Free dyno hours quota exhausted";
+    H83 => "H83", "Planned service degradation";
+    H99 => "H99", "Platform error";
+    R10 => "R10", "Boot timeout";
+    R12 => "R12", "Exit timeout";
+    R13 => "R13", "Attach error";
+    R14 => "R14", "Memory quota exceeded";
+    R15 => "R15", "Memory quota vastly exceeded";
+    R16 => "R16", "Detached";
+    R17 => "R17", "Checksum error";
+    R99 => "R99", "Platform error";
+    L10 => "L10", "Drain buffer overflow";
+    L11 => "L11", "Tail buffer overflow";
+    L12 => "L12", "Local buffer overflow";
+    L13 => "L13", "Local delivery error";
+    L14 => "L14", "Certificate validation error";
+    L15 => "L15", "Tail buffer temporarily unavailable";
+}
+
+impl ErrorCode {
+    /// Find the error code of any log line: the `code=` field of router lines,
+    /// or the `Error R14 (Memory quota exceeded)` message of runtime and Logplex lines.
+    pub fn from_line(line: &LogLine) -> Option<ErrorCode> {
+        if let Some(code) = line.field("code") {
+            return code.parse().ok();
+        }
+        let mut words = line.message.split_whitespace();
+        while let Some(word) = words.next() {
+            if word == "Error" {
+                let code = words.next()?.trim_end_matches(':');
+                let mut chars = code.chars();
+                let valid = matches!(chars.next(), Some('H') | Some('R') | Some('L'))
+                    && code.len() == 3
+                    && chars.all(|c| c.is_ascii_digit());
+                return if valid { code.parse().ok() } else { None };
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.code(), self.description())
+    }
+}
@@ -0,0 +1,284 @@
+//! Streaming the logs of an app through log sessions.
+use super::{LogSessionCreate, LogSessionCreateParams};
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    reconnect::{self, LineBuffer, Reconnect},
+    response::{match_failure, ApiResponse},
+    HttpApiClient,
+};
+use std::collections::VecDeque;
+use std::io::{BufReader, Read};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// LogTail
+///
+/// An iterator over the log lines of an app, like `heroku logs --tail`.
+///
+/// It creates a [`LogSession`][session] and streams the body of its `logplex_url` line by line.
+/// Heroku ends tail sessions after a while, so when one ends a new session is created and streaming resumes.
+/// The lines replayed by the new session are skipped, up to the last line already returned.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::logs::LogTail;
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let logs = LogTail::new(&api_client, "APP_ID").source("app").lines(100);
+///
+/// for line in logs {
+///     match line {
+///         Ok(line) => println!("{}", line),
+///         Err(e) => println!("Error: {}", e),
+///     }
+/// }
+/// ```
+///
+/// [session]: struct.LogSession.html
+pub struct LogTail<'a> {
+    client: &'a HttpApiClient,
+    app_id: String,
+    dyno: Option<String>,
+    source: Option<String>,
+    lines: Option<i64>,
+    tail: bool,
+    resume_lines: i64,
+    reader: Option<BufReader<Box<dyn Read + Send>>>,
+    line: LineBuffer,
+    /// the last line returned, to find where a new session resumes
+    last: Option<String>,
+    /// lines replayed by a new session, kept until they are known to be new
+    replayed: VecDeque<String>,
+    /// how many replayed lines are still expected from the new session
+    replaying: i64,
+    connected: bool,
+    reconnect: Reconnect<'a>,
+}
+
+impl<'a> LogTail<'a> {
+    /// Tail the logs of an app, until the iterator is dropped.
+    pub fn new(client: &'a HttpApiClient, app_id: &str) -> LogTail<'a> {
+        LogTail {
+            client,
+            app_id: app_id.to_string(),
+            dyno: None,
+            source: None,
+            lines: None,
+            tail: true,
+            resume_lines: 100,
+            reader: None,
+            line: LineBuffer::default(),
+            last: None,
+            replayed: VecDeque::new(),
+            replaying: 0,
+            connected: false,
+            reconnect: Reconnect::new(Duration::from_secs(1)),
+        }
+    }
+
+    /// # dyno: dyno to limit results to, e.g. `web.1`
+    pub fn dyno(mut self, dyno: &str) -> Self {
+        self.dyno = Some(dyno.to_string());
+        self
+    }
+
+    /// # source: log source to limit results to, e.g. `app` or `heroku`
+    pub fn source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+
+    /// # lines: number of past log lines to stream first
+    pub fn lines(mut self, lines: i64) -> Self {
+        self.lines = Some(lines);
+        self
+    }
+
+    /// # tail: whether to stream ongoing logs, `true` by default
+    ///
+    /// When `false`, the iterator ends after the past lines, and no new session is created.
+    pub fn tail(mut self, tail: bool) -> Self {
+        self.tail = tail;
+        self
+    }
+
+    /// # resume_lines: how many past lines a new session replays to find where the previous one stopped, 100 by default
+    pub fn resume_lines(mut self, resume_lines: i64) -> Self {
+        self.resume_lines = resume_lines;
+        self
+    }
+
+    /// # max_reconnects: how many times in a row to reconnect after a failure, 10 by default
+    pub fn max_reconnects(mut self, max_reconnects: u32) -> Self {
+        self.reconnect.max_reconnects = max_reconnects;
+        self
+    }
+
+    /// # retry_delay: how long to wait before creating a new session, 1 second by default
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.reconnect.retry_delay = retry_delay;
+        self
+    }
+
+    /// # cancel: end the logs once `cancel` is set, e.g. from another thread
+    ///
+    /// It is checked before every line, and while waiting to create a new session. A read waiting for the next
+    /// line is not interrupted, but Heroku ends idle sessions.
+    pub fn cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.reconnect.cancel(cancel);
+        self
+    }
+
+    /// Call `callback` with every log line, until the logs end or a failure can't be recovered from.
+    ///
+    /// # Example:
+    ///
+    /// ```rust,no_run
+    /// use heroku_rs::endpoints::logs::LogTail;
+    /// use heroku_rs::prelude::*;
+    ///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+    ///
+    /// LogTail::new(&api_client, "APP_ID")
+    ///     .dyno("web.1")
+    ///     .for_each_line(|line| println!("{}", line))
+    ///     .unwrap();
+    /// ```
+    pub fn for_each_line<F: FnMut(&str)>(self, mut callback: F) -> ApiResponse<()> {
+        for line in self {
+            callback(&line?);
+        }
+        Ok(())
+    }
+
+    fn connect(&mut self) -> ApiResponse<()> {
+        // A new session replays some history, which is only needed to find where the previous one stopped.
+        let lines = match self.last {
+            Some(_) => Some(self.resume_lines),
+            None => self.lines,
+        };
+        let session = self.client.request(&LogSessionCreate {
+            app_id: &self.app_id,
+            params: LogSessionCreateParams {
+                dyno: self.dyno.as_deref(),
+                lines,
+                source: self.source.as_deref(),
+                tail: Some(self.tail),
+            },
+        })?;
+
+        let request = reconnect::get(&session.logplex_url)?;
+        let response = self.client.transport().send(request)?;
+        if !response.status.is_success() {
+            return Err(match_failure(response));
+        }
+
+        self.replaying = if self.last.is_some() {
+            self.resume_lines
+        } else {
+            0
+        };
+        self.reader = Some(BufReader::new(response.body));
+        self.connected = true;
+        Ok(())
+    }
+
+    /// Keep a line replayed by a new session until it is known whether it was already returned.
+    fn replay(&mut self, line: String) {
+        if self.last.as_ref() == Some(&line) {
+            // Everything up to here was returned by the previous session.
+            self.replayed.clear();
+            self.replaying = 0;
+            return;
+        }
+        self.replayed.push_back(line);
+        self.replaying -= 1;
+    }
+
+    /// Stop waiting for replayed lines, the ones kept so far are all new.
+    fn stop_replaying(&mut self) {
+        self.replaying = 0;
+    }
+
+    fn emit(&mut self, line: String) -> Option<ApiResponse<String>> {
+        self.last = Some(line.clone());
+        Some(Ok(line))
+    }
+}
+
+impl<'a> Iterator for LogTail<'a> {
+    type Item = ApiResponse<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.reconnect.is_cancelled() {
+                return None;
+            }
+            if self.replaying <= 0 {
+                if let Some(line) = self.replayed.pop_front() {
+                    return self.emit(line);
+                }
+            }
+            if self.reconnect.is_done() {
+                return None;
+            }
+            if self.reader.is_none() {
+                if self.connected && !self.tail {
+                    self.reconnect.finish();
+                    continue;
+                }
+                if let Err(e) = self.connect() {
+                    match self.reconnect.retry(e) {
+                        Some(e) => return Some(Err(e)),
+                        None => continue,
+                    }
+                }
+            }
+
+            let reader = self.reader.as_mut().expect("connected above");
+            match self.line.read_from(reader) {
+                // The session ended, a new one is created on the next turn if tailing.
+                Ok(0) => {
+                    self.reader = None;
+                    self.stop_replaying();
+                    if !self.line.is_empty() {
+                        let line = self.line.take();
+                        // After the replayed lines kept so far, which are returned first.
+                        self.replayed.push_back(line);
+                        continue;
+                    }
+                    if self.tail {
+                        self.reconnect.wait();
+                    }
+                }
+                Ok(_) => {
+                    if !self.line.is_complete() {
+                        continue;
+                    }
+                    self.reconnect.received();
+                    let line = self.line.take();
+                    if self.replaying > 0 {
+                        self.replay(line);
+                    } else {
+                        return self.emit(line);
+                    }
+                }
+                // A partial line is dropped, the new session replays it.
+                Err(e) => {
+                    self.line.clear();
+                    self.stop_replaying();
+                    self.reader = None;
+                    if !self.tail {
+                        self.reconnect.finish();
+                        return Some(Err(e.into()));
+                    }
+                    if let Some(e) = self.reconnect.retry(e.into()) {
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -15,13 +15,8 @@ pub use sources::SourceBlob;
 pub use stack::Stack;
 
 impl ApiResult for Region {}
-impl ApiResult for Vec<Region> {}
-
 impl ApiResult for Ratelimit {}
-
 impl ApiResult for Stack {}
-impl ApiResult for Vec<Stack> {}
-
 impl ApiResult for SourceBlob {}
 
 mod region {
@@ -36,6 +31,7 @@ mod region {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#region)
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Region {
         /// country where the region exists
         pub country: String,
@@ -55,22 +51,36 @@ mod region {
         pub provider: Provider,
         /// when region was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Provider {
         /// name of provider
         pub name: String,
         /// region name used by provider
         /// one of:"ap-south-1" or "eu-west-1" or "ap-southeast-1" or "ap-southeast-2" or "eu-central-1" or "ap-northeast-2" or "ap-northeast-1" or "us-east-1" or "sa-east-1" or "us-west-1" or "us-west-2"
         pub region: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
 mod ratelimit {
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Ratelimit {
         pub remaining: i64,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -87,6 +97,7 @@ mod stack {
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#stack)
 
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Stack {
         /// indicates this stack is the default for new apps
         pub default: bool,
@@ -100,6 +111,10 @@ mod stack {
         pub state: String,
         /// when stack was last modified
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -112,15 +127,25 @@ mod sources {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#source)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct SourceBlob {
         /// the urls which you can download or upload the source
         pub source_blob: SourceBlobData,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct SourceBlobData {
         /// URL to download the source
         pub get_url: String,
         /// URL to upload the source
         pub put_url: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
@@ -15,13 +15,8 @@ pub use post::{
 };
 
 impl ApiResult for auth::OAuth {}
-impl ApiResult for Vec<auth::OAuth> {}
-
 impl ApiResult for client::OAuthClient {}
-impl ApiResult for Vec<client::OAuthClient> {}
-
 impl ApiResult for token::OAuthToken {}
-impl ApiResult for Vec<token::OAuthToken> {}
 
 pub use auth::OAuth;
 pub use client::OAuthClient;
@@ -40,6 +35,7 @@ mod auth {
     ///
     /// [For more information please refer to the Heroku OAuth documentation](https://devcenter.heroku.com/articles/oauth)
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct OAuth {
         /// access token for this authorization
         pub access_token: Option<AccessToken>,
@@ -59,10 +55,15 @@ mod auth {
         pub updated_at: DateTime<Utc>,
         /// User account
         pub user: User,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// OAuth Token
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AccessToken {
         /// seconds until OAuth token expires; may be null for tokens with indefinite lifetime
         pub expires_in: Option<i64>,
@@ -70,10 +71,15 @@ mod auth {
         pub id: String,
         /// contents of the token to be used for authorization
         pub token: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// OAuth Client
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Client {
         /// unique identifier of this OAuth client
         pub id: String,
@@ -81,10 +87,15 @@ mod auth {
         pub name: String,
         /// endpoint for redirection after authorization with OAuth client
         pub redirect_uri: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// OAuth Grant
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Grant {
         /// grant code received from OAuth web application authorization
         pub code: String,
@@ -92,10 +103,15 @@ mod auth {
         pub expires_in: i64,
         /// unique identifier of OAuth grant
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// OAuth Token
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct RefreshToken {
         /// refresh token for this authorization
         pub expires_in: Option<i64>,
@@ -103,10 +119,15 @@ mod auth {
         pub id: String,
         /// contents of the token to be used for authorization
         pub token: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// Account
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct User {
         /// identifier of an account
         pub id: String,
@@ -114,6 +135,10 @@ mod auth {
         pub email: String,
         /// full name of the account owner
         pub full_name: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -129,6 +154,7 @@ mod client {
     ///
     /// [For more information please refer to the Heroku OAuth documentation](https://devcenter.heroku.com/articles/oauth)
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct OAuthClient {
         /// when OAuth client was created
         pub created_at: DateTime<Utc>,
@@ -144,6 +170,10 @@ mod client {
         pub secret: String,
         /// when OAuth client was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -159,6 +189,7 @@ mod token {
     ///
     /// [For more information please refer to the Heroku OAuth documentation](https://devcenter.heroku.com/articles/oauth)
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct OAuthToken {
         /// AccessToken
         pub access_token: AccessToken,
@@ -180,9 +211,14 @@ mod token {
         pub updated_at: DateTime<Utc>,
         /// Account
         pub user: User,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AccessToken {
         /// seconds until OAuth token expires; may be null for tokens with indefinite lifetime
         pub expires_in: Option<i64>,
@@ -190,33 +226,53 @@ mod token {
         pub id: String,
         /// contents of the token to be used for authorization
         pub token: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Authorization {
         /// unique identifier of OAuth authorization
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// OAuth client secret used to obtain token
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Client {
         /// secret used to obtain OAuth authorizations under this client
         pub secret: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// Grant
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Grant {
         /// grant code received from OAuth web application authorization
         pub code: String,
         /// type of grant requested, one of authorization_code or refresh_token
         #[serde(rename = "type")]
         pub type_field: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// RefreshToken
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct RefreshToken {
         /// seconds until OAuth token expires; may be null for tokens with indefinite lifetime
         pub expires_in: Option<i64>,
@@ -224,19 +280,33 @@ mod token {
         pub id: String,
         /// contents of the token to be used for authorization
         pub token: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// Session
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Session {
         /// unique identifier of OAuth token
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// Account
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct User {
         /// identifier of an account
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
@@ -0,0 +1,312 @@
+//! Promoting an app of a pipeline to the next stage, and waiting for the target releases.
+use super::post::{AppParam, PipelineParam, SourceParam, TargetParam};
+use super::{
+    PipelineCoupling, PipelineCouplingByPipelineList, PipelineLatestReleaseList, PipelinePromotion,
+    PipelinePromotionCreate, PipelinePromotionCreateParams, PipelinePromotionDetails,
+    PipelinePromotionTargetList, PipelineRelease,
+};
+use crate::endpoints::releases::{Release, ReleaseInfo};
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    response::HerokuApiFailure,
+    waiter::{WaitError, Waiter},
+    HttpApiClient,
+};
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// The stages of a pipeline, in promotion order.
+const STAGES: [&str; 5] = ["test", "review", "development", "staging", "production"];
+
+/// Promote the latest release of `source_app_id` to `targets`, and wait until every target is released.
+///
+/// When `targets` is empty, the source is promoted to every app of the next stage of the pipeline, e.g. from
+/// `staging` to `production`.
+///
+/// See [`Promoter`][promoter] for more options.
+///
+/// [promoter]: struct.Promoter.html
+pub fn promote(
+    client: &HttpApiClient,
+    pipeline_id: &str,
+    source_app_id: &str,
+    targets: &[&str],
+) -> Result<PromotionSummary, PromotionError> {
+    Promoter::new(client, pipeline_id, source_app_id)
+        .targets(targets)
+        .run()
+}
+
+/// Promotes an app of a pipeline, and reports the outcome of every target.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::pipelines::Promoter;
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let summary = Promoter::new(&api_client, "PIPELINE_ID", "STAGING_APP_ID")
+///     .require_successful_source()
+///     .run()
+///     .unwrap();
+///
+/// for target in summary.failed {
+///     println!("{}: {}", target.app_id, target.error_message.unwrap_or_default());
+/// }
+/// ```
+pub struct Promoter<'a> {
+    client: &'a HttpApiClient,
+    pipeline_id: &'a str,
+    source_app_id: &'a str,
+    targets: Vec<&'a str>,
+    require_successful_source: bool,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl<'a> Promoter<'a> {
+    pub fn new(client: &'a HttpApiClient, pipeline_id: &'a str, source_app_id: &'a str) -> Self {
+        Promoter {
+            client,
+            pipeline_id,
+            source_app_id,
+            targets: Vec::new(),
+            require_successful_source: false,
+            poll_interval: Duration::from_secs(2),
+            timeout: None,
+        }
+    }
+
+    /// # targets: the apps to promote to, the apps of the next stage by default
+    pub fn targets(mut self, targets: &[&'a str]) -> Self {
+        self.targets = targets.to_vec();
+        self
+    }
+
+    /// # require_successful_source: refuse to promote a source whose latest release failed
+    pub fn require_successful_source(mut self) -> Self {
+        self.require_successful_source = true;
+        self
+    }
+
+    /// # poll_interval: how long to wait between two checks of the promotion and releases, 2 seconds by default
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// # timeout: how long to wait at most for the promotion, then for each release, forever by default
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Promote the source, wait for the promotion and the target releases, and summarize the outcome.
+    ///
+    /// Targets which failed to promote or to release are reported in `failed`, they do not make this fail.
+    pub fn run(self) -> Result<PromotionSummary, PromotionError> {
+        let client = self.client;
+        if self.require_successful_source {
+            let releases = client.request(&PipelineLatestReleaseList {
+                pipeline_id: self.pipeline_id,
+            })?;
+            if let Some(release) = releases
+                .into_iter()
+                .find(|release| release.app.id == self.source_app_id)
+                .filter(|release| release.status == "failed")
+            {
+                return Err(PromotionError::SourceReleaseFailed(Box::new(release)));
+            }
+        }
+
+        let targets = match self.targets.is_empty() {
+            true => self.next_stage()?,
+            false => self.targets.iter().map(|app| app.to_string()).collect(),
+        };
+        let promotion = client.request(&PipelinePromotionCreate {
+            params: PipelinePromotionCreateParams {
+                pipeline: PipelineParam {
+                    id: self.pipeline_id,
+                },
+                source: SourceParam {
+                    app: AppParam {
+                        id: self.source_app_id,
+                    },
+                },
+                targets: targets
+                    .iter()
+                    .map(|app| TargetParam {
+                        app: AppParam { id: app },
+                    })
+                    .collect(),
+            },
+        })?;
+
+        let details = PipelinePromotionDetails {
+            promotion_id: &promotion.id,
+        };
+        let promotion = self
+            .waiter(Waiter::for_promotion(client, &details))
+            .wait()
+            .map_err(|e| match e {
+                WaitError::Api(error) => PromotionError::Api(error),
+                WaitError::TimedOut(_) | WaitError::Cancelled(_) => PromotionError::TimedOut {
+                    promotion_id: promotion.id.clone(),
+                },
+            })?;
+
+        let mut summary = PromotionSummary {
+            promotion,
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        };
+        let targets = client.request(&PipelinePromotionTargetList {
+            promotion_id: &summary.promotion.id,
+        })?;
+        for target in targets {
+            let mut promoted = PromotedTarget {
+                app_id: target.app.id,
+                release: None,
+                error_message: target.error_message,
+            };
+            if let (Some(release), "succeeded") = (target.release, target.status.as_str()) {
+                let info = ReleaseInfo {
+                    app_id: &promoted.app_id,
+                    release_id: &release.id,
+                };
+                // A target which could not be waited for is reported as failed, the others still are.
+                let release = match self.waiter(Waiter::for_release(client, &info)).wait() {
+                    Ok(release) => Some(release),
+                    Err(WaitError::TimedOut(release)) => {
+                        promoted.error_message = Some(format!(
+                            "Timed out waiting for release v{}",
+                            release.version
+                        ));
+                        Some(release)
+                    }
+                    Err(e) => {
+                        promoted.error_message =
+                            Some(format!("Waiting for release {} failed: {}", release.id, e));
+                        match e {
+                            WaitError::Cancelled(release) => release,
+                            _ => None,
+                        }
+                    }
+                };
+                if let Some(release) = release {
+                    if release.status == "failed" {
+                        promoted.error_message =
+                            Some(format!("Release v{} failed", release.version));
+                    }
+                    promoted.release = Some(release);
+                }
+            } else if promoted.error_message.is_none() {
+                promoted.error_message = Some(format!("Promotion {}", target.status));
+            }
+
+            match promoted.release.as_ref() {
+                Some(release) if release.status == "succeeded" => summary.succeeded.push(promoted),
+                _ => summary.failed.push(promoted),
+            }
+        }
+        Ok(summary)
+    }
+
+    /// The apps coupled to the stage after the source's.
+    fn next_stage(&self) -> Result<Vec<String>, PromotionError> {
+        let couplings = self.client.request(&PipelineCouplingByPipelineList {
+            pipeline_id: self.pipeline_id,
+        })?;
+        let stage = couplings
+            .iter()
+            .find(|coupling| coupling.app.id == self.source_app_id)
+            .and_then(|coupling| STAGES.iter().position(|stage| *stage == coupling.stage))
+            .ok_or(PromotionError::NotCoupled)?;
+        let next = STAGES.get(stage + 1).ok_or(PromotionError::NoTargets)?;
+        let targets: Vec<String> = couplings
+            .into_iter()
+            .filter(|coupling: &PipelineCoupling| coupling.stage == *next)
+            .map(|coupling| coupling.app.id)
+            .collect();
+        match targets.is_empty() {
+            true => Err(PromotionError::NoTargets),
+            false => Ok(targets),
+        }
+    }
+
+    fn waiter<T>(&self, waiter: Waiter<'a, T>) -> Waiter<'a, T>
+    where
+        T: crate::framework::response::ApiResult,
+    {
+        let waiter = waiter.poll_interval(self.poll_interval);
+        match self.timeout {
+            Some(timeout) => waiter.timeout(timeout),
+            None => waiter,
+        }
+    }
+}
+
+/// The outcome of a promotion, target by target.
+#[derive(Debug, Clone)]
+pub struct PromotionSummary {
+    /// the completed promotion
+    pub promotion: PipelinePromotion,
+    /// the targets which were promoted and released
+    pub succeeded: Vec<PromotedTarget>,
+    /// the targets which failed to promote, or whose release failed
+    pub failed: Vec<PromotedTarget>,
+}
+
+/// A target of a promotion.
+#[derive(Debug, Clone)]
+pub struct PromotedTarget {
+    /// unique app identifier
+    pub app_id: String,
+    /// the release of the promoted code, if it was created
+    pub release: Option<Release>,
+    /// why the target failed
+    pub error_message: Option<String>,
+}
+
+/// Why a promotion could not be run.
+#[derive(Debug)]
+pub enum PromotionError {
+    /// A request failed
+    Api(HerokuApiFailure),
+    /// The latest release of the source failed, and `require_successful_source` was set
+    SourceReleaseFailed(Box<PipelineRelease>),
+    /// No targets were given, and the source is not coupled to the pipeline
+    NotCoupled,
+    /// No targets were given, and no app is coupled to the next stage of the pipeline
+    NoTargets,
+    /// The promotion did not complete before the timeout, it can still be followed with its id
+    TimedOut { promotion_id: String },
+}
+
+impl From<HerokuApiFailure> for PromotionError {
+    fn from(error: HerokuApiFailure) -> Self {
+        PromotionError::Api(error)
+    }
+}
+
+impl fmt::Display for PromotionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PromotionError::Api(err) => write!(f, "{}", err),
+            PromotionError::SourceReleaseFailed(release) => write!(
+                f,
+                "The latest release of {}, v{}, failed",
+                release.app.name, release.version
+            ),
+            PromotionError::NotCoupled => write!(f, "The source app is not in the pipeline"),
+            PromotionError::NoTargets => write!(f, "No app to promote to"),
+            PromotionError::TimedOut { promotion_id } => {
+                write!(f, "Timed out waiting for the promotion {}", promotion_id)
+            }
+        }
+    }
+}
+
+impl Error for PromotionError {}
@@ -8,7 +8,6 @@ pub use get::{ReleaseInfo, ReleaseList};
 pub use post::{ReleaseCreate, ReleaseCreateParams, ReleaseRollback, ReleaseRollbackParams};
 
 impl ApiResult for Release {}
-impl ApiResult for Vec<Release> {}
 
 /// Heroku Release
 ///
@@ -18,6 +17,7 @@ impl ApiResult for Vec<Release> {}
 ///
 /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#release)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Release {
     /// add-on plans installed on the app for this release
     pub addon_plan_names: Vec<i64>,
@@ -43,29 +43,48 @@ pub struct Release {
     pub user: User,
     /// unique version assigned to the release
     pub version: i64,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// An app represents the program that you would like to deploy and run on Heroku.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct App {
     /// unique identifier
     pub id: String,
     /// name of app pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// A slug running the release
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Slug {
     /// unique identifier of slug
     pub id: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// User account running release
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct User {
     /// identifier of an account
     pub id: String,
     /// unique email address
     pub email: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
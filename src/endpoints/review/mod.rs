@@ -16,10 +16,7 @@ pub use post::{
 };
 
 impl ApiResult for ReviewApp {}
-impl ApiResult for Vec<ReviewApp> {}
-
 impl ApiResult for ReviewAppConfig {}
-impl ApiResult for Vec<ReviewAppConfig> {}
 
 pub use review_app::ReviewApp;
 pub use review_app_config::ReviewAppConfig;
@@ -38,6 +35,7 @@ mod review_app {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#review-app)
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct ReviewApp {
         /// the Heroku app associated to this review app
         pub app: Option<App>,
@@ -68,26 +66,50 @@ mod review_app {
         pub fork_repo: Option<ForkRepo>,
         /// GitHub Pull Request number if the Review app was created automatically
         pub pr_number: Option<i64>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct App {
         /// unique identifier
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct AppSetup {
         /// unique identifier of app setup
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Pipeline {
         /// unique identifier of pipeline
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct ForkRepo {
         /// repository id of the fork the branch resides in
         pub id: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -102,6 +124,7 @@ mod review_app_config {
     // TODO(ben): Heroku docs have the wrong response in the documentation on the pipeline field.
     //      It's represented as a `pipeline_id: String` field, but in fact in a Pipeline object with the id field. Double check edge cases.
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct ReviewAppConfig {
         /// repo
         pub repo: Repo,
@@ -119,18 +142,33 @@ mod review_app_config {
         pub wait_for_ci: bool,
         /// A unique prefix that will be used to create review app names
         pub base_name: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Repo {
         /// repository id
         pub id: i32,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Pipeline {
         /// pipeline id
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct DeployTarget {
         /// unique identifier of deploy target
         ///  pattern: `(^[a-fA-F0-9]{8}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{12}$
@@ -139,5 +177,9 @@ mod review_app_config {
         /// type of deploy target
         ///  pattern: `(^space$
         pub type_field: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
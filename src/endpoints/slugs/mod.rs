@@ -9,12 +9,15 @@ pub mod get;
 pub mod patch;
 pub mod post;
 pub mod put;
+#[cfg(feature = "deploy")]
+pub mod release;
 
 pub use get::SlugDetails;
 pub use post::{SlugCreate, SlugCreateParams};
+#[cfg(feature = "deploy")]
+pub use release::{release_slug, SlugRelease};
 
 impl ApiResult for Slug {}
-impl ApiResult for Vec<Slug> {}
 
 /// Slug
 ///
@@ -24,6 +27,7 @@ impl ApiResult for Vec<Slug> {}
 ///
 /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#slug)
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Slug {
     /// slug blob
     pub blob: Blob,
@@ -47,26 +51,45 @@ pub struct Slug {
     pub stack: Stack,
     /// when slug was updated
     pub updated_at: DateTime<Utc>,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Blob {
     /// method to be used to interact with the slug blob
     pub method: String,
     /// URL to interact with the slug blob
     pub url: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ProcessTypes {
     /// hash mapping process type names to their respective command
     pub web: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Stack {
     /// identifier of stack
     pub id: String,
     /// when slug was updated
     pub name: String,
+    /// fields returned by Heroku which are not modeled by this struct
+    #[cfg(feature = "extra_fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -0,0 +1,139 @@
+//! Releasing a prebuilt slug: slug creation, upload and release.
+use super::{Slug, SlugCreate, SlugCreateParams};
+use crate::endpoints::releases::{Release, ReleaseCreate, ReleaseCreateParams};
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    response::{match_failure, ApiResponse},
+    transport::HttpRequest,
+    HttpApiClient,
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io;
+use std::path::{Component, Path};
+
+/// Release a prebuilt slug archive, skipping the build.
+///
+/// `tarball` is a gzipped tarball whose root is an `./app` directory, as described in
+/// [Creating Slugs from Scratch](https://devcenter.heroku.com/articles/platform-api-deploying-slugs). It is checked
+/// before anything is created. Then a slug is created with its SHA256 checksum, the tarball is uploaded to the slug's
+/// blob, and a release of the slug is created.
+///
+/// `stack` defaults to the app's stack.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::slugs::release_slug;
+/// use heroku_rs::prelude::*;
+/// use std::collections::HashMap;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let tarball = std::fs::read("slug.tgz").unwrap();
+/// let mut process_types = HashMap::new();
+/// process_types.insert("web", "./bin/web -p $PORT");
+///
+/// let released = release_slug(&api_client, "APP_ID", &tarball, process_types, Some("heroku-20")).unwrap();
+/// println!("Released v{}", released.release.version);
+/// ```
+pub fn release_slug(
+    client: &HttpApiClient,
+    app_id: &str,
+    tarball: &[u8],
+    process_types: HashMap<&str, &str>,
+    stack: Option<&str>,
+) -> ApiResponse<SlugRelease> {
+    validate_slug(tarball)?;
+    let checksum = checksum(tarball);
+
+    let slug = client.request(&SlugCreate {
+        app_id,
+        params: SlugCreateParams {
+            process_types,
+            buildpack_provided_description: None,
+            checksum: Some(&checksum),
+            commit: None,
+            commit_description: None,
+            stack,
+        },
+    })?;
+
+    let method = http::Method::from_bytes(slug.blob.method.to_uppercase().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let url = url::Url::parse(&slug.blob.url)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut upload = HttpRequest::new(method, url);
+    upload.body = Some(tarball.to_vec());
+    let response = client.transport().send(upload)?;
+    if !response.status.is_success() {
+        return Err(match_failure(response));
+    }
+
+    let release = client.request(&ReleaseCreate {
+        app_id,
+        params: ReleaseCreateParams {
+            slug: &slug.id,
+            description: None,
+        },
+    })?;
+
+    Ok(SlugRelease { slug, release })
+}
+
+/// The checksum of a slug archive, as Heroku expects it: `SHA256:` followed by the hex digest.
+pub fn checksum(tarball: &[u8]) -> String {
+    let digest = Sha256::digest(tarball);
+    let mut checksum = String::from("SHA256:");
+    for byte in digest.iter() {
+        let _ = write!(checksum, "{:02x}", byte);
+    }
+    checksum
+}
+
+/// Check that a slug archive is a gzipped tarball with everything under its `./app` directory.
+///
+/// Returns an `InvalidData` error naming the first path outside of it.
+pub fn validate_slug(tarball: &[u8]) -> io::Result<()> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(tarball));
+    let mut entries = 0;
+    for entry in archive.entries()? {
+        let entry = entry?;
+        let path = entry.path()?;
+        let mut components = path
+            .components()
+            .filter(|component| *component != Component::CurDir);
+        match components.next() {
+            // the `.` entry of `tar czf slug.tgz ./app`
+            None => continue,
+            Some(Component::Normal(root)) if Path::new(root) == Path::new("app") => entries += 1,
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "slug archive entries must be under ./app, found {}",
+                        path.display()
+                    ),
+                ))
+            }
+        }
+    }
+    if entries == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "slug archive has no ./app directory",
+        ));
+    }
+    Ok(())
+}
+
+/// The result of [`release_slug`][release_slug].
+///
+/// [release_slug]: fn.release_slug.html
+#[derive(Debug, Clone)]
+pub struct SlugRelease {
+    /// the uploaded slug
+    pub slug: Slug,
+    /// the release of the slug
+    pub release: Release,
+}
@@ -24,25 +24,12 @@ pub use put::{
 pub use delete::{SpaceDelete, VPNDelete};
 
 impl ApiResult for Space {}
-impl ApiResult for Vec<Space> {}
-
 impl ApiResult for SpaceAccess {}
-impl ApiResult for Vec<SpaceAccess> {}
-
 impl ApiResult for SpaceNAT {}
-impl ApiResult for Vec<SpaceNAT> {}
-
 impl ApiResult for SpaceTransfer {}
-impl ApiResult for Vec<SpaceTransfer> {}
-
 impl ApiResult for InboundRuleset {}
-impl ApiResult for Vec<InboundRuleset> {}
-
 impl ApiResult for OutboundRuleset {}
-impl ApiResult for Vec<OutboundRuleset> {}
-
 impl ApiResult for VPN {}
-impl ApiResult for Vec<VPN> {}
 
 pub use inbound_ruleset::InboundRuleset;
 pub use outbound_ruleset::OutboundRuleset;
@@ -64,6 +51,7 @@ mod spaces {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#space-1)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Space {
         /// when space was created
         pub created_at: DateTime<Utc>,
@@ -91,28 +79,47 @@ mod spaces {
         pub cidr: String,
         /// The RFC-1918 CIDR that the Private Space will use for the Heroku-managed peering connection that’s automatically created when using Heroku Data add-ons. It must be between a /16 and a /20
         pub data_cidr: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Organization {
         /// unique name of team
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Team {
         /// unique identifier of team
         pub id: String,
         /// unique name of team
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Region {
         /// unique identifier
         pub id: String,
         /// name of region
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -128,6 +135,7 @@ mod space_access {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#space-access)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct SpaceAccess {
         /// space object
         pub space: Space,
@@ -141,26 +149,45 @@ mod space_access {
         pub updated_at: DateTime<Utc>,
         /// account
         pub user: User,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Space {
         /// name of app
         ///  pattern: ^[a-z][a-z0-9-]{1,28}[a-z0-9]$
         pub name: String,
         /// unique identifier
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Permission {
         pub description: String,
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct User {
         /// unique email address
         pub email: String,
         /// identifier of an account
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -176,6 +203,7 @@ mod space_nat {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#space-network-address-translation)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct SpaceNAT {
         /// when network address translation for a space was created
         pub created_at: DateTime<Utc>,
@@ -186,6 +214,10 @@ mod space_nat {
         pub state: String,
         /// when network address translation for a space was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -201,6 +233,7 @@ mod space_transfer {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#space-transfer)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct SpaceTransfer {
         pub created_at: DateTime<Utc>,
         pub id: String,
@@ -213,20 +246,39 @@ mod space_transfer {
         pub updated_at: DateTime<Utc>,
         pub cidr: String,
         pub data_cidr: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Organization {
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Team {
         pub id: String,
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Region {
         pub id: String,
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -242,6 +294,7 @@ mod inbound_ruleset {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#inbound-ruleset)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct InboundRuleset {
         /// unique identifier of an inbound-ruleset
         pub id: String,
@@ -253,19 +306,33 @@ mod inbound_ruleset {
         pub rules: Option<Vec<Rule>>,
         /// unique email address
         pub created_by: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Space {
         /// unique identifier of space
         pub id: String,
         ///  pattern: `^[a-z0-9](?:[a-z0-9]
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     /// example: [{"action":"allow","source":"1.1.1.1/1"}]
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Rule {
         pub action: String,
         pub source: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -281,6 +348,7 @@ mod outbound_ruleset {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#outbound-ruleset)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct OutboundRuleset {
         /// unique identifier of an outbound-ruleset
         pub id: String,
@@ -292,21 +360,35 @@ mod outbound_ruleset {
         pub rules: Option<Vec<Rule>>,
         /// unique email address
         pub created_by: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Space {
         /// unique identifier of space
         pub id: String,
         /// unique name of space
         ///  pattern: `^[a-z0-9](?:[a-z0-9]
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Rule {
         pub target: String,
         pub from_port: i64,
         pub to_port: i64,
         pub protocol: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -319,6 +401,7 @@ mod vpn {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#private-spaces-vpn)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct VPN {
         /// VPN unique identifier
         pub id: String,
@@ -339,8 +422,13 @@ mod vpn {
         pub status: String,
         /// Details of the status
         pub status_message: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Tunnel {
         pub last_status_change: String,
         pub ip: String,
@@ -348,5 +436,9 @@ mod vpn {
         pub pre_shared_key: String,
         pub status: String,
         pub status_message: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
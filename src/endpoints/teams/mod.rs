@@ -28,27 +28,13 @@ pub use put::{
 };
 
 impl ApiResult for Team {}
-impl ApiResult for Vec<Team> {}
-
 impl ApiResult for TeamApp {}
-impl ApiResult for Vec<TeamApp> {}
-
-impl ApiResult for Vec<TeamAppPermission> {}
-
+impl ApiResult for TeamAppPermission {}
 impl ApiResult for TeamFeature {}
-impl ApiResult for Vec<TeamFeature> {}
-
 impl ApiResult for TeamInvitation {}
-impl ApiResult for Vec<TeamInvitation> {}
-
 impl ApiResult for TeamInvoice {}
-impl ApiResult for Vec<TeamInvoice> {}
-
 impl ApiResult for TeamMember {}
-impl ApiResult for Vec<TeamMember> {}
-
 impl ApiResult for TeamPreferences {}
-impl ApiResult for Vec<TeamPreferences> {}
 
 pub use team::Team;
 pub use team_app::TeamApp;
@@ -71,6 +57,7 @@ mod team {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#team)
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Team {
         pub id: String,
         /// when the team was created
@@ -98,24 +85,38 @@ mod team {
         pub type_field: String,
         /// when the team was updated
         pub updated_at: DateTime<Utc>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// Entererprise account associated with the Team
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct EnterpriseAccount {
         /// unique identifier of the enterprise account
         pub id: String,
         /// unique name of the enterprise account
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     /// Identity Provider associated with the Team
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct IdentityProvider {
         /// unique identifier of this identity provider
         pub id: String,
         /// user-friendly unique identifier for this identity provider
         pub slug: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -131,6 +132,7 @@ mod team_app {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#team-app)
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TeamApp {
         /// when app was archived
         pub archived_at: Option<DateTime<Utc>>,
@@ -177,47 +179,81 @@ mod team_app {
         /// web URL of app
         /// pattern: ^https?://[a-z][a-z0-9-]{3,30}\.herokuapp\.com/$
         pub web_url: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct BuildStack {
         /// identifier of stack
         pub id: String,
         /// unique name
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Team {
         /// unique name of team
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Owner {
         /// unique email address
         pub email: String,
         /// identifier of an account
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Region {
         /// unique identifier
         pub id: String,
         /// name of region
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Space {
         /// unique identifier of space
         pub id: String,
         /// unique name of space
         /// pattern: `^[a-z0-9](?:[a-z0-9]
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Stack {
         /// identifier of stack
         pub id: String,
         /// unique name
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -231,11 +267,16 @@ mod team_permission {
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#team-app-permission)
     ///
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TeamAppPermission {
         /// The name of the app permission.
         pub name: String,
         /// A description of what the app permission allows.
         pub description: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -251,6 +292,7 @@ mod team_feature {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#team-feature)
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TeamFeature {
         /// when team feature was created
         pub created_at: DateTime<Utc>,
@@ -272,6 +314,10 @@ mod team_feature {
         pub display_name: String,
         /// e-mail to send feedback about the feature
         pub feedback_email: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -287,6 +333,7 @@ mod team_invitation {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#team-invitation)
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TeamInvitation {
         /// when invitation was created
         pub created_at: DateTime<Utc>,
@@ -303,8 +350,13 @@ mod team_invitation {
         pub updated_at: DateTime<Utc>,
         /// account
         pub user: User,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct InvitedBy {
         /// unique email address
         pub email: String,
@@ -312,15 +364,25 @@ mod team_invitation {
         pub id: String,
         /// full name of the account owner
         pub name: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Team {
         /// unique identifier of team
         pub id: String,
         /// unique name of team
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct User {
         /// unique email address
         pub email: String,
@@ -328,6 +390,10 @@ mod team_invitation {
         pub id: String,
         /// full name of the account owner
         pub name: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -343,6 +409,7 @@ mod team_invoice {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#team-invoice)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TeamInvoice {
         /// total add-ons charges in on this invoice
         pub addons_total: i64,
@@ -376,6 +443,10 @@ mod team_invoice {
         pub updated_at: DateTime<Utc>,
         /// The total amount of hours consumed across dyno types.
         pub weighted_dyno_hours: i64,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -391,6 +462,7 @@ mod team_member {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#team-member)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TeamMember {
         /// when the membership record was created
         pub created_at: DateTime<Utc>,
@@ -411,8 +483,13 @@ mod team_member {
         pub updated_at: DateTime<Utc>,
         /// account
         pub user: User,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct IdentityProvider {
         /// unique identifier of this identity provider
         pub id: String,
@@ -422,8 +499,13 @@ mod team_member {
         pub redacted: bool,
         /// account owner
         pub owner: Owner,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Owner {
         /// unique identifier of the owner
         pub id: String,
@@ -433,8 +515,13 @@ mod team_member {
         /// one of:"team" or "enterprise-account"
         #[serde(rename = "type")]
         pub type_field: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct User {
         /// unique email address
         pub email: String,
@@ -442,6 +529,10 @@ mod team_member {
         pub id: String,
         /// full name of the account owner
         pub name: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -455,6 +546,7 @@ mod team_preferences {
     ///
     /// [For more information please refer to the Heroku documentation](https://devcenter.heroku.com/articles/platform-api-reference#team-preferences)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TeamPreferences {
         /// The default permission used when adding new members to the team
         ///  one of:"admin" or "member" or "viewer" or null
@@ -463,5 +555,9 @@ mod team_preferences {
         /// Whether whitelisting rules should be applied to add-on installations
         #[serde(rename = "whitelisting-enabled")]
         pub whitelisting_enabled: Option<bool>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
@@ -0,0 +1,171 @@
+//! Following a test run to completion, with the output of its nodes.
+use super::{report, TestCase, TestCaseList, TestNode, TestNodeList, TestRun, TestRunDetails};
+use crate::endpoints::builds::BuildOutputStream;
+use crate::framework::{apiclient::HerokuApiClient, response::ApiResponse, HttpApiClient};
+use std::collections::HashSet;
+use std::thread;
+use std::time::Duration;
+
+/// Which stream of a test node a line comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeStream {
+    /// the `setup_stream_url`, the build of the test app
+    Setup,
+    /// the `output_stream_url`, the output of the tests
+    Output,
+}
+
+/// TestRunFollower
+///
+/// Follows a test run until it is done: streams the setup, then the output, of each of its nodes,
+/// and returns the run with its test cases.
+///
+/// # Example:
+///
+/// Following a Heroku CI run, and exporting its results for another CI dashboard.
+/// ```rust,no_run
+/// use heroku_rs::endpoints::testing::TestRunFollower;
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let result = TestRunFollower::new(&api_client, "RUN_ID")
+///     .follow(|node, _stream, line| println!("[node {}] {}", node.index, line))
+///     .unwrap();
+///
+/// std::fs::write("heroku-ci.xml", result.junit()).unwrap();
+/// std::fs::write("heroku-ci.tap", result.tap()).unwrap();
+/// ```
+pub struct TestRunFollower<'a> {
+    client: &'a HttpApiClient,
+    run_id: String,
+    poll_interval: Duration,
+}
+
+impl<'a> TestRunFollower<'a> {
+    pub fn new(client: &'a HttpApiClient, run_id: &str) -> TestRunFollower<'a> {
+        TestRunFollower {
+            client,
+            run_id: run_id.to_string(),
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+
+    /// # poll_interval: how long to wait between two checks of the run and its nodes, 2 seconds by default
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Follow the run, calling `callback` with every line of its nodes' setup and output.
+    ///
+    /// Nodes are listed again on every check of the run until it is done, and followed one after the other,
+    /// by index. Their streams are read from the start, so no line is lost while another node is followed.
+    pub fn follow<F>(self, mut callback: F) -> ApiResponse<TestRunResult>
+    where
+        F: FnMut(&TestNode, NodeStream, &str),
+    {
+        let client = self.client;
+        let run_id = self.run_id.as_str();
+
+        let mut followed = HashSet::new();
+        let run = loop {
+            // The run is checked before its nodes, so that no node is missed once it is done.
+            let run = client.request(&TestRunDetails { run_id })?;
+            let mut nodes = client.request(&TestNodeList { run_id })?;
+            nodes.retain(|node| !followed.contains(&node.id));
+            nodes.sort_by_key(|node| node.index);
+            for node in &nodes {
+                self.follow_node(node, &mut callback)?;
+                followed.insert(node.id.clone());
+            }
+            if is_finished(&run.status) {
+                break run;
+            }
+            if nodes.is_empty() {
+                thread::sleep(self.poll_interval);
+            }
+        };
+        let nodes = client.request(&TestNodeList { run_id })?;
+        let cases = client.request(&TestCaseList { run_id })?;
+
+        Ok(TestRunResult { run, nodes, cases })
+    }
+
+    /// Stream the setup, then the output, of a node until it is done with each.
+    fn follow_node<F>(&self, node: &TestNode, callback: &mut F) -> ApiResponse<()>
+    where
+        F: FnMut(&TestNode, NodeStream, &str),
+    {
+        let client = self.client;
+        let run_id = self.run_id.as_str();
+        let streams = [
+            (NodeStream::Setup, &node.setup_stream_url),
+            (NodeStream::Output, &node.output_stream_url),
+        ];
+        for (stream, url) in streams.iter() {
+            let stream = *stream;
+            let node_id = node.id.clone();
+            BuildOutputStream::new(client, url)
+                .retry_delay(self.poll_interval)
+                .until(move || {
+                    let status = client
+                        .request(&TestNodeList { run_id })?
+                        .into_iter()
+                        .find(|node| node.id == node_id)
+                        .map(|node| node.status)
+                        .unwrap_or_default();
+                    Ok(match stream {
+                        NodeStream::Setup => !is_setting_up(&status),
+                        NodeStream::Output => is_finished(&status) || status.is_empty(),
+                    })
+                })
+                .for_each_line(|line| callback(node, stream, line))?;
+        }
+        Ok(())
+    }
+}
+
+/// The statuses of a test run, or node, which is done.
+fn is_finished(status: &str) -> bool {
+    matches!(status, "succeeded" | "failed" | "errored" | "cancelled")
+}
+
+/// The statuses of a test node which is not running its tests yet.
+fn is_setting_up(status: &str) -> bool {
+    matches!(status, "pending" | "creating" | "building")
+}
+
+/// The result of a [`TestRunFollower`][follower].
+///
+/// [follower]: struct.TestRunFollower.html
+#[derive(Debug, Clone)]
+pub struct TestRunResult {
+    /// the run, once done
+    pub run: TestRun,
+    /// the nodes of the run
+    pub nodes: Vec<TestNode>,
+    /// the test cases of the run
+    pub cases: Vec<TestCase>,
+}
+
+impl TestRunResult {
+    /// Whether the run succeeded.
+    pub fn succeeded(&self) -> bool {
+        self.run.status == "succeeded"
+    }
+
+    /// The test cases as a JUnit XML report, see [`report::junit`][junit].
+    ///
+    /// [junit]: report/fn.junit.html
+    pub fn junit(&self) -> String {
+        let name = format!("{} #{}", self.run.pipeline.id, self.run.number);
+        report::junit(&name, &self.cases)
+    }
+
+    /// The test cases as a TAP stream, see [`report::tap`][tap].
+    ///
+    /// [tap]: report/fn.tap.html
+    pub fn tap(&self) -> String {
+        report::tap(&self.cases)
+    }
+}
@@ -1,23 +1,23 @@
 use crate::framework::response::ApiResult;
 
 pub mod delete;
+#[cfg(feature = "builds")]
+pub mod follow;
 pub mod get;
 pub mod patch;
 pub mod post;
 pub mod put;
+pub mod report;
 
+#[cfg(feature = "builds")]
+pub use follow::{NodeStream, TestRunFollower, TestRunResult};
 pub use get::{TestCaseList, TestNodeList, TestRunDetails, TestRunDetailsByPipeline, TestRunList};
 pub use patch::{TestRunUpdate, TestRunUpdateParams};
 pub use post::{TestRunCreate, TestRunCreateParams};
 
 impl ApiResult for TestCase {}
-impl ApiResult for Vec<TestCase> {}
-
 impl ApiResult for TestNode {}
-impl ApiResult for Vec<TestNode> {}
-
 impl ApiResult for TestRun {}
-impl ApiResult for Vec<TestRun> {}
 
 pub use test_case::TestCase;
 pub use test_node::TestNode;
@@ -35,6 +35,7 @@ mod test_case {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#test-case)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TestCase {
         /// unique identifier of a test case
         pub id: String,
@@ -56,17 +57,31 @@ mod test_case {
         pub test_node: TestNode,
         /// Test run
         pub test_run: TestRun,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TestNode {
         /// unique identifier of a test node
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TestRun {
         /// unique identifier of a test run
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -82,6 +97,7 @@ mod test_node {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#test-node)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TestNode {
         /// when test node was created
         pub created_at: DateTime<Utc>,
@@ -110,23 +126,42 @@ mod test_node {
         pub updated_at: DateTime<Utc>,
         /// test run
         pub test_run: TestRun,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Dyno {
         /// unique identifier or the name of this process on this dyno
         pub id: String,
         /// a URL to stream output from for debug runs or null for non-debug runs
         pub attach_url: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Pipeline {
         /// unique identifier or name of pipeline
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TestRun {
         /// unique identifier of a test run
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
 
@@ -143,6 +178,7 @@ mod test_run {
     ///
     /// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#test-run)
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct TestRun {
         /// the email of the actor triggering the test run
         pub actor_email: String,
@@ -183,23 +219,43 @@ mod test_run {
         pub user: User,
         /// human friently warning emitted during the test run
         pub warning_message: Option<String>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Dyno {
         /// dyno size (default: “standard-1X”)
         pub size: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Organization {
         /// unique name of team
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Pipeline {
         /// unique identifier or name of pipeline
         pub id: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct User {
         /// whether to allow third party web activity tracking
         ///  default: true
@@ -236,8 +292,13 @@ mod test_run {
         pub default_organization: Option<DefaultOrganization>,
         /// team selected by default
         pub default_team: Option<DefaultTeam>,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct IdentityProvider {
         /// unique identifier of this identity provider
         pub id: String,
@@ -247,13 +308,23 @@ mod test_run {
         pub organization: Organization,
         /// identity provider owner
         pub owner: Owner,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Team {
         /// unique name of team
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct Owner {
         /// unique identifier of the owner
         pub id: String,
@@ -263,19 +334,33 @@ mod test_run {
         ///  one of:"team" or "enterprise-account"
         #[serde(rename = "type")]
         pub type_field: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct DefaultOrganization {
         /// unique identifier of team
         pub id: String,
         /// unique name of team
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
     #[derive(Deserialize, Serialize, Debug, Clone)]
+    #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
     pub struct DefaultTeam {
         /// unique identifier of team
         pub id: String,
         /// unique name of team
         pub name: String,
+        /// fields returned by Heroku which are not modeled by this struct
+        #[cfg(feature = "extra_fields")]
+        #[serde(flatten)]
+        pub extra: serde_json::Map<String, serde_json::Value>,
     }
 }
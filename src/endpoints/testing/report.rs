@@ -0,0 +1,139 @@
+//! Exporting the test cases of a test run, for other CI dashboards.
+use super::TestCase;
+use std::fmt::Write;
+
+/// Export test cases as a JUnit XML report, in a single test suite called `name`.
+///
+/// Failed cases become `<failure>`s with their diagnostic, and cases with a `SKIP` or `TODO` directive
+/// become `<skipped>` ones.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::endpoints::testing::report;
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let cases = api_client.request(&TestCaseList::new("RUN_ID")).unwrap();
+///
+/// std::fs::write("heroku-ci.xml", report::junit("heroku-ci", &cases)).unwrap();
+/// ```
+pub fn junit(name: &str, cases: &[TestCase]) -> String {
+    let cases = sorted(cases);
+    let skipped = cases.iter().filter(|case| is_skipped(case)).count();
+    let failures = cases
+        .iter()
+        .filter(|case| !case.passed && !is_skipped(case))
+        .count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\">",
+        name = escape(name),
+        tests = cases.len(),
+        failures = failures,
+        skipped = skipped,
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\">",
+        name = escape(name),
+        tests = cases.len(),
+        failures = failures,
+        skipped = skipped,
+    );
+    for case in cases {
+        let _ = write!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"{}\"",
+            escape(&case.description),
+            escape(name)
+        );
+        if is_skipped(case) {
+            let _ = writeln!(
+                xml,
+                ">\n      <skipped message=\"{}\"/>\n    </testcase>",
+                escape(case.directive.trim())
+            );
+        } else if !case.passed {
+            let _ = writeln!(
+                xml,
+                ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                escape(first_line(&case.diagnostic)),
+                escape(&case.diagnostic)
+            );
+        } else {
+            xml.push_str("/>\n");
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+/// Export test cases as a [TAP](https://testanything.org) stream.
+///
+/// Diagnostics follow their case as `#` comments, and directives are kept, e.g. `ok 3 - login # SKIP no network`.
+pub fn tap(cases: &[TestCase]) -> String {
+    let cases = sorted(cases);
+    let mut tap = format!("1..{}\n", cases.len());
+    for (index, case) in cases.iter().enumerate() {
+        let _ = write!(
+            tap,
+            "{}ok {}",
+            if case.passed { "" } else { "not " },
+            index + 1
+        );
+        if !case.description.is_empty() {
+            let _ = write!(tap, " - {}", case.description.replace('#', "\\#"));
+        }
+        if !case.directive.trim().is_empty() {
+            let _ = write!(tap, " # {}", case.directive.trim());
+        }
+        tap.push('\n');
+        for line in case
+            .diagnostic
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+        {
+            let _ = writeln!(tap, "# {}", line);
+        }
+    }
+    tap
+}
+
+/// Whether the case was skipped, or is a TODO, so it doesn't count as a failure.
+fn is_skipped(case: &TestCase) -> bool {
+    let directive = case.directive.trim_start().to_ascii_uppercase();
+    directive.starts_with("SKIP") || directive.starts_with("TODO")
+}
+
+fn sorted(cases: &[TestCase]) -> Vec<&TestCase> {
+    let mut cases: Vec<&TestCase> = cases.iter().collect();
+    cases.sort_by_key(|case| case.number);
+    cases
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("")
+        .trim()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // not allowed in XML 1.0
+            c if (c as u32) < 0x20 && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -2,6 +2,7 @@
 use crate::framework::{
     endpoint::HerokuEndpoint,
     response::{ApiResponse, ApiResult},
+    transport::HttpResponse,
 };
 use serde::Serialize;
 
@@ -19,13 +20,15 @@ pub trait HerokuApiClient {
         QueryType: Serialize,
         BodyType: Serialize;
 
-    /// This returns a Result<reqwest::blocking::Response, heroku_rs::framework::response::error::HerokuApiFailure>
+    /// This returns a Result<heroku_rs::framework::transport::HttpResponse, heroku_rs::framework::response::error::HerokuApiFailure>
+    ///
+    /// The response is returned whatever its status, with its headers and unread body.
     ///
     /// This is primarily used for debugging and testing, but can be used if this works better for your use-case.
     fn request_raw<ResultType, QueryType, BodyType>(
         &self,
         endpoint: &dyn HerokuEndpoint<ResultType, QueryType, BodyType>,
-    ) -> ApiResponse<reqwest::blocking::Response>
+    ) -> ApiResponse<HttpResponse>
     where
         ResultType: ApiResult,
         QueryType: Serialize,
@@ -1,4 +1,7 @@
-use reqwest::blocking::RequestBuilder;
+use crate::framework::{
+    response::ApiResponse,
+    transport::{HttpRequest, TransportError},
+};
 
 /// Credentials enum, currently only supporting token authentication
 #[derive(Debug)]
@@ -14,15 +17,21 @@ impl Credentials {
     }
 }
 
-pub trait AuthClient {
-    fn auth(self, credentials: &Credentials) -> Self;
+pub trait AuthClient: Sized {
+    /// Set the headers of the credentials, replacing any header of the same name.
+    fn auth(self, credentials: &Credentials) -> ApiResponse<Self>;
 }
 
-impl AuthClient for RequestBuilder {
-    fn auth(mut self, credentials: &Credentials) -> Self {
-        for (k, v) in credentials.headers() {
-            self = self.header(k, v);
+impl AuthClient for HttpRequest {
+    fn auth(mut self, credentials: &Credentials) -> ApiResponse<Self> {
+        for (name, value) in credentials.headers() {
+            // The value is left out of the error, as it holds the token.
+            let value = http::HeaderValue::from_str(&value).map_err(|e| {
+                let message = format!("invalid {} header value: {}", name, e);
+                TransportError::new(self.url.clone(), message)
+            })?;
+            self.headers.insert(name, value);
         }
-        self
+        Ok(self)
     }
 }
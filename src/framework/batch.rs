@@ -0,0 +1,121 @@
+//! This module contains the batch execution of endpoints on the synchronous client.
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    endpoint::HerokuEndpoint,
+    response::{match_response, ApiResponse, ApiResult},
+    HttpApiClient,
+};
+use serde::Serialize;
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// The header Heroku uses to report the remaining rate limit budget of the account.
+const RATE_LIMIT_REMAINING: &str = "RateLimit-Remaining";
+
+/// Configuration for a batch of requests. Allows users to customize how many requests run at once
+/// and how much of the rate limit budget a batch is allowed to consume.
+pub struct BatchConfig {
+    /// The maximum number of requests in flight at the same time.
+    /// By default 4 requests run concurrently.
+    pub concurrency: usize,
+    /// The number of API calls to leave untouched for other clients of the same account.
+    /// When Heroku reports fewer remaining calls than this, the batch pauses before sending the next request.
+    pub rate_limit_reserve: i64,
+    /// How long to pause when the rate limit reserve has been reached.
+    /// Heroku replenishes the budget at roughly 75 requests per minute.
+    pub rate_limit_pause: Duration,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        BatchConfig {
+            concurrency: 4,
+            rate_limit_reserve: 100,
+            rate_limit_pause: Duration::from_secs(10),
+        }
+    }
+}
+
+impl HttpApiClient {
+    /// Send many requests of the same endpoint type to the Heroku API, with bounded concurrency.
+    ///
+    /// The requests are spread over `config.concurrency` threads.
+    /// The results are returned in the same order as the endpoints, with a success or failure for every item.
+    ///
+    /// # Example:
+    ///
+    /// Restarting all dynos of several apps, four apps at a time.
+    /// ```rust
+    /// use heroku_rs::prelude::*;
+    ///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+    ///
+    /// let apps = vec!["APP_ID_1", "APP_ID_2", "APP_ID_3"];
+    /// let restarts: Vec<DynoAllRestart> = apps.iter().map(|app| DynoAllRestart::new(app)).collect();
+    ///
+    /// let responses = api_client.request_batch(&restarts, &BatchConfig::default());
+    ///
+    /// for (app, response) in apps.iter().zip(responses) {
+    ///     match response {
+    ///         Ok(success) => println!("{}: {:#?}", app, success),
+    ///         Err(e) => println!("{}: Error: {}", app, e),
+    ///     }
+    /// }
+    /// ```
+    pub fn request_batch<ResultType, QueryType, BodyType, Endpoint>(
+        &self,
+        endpoints: &[Endpoint],
+        config: &BatchConfig,
+    ) -> Vec<ApiResponse<ResultType>>
+    where
+        ResultType: ApiResult + Send,
+        QueryType: Serialize,
+        BodyType: Serialize,
+        Endpoint: HerokuEndpoint<ResultType, QueryType, BodyType> + Sync,
+    {
+        let next = AtomicUsize::new(0);
+        // Unknown until the first response comes back.
+        let remaining = AtomicI64::new(i64::MAX);
+        let results: Mutex<Vec<Option<ApiResponse<ResultType>>>> =
+            Mutex::new(endpoints.iter().map(|_| None).collect());
+        let workers = config.concurrency.max(1).min(endpoints.len());
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    if index >= endpoints.len() {
+                        break;
+                    }
+                    while remaining.load(Ordering::SeqCst) < config.rate_limit_reserve {
+                        thread::sleep(config.rate_limit_pause);
+                        // Optimistically assume the budget has been replenished, the next response will tell.
+                        remaining.store(config.rate_limit_reserve, Ordering::SeqCst);
+                    }
+
+                    let response = self.request_raw(&endpoints[index]).and_then(|raw| {
+                        if let Some(value) = raw
+                            .headers
+                            .get(RATE_LIMIT_REMAINING)
+                            .and_then(|value| value.to_str().ok())
+                            .and_then(|value| value.parse().ok())
+                        {
+                            remaining.store(value, Ordering::SeqCst);
+                        }
+                        match_response(raw)
+                    });
+
+                    results.lock().unwrap()[index] = Some(response);
+                });
+            }
+        });
+
+        results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|result| result.expect("every endpoint of the batch has been requested"))
+            .collect()
+    }
+}
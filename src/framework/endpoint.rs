@@ -13,6 +13,18 @@ pub enum Method {
     Patch,
 }
 
+impl From<Method> for http::Method {
+    fn from(method: Method) -> Self {
+        match method {
+            Method::Get => http::Method::GET,
+            Method::Post => http::Method::POST,
+            Method::Delete => http::Method::DELETE,
+            Method::Put => http::Method::PUT,
+            Method::Patch => http::Method::PATCH,
+        }
+    }
+}
+
 /// Heroku Endpoint trait by default has a empty struct and void query types and body types
 ///
 /// This trait is responsible for the majority of the functionality of this crate.
@@ -30,6 +42,10 @@ where
     fn body(&self) -> Option<BodyType> {
         None
     }
+    /// Checks the endpoint before its request is prepared, e.g. that every parameter of the path has a value.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
     fn url(&self, environment: &ApiEnvironment) -> Url {
         Url::from(environment).join(&self.path()).unwrap()
     }
@@ -42,4 +58,8 @@ where
     fn agent(&self) -> &str {
         "heroku_rs"
     }
+    /// Additional headers sent with the request. They take precedence over the default ones.
+    fn headers(&self) -> http::HeaderMap {
+        http::HeaderMap::new()
+    }
 }
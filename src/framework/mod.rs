@@ -2,13 +2,26 @@
 
 pub mod apiclient;
 pub mod auth;
+pub mod batch;
 pub mod endpoint;
-mod reqwest_utils;
+pub(crate) mod reconnect;
 pub mod response;
+pub mod sse;
+pub mod streaming;
+pub mod transport;
+pub mod waiter;
 
-use crate::framework::{apiclient::HerokuApiClient, auth::AuthClient, response::match_response};
+pub use batch::BatchConfig;
+pub use waiter::Waiter;
+
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    auth::AuthClient,
+    response::{match_response, ApiResponse},
+    transport::{HttpRequest, HttpResponse, Transport, TransportError},
+};
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
 use failure::Fallible;
-use reqwest_utils::match_reqwest_method;
 use serde::Serialize;
 use std::time::Duration;
 
@@ -35,8 +48,8 @@ pub struct HttpApiClient {
     environment: ApiEnvironment,
     /// The authentication credential
     credentials: auth::Credentials,
-    /// The blocking client
-    http_client: reqwest::blocking::Client,
+    /// The HTTP transport the requests are sent with
+    transport: Box<dyn Transport>,
 }
 
 /// Configuration for the API client. Allows users to customize its behaviour.
@@ -71,22 +84,15 @@ impl HttpApiClient {
     ///    Ok(())
     /// }
     /// ```
+    #[cfg(any(feature = "reqwest", feature = "ureq"))]
     pub fn create(token: &str) -> Fallible<HttpApiClient> {
         let credentials: auth::Credentials = auth::Credentials::UserAuthToken {
             token: String::from(token),
         };
         let config: HttpApiClientConfig = HttpApiClientConfig::default();
         let environment: ApiEnvironment = ApiEnvironment::Production;
-        let http_client = reqwest::blocking::Client::builder()
-            .timeout(config.http_timeout)
-            .default_headers(config.default_headers)
-            .build()?;
 
-        Ok(HttpApiClient {
-            environment,
-            credentials,
-            http_client,
-        })
+        HttpApiClient::new(credentials, config, environment)
     }
 
     /// # Example 2:
@@ -118,54 +124,135 @@ impl HttpApiClient {
     /// }
     ///
     /// ```
+    ///
+    /// The requests are sent with `reqwest`, or with `ureq` if only the `ureq` feature is enabled.
+    #[cfg(any(feature = "reqwest", feature = "ureq"))]
     pub fn new(
         credentials: auth::Credentials,
         config: HttpApiClientConfig,
         environment: ApiEnvironment,
     ) -> Fallible<HttpApiClient> {
-        let http_client = reqwest::blocking::Client::builder()
-            .timeout(config.http_timeout)
-            .default_headers(config.default_headers)
-            .build()?;
+        #[cfg(feature = "reqwest")]
+        let transport = transport::ReqwestTransport::new(&config)?;
+        #[cfg(not(feature = "reqwest"))]
+        let transport = transport::UreqTransport::new(&config);
 
-        Ok(HttpApiClient {
+        Ok(HttpApiClient::with_transport(
+            credentials,
+            environment,
+            transport,
+        ))
+    }
+
+    /// # Example 3:
+    /// Creating a client which sends its requests with a [`Transport`][transport] of your choice.
+    ///
+    /// This example answers every request in memory, which is handy for tests.
+    /// ```rust
+    /// use heroku_rs::framework::transport::MemoryTransport;
+    /// use heroku_rs::prelude::*;
+    ///
+    /// let credentials = Credentials::UserAuthToken {
+    ///     token: String::from("API_KEY"),
+    /// };
+    /// let transport = MemoryTransport::responding(http::StatusCode::OK, "[]");
+    ///
+    /// let api_client = HttpApiClient::with_transport(credentials, ApiEnvironment::Production, transport);
+    ///
+    /// // you can start making requests here with api_client
+    /// ```
+    ///
+    /// [transport]: transport/trait.Transport.html
+    pub fn with_transport<T: Transport + 'static>(
+        credentials: auth::Credentials,
+        environment: ApiEnvironment,
+        transport: T,
+    ) -> HttpApiClient {
+        HttpApiClient {
             environment,
             credentials,
-            http_client,
-        })
+            transport: Box::new(transport),
+        }
+    }
+
+    /// The transport the requests are sent with.
+    ///
+    /// Use it to reach URLs outside of the API, e.g. the output streams of builds.
+    pub fn transport(&self) -> &dyn Transport {
+        &*self.transport
     }
 }
 
-impl<'a> HerokuApiClient for HttpApiClient {
-    /// Synchronously send a request to the Heroku API.
-    fn request<ResultType, QueryType, BodyType>(
+impl HttpApiClient {
+    /// Prepare a request for the endpoint, with the body, the Heroku headers and the credentials.
+    pub(crate) fn prepare<ResultType, QueryType, BodyType>(
         &self,
         endpoint: &dyn endpoint::HerokuEndpoint<ResultType, QueryType, BodyType>,
-    ) -> response::ApiResponse<ResultType>
+    ) -> ApiResponse<HttpRequest>
     where
         ResultType: response::ApiResult,
         QueryType: Serialize,
         BodyType: Serialize,
     {
-        // Build the request
-        let mut request = self
-            .http_client
-            .request(
-                match_reqwest_method(endpoint.method()),
-                endpoint.url(&self.environment),
-            )
-            .query(&endpoint.query());
+        let mut url = endpoint.url(&self.environment);
+        if let Err(message) = endpoint.validate() {
+            return Err(TransportError::new(url, message).into());
+        }
+        {
+            let mut pairs = url.query_pairs_mut();
+            let query = endpoint.query();
+            if let Err(e) = query.serialize(serde_urlencoded::Serializer::new(&mut pairs)) {
+                drop(pairs);
+                return Err(TransportError::new(url, e).into());
+            }
+        }
+        if url.query() == Some("") {
+            url.set_query(None);
+        }
+        let mut request = HttpRequest::new(endpoint.method().into(), url);
 
+        // Add body if one was passed
         if let Some(body) = endpoint.body() {
-            request = request.body(serde_json::to_string(&body).unwrap());
-            request = request.header(reqwest::header::CONTENT_TYPE, endpoint.content_type());
+            request.body = Some(serde_json::to_vec(&body)?);
+            let content_type = header_value(&request, endpoint.content_type())?;
+            request.headers.insert(http::header::CONTENT_TYPE, content_type);
         }
 
-        request = request.header(reqwest::header::ACCEPT, endpoint.version());
-        request = request.header(reqwest::header::USER_AGENT, endpoint.agent());
-        request = request.auth(&self.credentials);
+        let accept = header_value(&request, endpoint.version())?;
+        request.headers.insert(http::header::ACCEPT, accept);
+        let agent = header_value(&request, endpoint.agent())?;
+        request.headers.insert(http::header::USER_AGENT, agent);
+        // Endpoint headers replace the default ones of the same name, but not the credentials.
+        request.headers.extend(endpoint.headers());
+        request.auth(&self.credentials)
+    }
+
+    /// Send a prepared request with the transport of the client.
+    pub(crate) fn send(&self, request: HttpRequest) -> ApiResponse<HttpResponse> {
+        Ok(self.transport.send(request)?)
+    }
+}
 
-        let response = request.send()?;
+/// A header value of the endpoint, which fails the request when it has characters a header cannot hold.
+fn header_value(request: &HttpRequest, value: &str) -> ApiResponse<http::HeaderValue> {
+    http::HeaderValue::from_str(value).map_err(|e| {
+        let message = format!("invalid header value {:?}: {}", value, e);
+        TransportError::new(request.url.clone(), message).into()
+    })
+}
+
+impl<'a> HerokuApiClient for HttpApiClient {
+    /// Synchronously send a request to the Heroku API.
+    fn request<ResultType, QueryType, BodyType>(
+        &self,
+        endpoint: &dyn endpoint::HerokuEndpoint<ResultType, QueryType, BodyType>,
+    ) -> response::ApiResponse<ResultType>
+    where
+        ResultType: response::ApiResult,
+        QueryType: Serialize,
+        BodyType: Serialize,
+    {
+        let response = self.send(self.prepare(endpoint)?)?;
 
         match_response(response)
     }
@@ -173,32 +260,12 @@ impl<'a> HerokuApiClient for HttpApiClient {
     fn request_raw<ResultType, QueryType, BodyType>(
         &self,
         endpoint: &dyn endpoint::HerokuEndpoint<ResultType, QueryType, BodyType>,
-    ) -> response::ApiResponse<reqwest::blocking::Response>
+    ) -> response::ApiResponse<HttpResponse>
     where
         ResultType: response::ApiResult,
         QueryType: Serialize,
         BodyType: Serialize,
     {
-        // Build the raw request
-        let mut request = self
-            .http_client
-            .request(
-                match_reqwest_method(endpoint.method()),
-                endpoint.url(&self.environment),
-            )
-            .query(&endpoint.query());
-
-        // Add body if one was passed
-        if let Some(body) = endpoint.body() {
-            request = request.body(serde_json::to_string(&body).unwrap());
-            request = request.header(reqwest::header::CONTENT_TYPE, endpoint.content_type());
-        }
-
-        request = request.header(reqwest::header::ACCEPT, endpoint.version());
-        request = request.header(reqwest::header::USER_AGENT, endpoint.agent());
-        request = request.auth(&self.credentials);
-
-        let response = request.send()?;
-        Ok(response)
+        self.send(self.prepare(endpoint)?)
     }
 }
@@ -0,0 +1,184 @@
+//! This module contains what the streams of the API share to survive a dropped connection.
+//!
+//! Heroku closes its output streams and log sessions whenever it wants, and connections drop. The streams built
+//! on `Reconnect` connect again after a failure, and give up after too many failures in a row.
+use crate::framework::{
+    response::{ApiResponse, HerokuApiFailure},
+    transport::HttpRequest,
+};
+use std::io::{self, BufRead};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The longest uninterrupted wait of a cancellable stream, so cancellation is noticed quickly.
+const CANCEL_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How a stream reconnects after a failure, and when it is done.
+pub(crate) struct Reconnect<'a> {
+    /// how many times in a row to reconnect after a failure
+    pub(crate) max_reconnects: u32,
+    /// how long to wait before reconnecting
+    pub(crate) retry_delay: Duration,
+    reconnects: u32,
+    finished: Option<Box<dyn FnMut() -> ApiResponse<bool> + 'a>>,
+    cancel: Option<Arc<AtomicBool>>,
+    done: bool,
+}
+
+impl<'a> Reconnect<'a> {
+    /// Reconnect at most 10 times in a row, waiting `retry_delay` before each.
+    pub(crate) fn new(retry_delay: Duration) -> Reconnect<'a> {
+        Reconnect {
+            max_reconnects: 10,
+            retry_delay,
+            reconnects: 0,
+            finished: None,
+            cancel: None,
+            done: false,
+        }
+    }
+
+    /// Tell whether the stream is complete when the server closes it, instead of ending it right away.
+    pub(crate) fn until<F>(&mut self, finished: F)
+    where
+        F: FnMut() -> ApiResponse<bool> + 'a,
+    {
+        self.finished = Some(Box::new(finished));
+    }
+
+    /// End the stream once `cancel` is set, e.g. from another thread.
+    pub(crate) fn cancel(&mut self, cancel: Arc<AtomicBool>) {
+        self.cancel = Some(cancel);
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        match &self.cancel {
+            Some(cancel) => cancel.load(Ordering::SeqCst),
+            None => false,
+        }
+    }
+
+    /// Whether the stream ended, or was cancelled.
+    pub(crate) fn is_done(&self) -> bool {
+        self.done || self.is_cancelled()
+    }
+
+    /// End the stream, the iterator returns `None` from now on.
+    pub(crate) fn finish(&mut self) {
+        self.done = true;
+    }
+
+    /// Data was received, so the next failure is the first in a row.
+    pub(crate) fn received(&mut self) {
+        self.reconnects = 0;
+    }
+
+    /// Retry after a failure, or give up with it once the reconnects are exhausted.
+    ///
+    /// Client errors, e.g. an expired stream URL, and responses or URLs which can't be parsed are never retried.
+    pub(crate) fn retry(&mut self, error: HerokuApiFailure) -> Option<HerokuApiFailure> {
+        let permanent = match &error {
+            HerokuApiFailure::Error(status, _) => !status.is_server_error(),
+            HerokuApiFailure::Parse(_) => true,
+            _ => false,
+        };
+        if permanent || self.reconnects >= self.max_reconnects {
+            self.done = true;
+            return Some(error);
+        }
+        self.reconnects += 1;
+        self.wait();
+        None
+    }
+
+    /// The server closed the stream: end it if it is complete, otherwise wait before reconnecting.
+    ///
+    /// Returns whether the stream ended. A failure to tell ends it too.
+    pub(crate) fn closed(&mut self) -> ApiResponse<bool> {
+        let finished = match self.finished.as_mut() {
+            Some(finished) => finished(),
+            None => Ok(true),
+        };
+        match finished {
+            Ok(false) => {
+                self.wait();
+                Ok(false)
+            }
+            finished => {
+                self.done = true;
+                finished
+            }
+        }
+    }
+
+    /// Wait before reconnecting, or until the stream is cancelled.
+    pub(crate) fn wait(&self) {
+        if self.cancel.is_none() {
+            return thread::sleep(self.retry_delay);
+        }
+        let until = Instant::now() + self.retry_delay;
+        while !self.is_cancelled() {
+            let remaining = until.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            thread::sleep(remaining.min(CANCEL_CHECK_INTERVAL));
+        }
+    }
+}
+
+/// A GET request of a stream URL.
+///
+/// An invalid URL fails as a parse error, so it is not retried.
+pub(crate) fn get(url: &str) -> ApiResponse<HttpRequest> {
+    let url = url::Url::parse(url).map_err(|e| {
+        <serde_json::Error as serde::de::Error>::custom(format!(
+            "invalid stream URL {:?}: {}",
+            url, e
+        ))
+    })?;
+    Ok(HttpRequest::new(http::Method::GET, url))
+}
+
+/// A line being read from a stream, which a failure may interrupt, and a reconnection complete.
+#[derive(Default)]
+pub(crate) struct LineBuffer(Vec<u8>);
+
+impl LineBuffer {
+    /// Read up to the end of the line. Bytes read before a failure are kept.
+    pub(crate) fn read_from<R: BufRead + ?Sized>(&mut self, reader: &mut R) -> io::Result<usize> {
+        reader.read_until(b'\n', &mut self.0)
+    }
+
+    /// How many bytes of the line were read.
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether the end of the line was read.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.0.last() == Some(&b'\n')
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// The line read so far, without its line ending, and start a new one.
+    pub(crate) fn take(&mut self) -> String {
+        let mut line = std::mem::take(&mut self.0);
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        String::from_utf8_lossy(&line).into_owned()
+    }
+}
@@ -1,3 +1,4 @@
+use crate::framework::transport::TransportError;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Debug;
@@ -19,9 +20,13 @@ pub struct HerokuApiError {
 #[derive(Debug)]
 pub enum HerokuApiFailure {
     /// If Heroku API returned a Error code, this enum is used to handle the error
-    Error(reqwest::StatusCode, HerokuApiError),
-    /// If there was a invalid response, or the response failed, this enum is used to handle the error
-    Invalid(reqwest::Error),
+    Error(http::StatusCode, HerokuApiError),
+    /// If the request could not be sent, or no response came back, this enum is used to handle the error
+    Invalid(TransportError),
+    /// If a streamed response body could not be parsed, this enum is used to handle the error
+    Parse(serde_json::Error),
+    /// If reading a streamed response body failed midway, this enum is used to handle the error
+    Io(std::io::Error),
 }
 
 impl PartialEq for HerokuApiError {
@@ -45,6 +50,12 @@ impl PartialEq for HerokuApiFailure {
             (HerokuApiFailure::Error(status1, e1), HerokuApiFailure::Error(status2, e2)) => {
                 status1 == status2 && e1 == e2
             }
+            (HerokuApiFailure::Parse(e1), HerokuApiFailure::Parse(e2)) => {
+                e1.to_string() == e2.to_string()
+            }
+            (HerokuApiFailure::Io(e1), HerokuApiFailure::Io(e2)) => {
+                e1.kind() == e2.kind() && e1.to_string() == e2.to_string()
+            }
             _ => false,
         }
     }
@@ -56,12 +67,24 @@ impl fmt::Display for HerokuApiError {
     }
 }
 
-impl From<reqwest::Error> for HerokuApiFailure {
-    fn from(error: reqwest::Error) -> Self {
+impl From<TransportError> for HerokuApiFailure {
+    fn from(error: TransportError) -> Self {
         HerokuApiFailure::Invalid(error)
     }
 }
 
+impl From<serde_json::Error> for HerokuApiFailure {
+    fn from(error: serde_json::Error) -> Self {
+        HerokuApiFailure::Parse(error)
+    }
+}
+
+impl From<std::io::Error> for HerokuApiFailure {
+    fn from(error: std::io::Error) -> Self {
+        HerokuApiFailure::Io(error)
+    }
+}
+
 impl fmt::Display for HerokuApiFailure {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -73,6 +96,8 @@ impl fmt::Display for HerokuApiFailure {
                 write!(f, "{}", output)
             }
             HerokuApiFailure::Invalid(err) => write!(f, "{}", err),
+            HerokuApiFailure::Parse(err) => write!(f, "{}", err),
+            HerokuApiFailure::Io(err) => write!(f, "{}", err),
         }
     }
 }
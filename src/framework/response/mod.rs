@@ -1,5 +1,5 @@
-extern crate reqwest;
 extern crate serde_json;
+use crate::framework::transport::HttpResponse;
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 mod error;
@@ -9,26 +9,27 @@ pub use error::*;
 pub type ApiResponse<T> = Result<T, HerokuApiFailure>;
 
 /// Match the response we just got from the API and return a parsed struct
-pub fn match_response<T: ApiResult>(api_response: reqwest::blocking::Response) -> ApiResponse<T> {
-    let api_status = api_response.status();
-
-    if api_status.is_success() {
-        let parsed_response: Result<T, reqwest::Error> = api_response.json();
-        match parsed_response {
-            Ok(response) => Ok(response),
-            Err(e) => Err(HerokuApiFailure::Invalid(e)),
-        }
+pub fn match_response<T: ApiResult>(api_response: HttpResponse) -> ApiResponse<T> {
+    if api_response.status.is_success() {
+        api_response.json()
     } else {
-        let parsed: Result<HerokuApiError, reqwest::Error> = api_response.json();
-        let errors = parsed.unwrap_or_default();
-        Err(HerokuApiFailure::Error(api_status, errors))
+        Err(match_failure(api_response))
     }
 }
 
+/// Parse the error body of a unsuccessful response we just got from the API
+pub(crate) fn match_failure(api_response: HttpResponse) -> HerokuApiFailure {
+    let api_status = api_response.status;
+    let errors: HerokuApiError = api_response.json().unwrap_or_default();
+    HerokuApiFailure::Error(api_status, errors)
+}
+
 // Some endpoints return empty objects, empty vectors or just ().
 impl ApiResult for Empty {}
 impl ApiResult for () {}
-impl ApiResult for Vec<Empty> {}
+
+// List endpoints return a vector of whatever a single item of the list is.
+impl<T: ApiResult> ApiResult for Vec<T> {}
 
 pub trait ApiResult: DeserializeOwned + Debug {}
 
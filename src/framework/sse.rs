@@ -0,0 +1,402 @@
+//! This module contains the decoding of server-sent events.
+//!
+//! Heroku serves its output streams, e.g. `Build.output_stream_url`, as `text/event-stream` when asked to.
+//! Every event carries an id, so a dropped stream can be resumed with the `Last-Event-Id` header.
+use crate::framework::{
+    endpoint::HerokuEndpoint,
+    reconnect::{self, Reconnect},
+    response::{match_failure, ApiResponse, ApiResult, HerokuApiFailure},
+    transport::HttpRequest,
+    HttpApiClient,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::{self, BufRead, BufReader, Read};
+use std::time::Duration;
+
+/// The header sent when reconnecting, with the id of the last event received.
+const LAST_EVENT_ID: &str = "Last-Event-Id";
+
+/// A server-sent event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// the id of the last event which set one, including this one
+    pub id: Option<String>,
+    /// the type of the event, `message` unless the server sets one
+    pub event: String,
+    /// the data of the event, with its lines joined by `\n`
+    pub data: String,
+}
+
+impl Event {
+    /// Deserialize the data of the event as JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_str(&self.data)
+    }
+}
+
+/// EventReader
+///
+/// An iterator which decodes the server-sent events of any reader, following the
+/// [event stream format](https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation).
+///
+/// # Example:
+///
+/// ```rust
+/// use heroku_rs::framework::sse::EventReader;
+///
+/// let body = "id: 1\ndata: -----> Building on Heroku\n\n: keep-alive\n\nid: 2\ndata: first\ndata: second\n\n";
+/// let events: Vec<_> = EventReader::new(body.as_bytes()).map(Result::unwrap).collect();
+///
+/// assert_eq!(events[0].data, "-----> Building on Heroku");
+/// assert_eq!(events[1].id.as_deref(), Some("2"));
+/// assert_eq!(events[1].data, "first\nsecond");
+/// ```
+pub struct EventReader<R> {
+    reader: BufReader<R>,
+    line: Vec<u8>,
+    /// the id of the event being read, only known to be received once the event is complete
+    id_buffer: Option<String>,
+    last_event_id: Option<String>,
+    retry: Option<Duration>,
+    started: bool,
+}
+
+impl<R: Read> EventReader<R> {
+    pub fn new(reader: R) -> EventReader<R> {
+        EventReader {
+            reader: BufReader::new(reader),
+            line: Vec::new(),
+            id_buffer: None,
+            last_event_id: None,
+            retry: None,
+            started: false,
+        }
+    }
+
+    /// The id of the last complete event which set one.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// The reconnection delay asked by the server with a `retry` field, if any.
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    /// Read the next line, without its line ending. `None` at the end of the stream.
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        // Bytes read before a failure are kept, and completed by the next call.
+        if self.reader.read_until(b'\n', &mut self.line)? == 0 && self.line.is_empty() {
+            return Ok(None);
+        }
+        if self.line.last() != Some(&b'\n') {
+            // An incomplete line at the end of the stream.
+            self.line.clear();
+            return Ok(None);
+        }
+        let mut line = std::mem::take(&mut self.line);
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        if !self.started {
+            self.started = true;
+            if line.starts_with("\u{FEFF}".as_bytes()) {
+                line.drain(..3);
+            }
+        }
+        Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+    }
+
+    fn next_event(&mut self) -> io::Result<Option<Event>> {
+        let mut event = String::new();
+        let mut data: Option<String> = None;
+
+        while let Some(line) = self.read_line()? {
+            if line.is_empty() {
+                self.last_event_id = self.id_buffer.clone();
+                match data.take() {
+                    Some(data) => {
+                        return Ok(Some(Event {
+                            id: self.last_event_id.clone(),
+                            event: if event.is_empty() {
+                                String::from("message")
+                            } else {
+                                event
+                            },
+                            data,
+                        }))
+                    }
+                    None => {
+                        event.clear();
+                        continue;
+                    }
+                }
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line.as_str(), ""),
+            };
+            match field {
+                "event" => event = value.to_string(),
+                "data" => match data.as_mut() {
+                    Some(data) => {
+                        data.push('\n');
+                        data.push_str(value);
+                    }
+                    None => data = Some(value.to_string()),
+                },
+                "id" if !value.contains('\0') => self.id_buffer = Some(value.to_string()),
+                "retry" => {
+                    if let Ok(millis) = value.parse() {
+                        self.retry = Some(Duration::from_millis(millis));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<R: Read> Iterator for EventReader<R> {
+    type Item = io::Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_event().transpose()
+    }
+}
+
+/// EventStream
+///
+/// An iterator over the server-sent events of a URL, which reconnects when the stream drops and resumes
+/// after the last event received, with the `Last-Event-Id` header.
+///
+/// Create one for any stream URL returned by the API with [`new`][new], or for an endpoint with
+/// [`HttpApiClient::request_events`][request_events].
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::framework::sse::EventStream;
+/// use heroku_rs::prelude::*;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let build = api_client.request(&BuildDetails::new("APP_ID", "BUILD_ID")).unwrap();
+///
+/// let events = EventStream::new(&api_client, &build.output_stream_url).until(|| {
+///     let build = api_client.request(&BuildDetails::new("APP_ID", "BUILD_ID"))?;
+///     Ok(build.status != "pending")
+/// });
+/// for event in events {
+///     match event {
+///         Ok(event) => println!("{}", event.data),
+///         Err(e) => println!("Error: {}", e),
+///     }
+/// }
+/// ```
+///
+/// [new]: #method.new
+/// [request_events]: ../struct.HttpApiClient.html#method.request_events
+pub struct EventStream<'a> {
+    client: &'a HttpApiClient,
+    url: String,
+    /// the request of an endpoint, sent instead of a plain GET of `url`
+    request: Option<HttpRequest>,
+    reader: Option<EventReader<Box<dyn Read + Send>>>,
+    last_event_id: Option<String>,
+    reconnect: Reconnect<'a>,
+}
+
+impl<'a> EventStream<'a> {
+    /// Follow the events of any URL. The stream ends as soon as the server closes it.
+    ///
+    /// No credentials are sent, stream URLs returned by the API don't need them.
+    /// Use [`until`][until] to keep following it until the work behind it is done.
+    ///
+    /// [until]: #method.until
+    pub fn new(client: &'a HttpApiClient, url: &str) -> EventStream<'a> {
+        EventStream {
+            client,
+            url: url.to_string(),
+            request: None,
+            reader: None,
+            last_event_id: None,
+            reconnect: Reconnect::new(Duration::from_secs(3)),
+        }
+    }
+
+    /// # until: tells whether the stream is complete
+    ///
+    /// Called every time the server closes the stream. While it returns `false`, the stream reconnects and resumes.
+    pub fn until<F>(mut self, finished: F) -> Self
+    where
+        F: FnMut() -> ApiResponse<bool> + 'a,
+    {
+        self.reconnect.until(finished);
+        self
+    }
+
+    /// # last_event_id: resume after this event, e.g. one received by an earlier stream
+    pub fn last_event_id(mut self, last_event_id: &str) -> Self {
+        self.last_event_id = Some(last_event_id.to_string());
+        self
+    }
+
+    /// # max_reconnects: how many times in a row to reconnect after a failure, 10 by default
+    pub fn max_reconnects(mut self, max_reconnects: u32) -> Self {
+        self.reconnect.max_reconnects = max_reconnects;
+        self
+    }
+
+    /// # retry_delay: how long to wait before reconnecting, 3 seconds by default
+    ///
+    /// The server can change it with the `retry` field of its events.
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.reconnect.retry_delay = retry_delay;
+        self
+    }
+
+    fn connect(&mut self) -> ApiResponse<()> {
+        let mut request = match self.request.as_ref() {
+            Some(request) => request.clone(),
+            None => reconnect::get(&self.url)?,
+        };
+        request.headers.insert(
+            http::header::ACCEPT,
+            http::HeaderValue::from_static("text/event-stream"),
+        );
+        if let Some(id) = self.last_event_id.as_deref() {
+            if let Ok(id) = http::HeaderValue::from_str(id) {
+                request.headers.insert(LAST_EVENT_ID, id);
+            }
+        }
+
+        let response = self.client.send(request)?;
+        // The server has nothing more to send.
+        if response.status == http::StatusCode::NO_CONTENT {
+            self.reconnect.finish();
+            return Ok(());
+        }
+        if !response.status.is_success() {
+            return Err(match_failure(response));
+        }
+        self.reader = Some(EventReader::new(response.body));
+        Ok(())
+    }
+
+    /// Keep what the server told about resuming the stream.
+    fn track(&mut self) {
+        if let Some(reader) = self.reader.as_ref() {
+            if let Some(id) = reader.last_event_id() {
+                self.last_event_id = Some(id.to_string());
+            }
+            if let Some(retry) = reader.retry() {
+                self.reconnect.retry_delay = retry;
+            }
+        }
+    }
+
+    /// Retry after a failure, resuming after the last event received.
+    fn retry(&mut self, error: HerokuApiFailure) -> Option<HerokuApiFailure> {
+        self.track();
+        self.reader = None;
+        self.reconnect.retry(error)
+    }
+}
+
+impl<'a> Iterator for EventStream<'a> {
+    type Item = ApiResponse<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.reconnect.is_done() {
+                return None;
+            }
+            if self.reader.is_none() {
+                if let Err(e) = self.connect() {
+                    match self.retry(e) {
+                        Some(e) => return Some(Err(e)),
+                        None => continue,
+                    }
+                }
+                if self.reconnect.is_done() {
+                    return None;
+                }
+            }
+
+            let reader = self.reader.as_mut().expect("connected above");
+            match reader.next_event() {
+                Ok(Some(event)) => {
+                    self.reconnect.received();
+                    self.track();
+                    return Some(Ok(event));
+                }
+                Ok(None) => {
+                    self.track();
+                    self.reader = None;
+                    match self.reconnect.closed() {
+                        Ok(true) => return None,
+                        // Still going, reconnect for more events.
+                        Ok(false) => {}
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                Err(e) => {
+                    if let Some(e) = self.retry(e.into()) {
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl HttpApiClient {
+    /// Stream the server-sent events of an endpoint, instead of deserializing its response as JSON.
+    ///
+    /// The request is sent with `Accept: text/event-stream`, and sent again with `Last-Event-Id`
+    /// if the stream drops. See [`EventStream`][stream].
+    ///
+    /// [stream]: sse/struct.EventStream.html
+    pub fn request_events<ResultType, QueryType, BodyType>(
+        &self,
+        endpoint: &dyn HerokuEndpoint<ResultType, QueryType, BodyType>,
+    ) -> ApiResponse<EventStream<'_>>
+    where
+        ResultType: ApiResult,
+        QueryType: Serialize,
+        BodyType: Serialize,
+    {
+        let request = self.prepare(endpoint)?;
+        Ok(EventStream {
+            url: request.url.to_string(),
+            request: Some(request),
+            ..EventStream::new(self, "")
+        })
+    }
+}
+
+#[test]
+fn decodes_fields() {
+    let body = "\u{FEFF}event: status\r\ndata\r\nid: 7\r\nretry: 250\r\n\r\ndata:no space\n\ndata: incomplete";
+    let mut reader = EventReader::new(body.as_bytes());
+
+    let event = reader.next().unwrap().unwrap();
+    assert_eq!(event.event, "status");
+    assert_eq!(event.data, "");
+    assert_eq!(event.id.as_deref(), Some("7"));
+    assert_eq!(reader.retry(), Some(Duration::from_millis(250)));
+
+    let event = reader.next().unwrap().unwrap();
+    assert_eq!(event.event, "message");
+    assert_eq!(event.data, "no space");
+    assert_eq!(event.id.as_deref(), Some("7"));
+
+    assert!(reader.next().is_none());
+}
@@ -0,0 +1,365 @@
+//! This module contains the streaming deserialization of list responses.
+//!
+//! List endpoints return a JSON array, which is normally buffered and parsed in one go.
+//! The types in this module parse the array element by element straight from the response body,
+//! and follow Heroku's `Range` pagination, so memory use stays flat no matter how long the list is.
+use crate::framework::{
+    endpoint::HerokuEndpoint,
+    response::{match_failure, ApiResponse, ApiResult, HerokuApiFailure},
+    HttpApiClient,
+};
+use http::header::{HeaderValue, RANGE};
+use serde::de::{DeserializeOwned, Error};
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Read};
+use std::marker::PhantomData;
+
+/// The header Heroku uses to point at the next page of a list.
+const NEXT_RANGE: &str = "Next-Range";
+
+enum ArrayState {
+    Start,
+    Elements { first: bool },
+    Done,
+}
+
+/// JsonArrayReader
+///
+/// An iterator which reads a JSON array from any reader and yields its elements one by one.
+///
+/// Only a single element is kept in memory at a time.
+///
+/// # Example:
+///
+/// ```rust
+/// use heroku_rs::framework::streaming::JsonArrayReader;
+///
+/// let body = r#"[{"id": "1"}, {"id": "2"}]"#.as_bytes();
+/// let ids: Vec<serde_json::Value> = JsonArrayReader::<_, serde_json::Value>::new(body)
+///     .map(|element| element.unwrap()["id"].clone())
+///     .collect();
+///
+/// assert_eq!(ids, vec!["1", "2"]);
+/// ```
+pub struct JsonArrayReader<R, T> {
+    reader: BufReader<R>,
+    state: ArrayState,
+    element: Vec<u8>,
+    marker: PhantomData<T>,
+}
+
+impl<R: Read, T: DeserializeOwned> JsonArrayReader<R, T> {
+    pub fn new(reader: R) -> JsonArrayReader<R, T> {
+        JsonArrayReader {
+            reader: BufReader::new(reader),
+            state: ArrayState::Start,
+            element: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+
+    fn peek(&mut self) -> std::io::Result<Option<u8>> {
+        Ok(self.reader.fill_buf()?.first().copied())
+    }
+
+    fn bump(&mut self) -> std::io::Result<Option<u8>> {
+        let byte = self.peek()?;
+        if byte.is_some() {
+            self.reader.consume(1);
+        }
+        Ok(byte)
+    }
+
+    fn skip_whitespace(&mut self) -> std::io::Result<Option<u8>> {
+        loop {
+            match self.peek()? {
+                Some(b' ') | Some(b'\n') | Some(b'\r') | Some(b'\t') => self.reader.consume(1),
+                byte => return Ok(byte),
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> ApiResponse<()> {
+        match self.bump()? {
+            Some(byte) if byte == expected => Ok(()),
+            Some(byte) => Err(unexpected(&format!(
+                "expected `{}`, found `{}`",
+                expected as char, byte as char
+            ))),
+            None => Err(unexpected(&format!(
+                "expected `{}`, found end of input",
+                expected as char
+            ))),
+        }
+    }
+
+    /// Copy the raw bytes of the next JSON value into `self.element`.
+    fn read_value(&mut self) -> ApiResponse<()> {
+        self.element.clear();
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+
+        loop {
+            let byte = match self.peek()? {
+                Some(byte) => byte,
+                None => return Err(unexpected("unexpected end of input inside array")),
+            };
+            if !in_string && depth == 0 && !self.element.is_empty() {
+                match byte {
+                    b',' | b']' | b' ' | b'\n' | b'\r' | b'\t' => return Ok(()),
+                    _ => {}
+                }
+            }
+            self.reader.consume(1);
+            self.element.push(byte);
+
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if byte == b'\\' {
+                    escaped = true;
+                } else if byte == b'"' {
+                    in_string = false;
+                }
+            } else {
+                match byte {
+                    b'"' => in_string = true,
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+
+            let closed = !in_string && depth == 0 && matches!(byte, b'}' | b']' | b'"');
+            if closed {
+                return Ok(());
+            }
+        }
+    }
+
+    fn next_element(&mut self) -> ApiResponse<Option<T>> {
+        if let ArrayState::Start = self.state {
+            self.skip_whitespace()?;
+            self.expect(b'[')?;
+            self.state = ArrayState::Elements { first: true };
+        }
+        let first = match self.state {
+            ArrayState::Elements { first } => first,
+            _ => return Ok(None),
+        };
+
+        if self.skip_whitespace()? == Some(b']') {
+            self.reader.consume(1);
+            self.state = ArrayState::Done;
+            return Ok(None);
+        }
+        if !first {
+            self.expect(b',')?;
+            self.skip_whitespace()?;
+        }
+        self.state = ArrayState::Elements { first: false };
+
+        self.read_value()?;
+        Ok(Some(serde_json::from_slice(&self.element)?))
+    }
+}
+
+impl<R: Read, T: DeserializeOwned> Iterator for JsonArrayReader<R, T> {
+    type Item = ApiResponse<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_element() {
+            Ok(Some(element)) => Some(Ok(element)),
+            Ok(None) => None,
+            Err(e) => {
+                self.state = ArrayState::Done;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn unexpected(message: &str) -> HerokuApiFailure {
+    HerokuApiFailure::Parse(serde_json::Error::custom(message))
+}
+
+/// ListStream
+///
+/// An iterator over every item of a list endpoint, across all of its pages.
+///
+/// Each page is deserialized element by element from the response body, and the next page is only requested
+/// once the current one has been consumed. Iteration stops after the first failure.
+///
+/// Create one with [`HttpApiClient::request_stream`][request_stream].
+///
+/// [request_stream]: ../struct.HttpApiClient.html#method.request_stream
+pub struct ListStream<'a, T, QueryType, BodyType> {
+    client: &'a HttpApiClient,
+    endpoint: &'a dyn HerokuEndpoint<Vec<T>, QueryType, BodyType>,
+    range: Option<String>,
+    page: Option<JsonArrayReader<Box<dyn Read + Send>, T>>,
+    started: bool,
+    done: bool,
+}
+
+impl<'a, T, QueryType, BodyType> ListStream<'a, T, QueryType, BodyType> {
+    /// # range: the `Range` header of the first page
+    ///
+    /// e.g. `id ..; max=1000` to request the largest pages Heroku allows.
+    pub fn range(mut self, range: &str) -> Self {
+        self.range = Some(range.to_string());
+        self
+    }
+}
+
+impl<'a, T, QueryType, BodyType> ListStream<'a, T, QueryType, BodyType>
+where
+    T: DeserializeOwned,
+    Vec<T>: ApiResult,
+    QueryType: Serialize,
+    BodyType: Serialize,
+{
+    fn next_page(&mut self) -> ApiResponse<()> {
+        let mut request = self.client.prepare(self.endpoint)?;
+        if let Some(range) = self.range.take() {
+            if let Ok(range) = HeaderValue::from_str(&range) {
+                request.headers.insert(RANGE, range);
+            }
+        }
+
+        let response = self.client.send(request)?;
+        if !response.status.is_success() {
+            return Err(match_failure(response));
+        }
+
+        self.range = response
+            .headers
+            .get(NEXT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+        self.page = Some(JsonArrayReader::new(response.body));
+        Ok(())
+    }
+}
+
+impl<'a, T, QueryType, BodyType> Iterator for ListStream<'a, T, QueryType, BodyType>
+where
+    T: DeserializeOwned,
+    Vec<T>: ApiResult,
+    QueryType: Serialize,
+    BodyType: Serialize,
+{
+    type Item = ApiResponse<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.done {
+                return None;
+            }
+            if let Some(page) = self.page.as_mut() {
+                match page.next() {
+                    Some(Ok(item)) => return Some(Ok(item)),
+                    Some(Err(e)) => {
+                        self.done = true;
+                        return Some(Err(e));
+                    }
+                    None => self.page = None,
+                }
+            }
+            if self.started && self.range.is_none() {
+                self.done = true;
+                return None;
+            }
+
+            self.started = true;
+            if let Err(e) = self.next_page() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+impl HttpApiClient {
+    /// Stream the items of a list endpoint, across all of its pages.
+    ///
+    /// Unlike `request`, the response bodies are never buffered as a whole, which keeps memory use flat for very large accounts.
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// use heroku_rs::prelude::*;
+    ///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+    ///
+    /// let app_list = AppList::new();
+    /// let apps = api_client.request_stream(&app_list).range("id ..; max=1000");
+    ///
+    /// for app in apps {
+    ///     match app {
+    ///         Ok(app) => println!("App: {}", app.name),
+    ///         Err(e) => println!("Error: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub fn request_stream<'a, T, QueryType, BodyType>(
+        &'a self,
+        endpoint: &'a dyn HerokuEndpoint<Vec<T>, QueryType, BodyType>,
+    ) -> ListStream<'a, T, QueryType, BodyType>
+    where
+        T: DeserializeOwned,
+        Vec<T>: ApiResult,
+        QueryType: Serialize,
+        BodyType: Serialize,
+    {
+        ListStream {
+            client: self,
+            endpoint,
+            range: None,
+            page: None,
+            started: false,
+            done: false,
+        }
+    }
+}
+
+#[test]
+fn streams_objects() {
+    let body = r#" [ {"name": "a]}\"", "tags": ["x", {"y": 1}]} ,{"name":"b"}] "#;
+    let names: Vec<String> = JsonArrayReader::<_, serde_json::Value>::new(body.as_bytes())
+        .map(|value| value.unwrap()["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names, vec!["a]}\"", "b"]);
+}
+
+#[test]
+fn streams_scalars() {
+    let body = "[1, true ,null,\"x\",-2.5e3]";
+    let values: Vec<serde_json::Value> = JsonArrayReader::new(body.as_bytes())
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(
+        values,
+        serde_json::json!([1, true, null, "x", -2.5e3])
+            .as_array()
+            .unwrap()
+            .clone()
+    );
+}
+
+#[test]
+fn streams_empty_array() {
+    let mut reader = JsonArrayReader::<_, serde_json::Value>::new("[ ]".as_bytes());
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn stops_on_invalid_input() {
+    let mut reader = JsonArrayReader::<_, serde_json::Value>::new("[{\"a\": 1} {".as_bytes());
+    assert!(reader.next().unwrap().is_ok());
+    assert!(matches!(
+        reader.next(),
+        Some(Err(HerokuApiFailure::Parse(_)))
+    ));
+    assert!(reader.next().is_none());
+}
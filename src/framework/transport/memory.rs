@@ -0,0 +1,64 @@
+use super::{HttpRequest, HttpResponse, Transport, TransportError};
+use std::sync::Mutex;
+
+/// MemoryTransport
+///
+/// A transport which never touches the network: every request is answered by a handler, and recorded.
+///
+/// Share it with an `Arc` to inspect the recorded requests after the client has sent them.
+///
+/// # Example:
+///
+/// ```rust
+/// use heroku_rs::framework::transport::{HttpResponse, MemoryTransport};
+/// use heroku_rs::prelude::*;
+/// use std::sync::Arc;
+///
+/// let transport = Arc::new(MemoryTransport::new(|_request| {
+///     HttpResponse::from_bytes(http::StatusCode::OK, "[]")
+/// }));
+/// let credentials = Credentials::UserAuthToken {
+///     token: String::from("API_KEY"),
+/// };
+/// let api_client = HttpApiClient::with_transport(credentials, ApiEnvironment::Production, transport.clone());
+///
+/// let apps = api_client.request(&AppList::new()).unwrap();
+///
+/// assert!(apps.is_empty());
+/// assert_eq!(transport.requests()[0].url.path(), "/apps");
+/// ```
+pub struct MemoryTransport {
+    handler: Box<dyn Fn(&HttpRequest) -> HttpResponse + Send + Sync>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl MemoryTransport {
+    pub fn new<F>(handler: F) -> MemoryTransport
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        MemoryTransport {
+            handler: Box::new(handler),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// A transport which answers every request with the same status and body.
+    pub fn responding(status: http::StatusCode, body: &str) -> MemoryTransport {
+        let body = body.to_string();
+        MemoryTransport::new(move |_| HttpResponse::from_bytes(status, body.clone()))
+    }
+
+    /// Every request sent so far, oldest first.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        let response = (self.handler)(&request);
+        self.requests.lock().unwrap().push(request);
+        Ok(response)
+    }
+}
@@ -0,0 +1,225 @@
+//! This module contains the HTTP transports the client sends its requests with.
+//!
+//! The client prepares a [`HttpRequest`][HttpRequest] for every endpoint, with its URL, headers and body,
+//! and hands it to a [`Transport`][Transport] which returns the status, headers and body of the response.
+//!
+//! Three transports ship with the crate:
+//!
+//! - [`ReqwestTransport`][ReqwestTransport], built on `reqwest`. Enabled by the `reqwest` feature, which is on by default.
+//! - [`UreqTransport`][UreqTransport], built on the lighter `ureq`. Enabled by the `ureq` feature.
+//! - [`MemoryTransport`][MemoryTransport], which answers requests in memory, e.g. in tests.
+//!
+//! [HttpRequest]: struct.HttpRequest.html
+//! [Transport]: trait.Transport.html
+//! [ReqwestTransport]: struct.ReqwestTransport.html
+//! [UreqTransport]: struct.UreqTransport.html
+//! [MemoryTransport]: struct.MemoryTransport.html
+use crate::framework::response::{ApiResponse, HerokuApiFailure};
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::fmt;
+use std::io::Read;
+use std::sync::Arc;
+use url::Url;
+
+mod memory;
+#[cfg(feature = "reqwest")]
+mod reqwest_transport;
+#[cfg(feature = "ureq")]
+mod ureq_transport;
+
+pub use memory::MemoryTransport;
+#[cfg(feature = "reqwest")]
+pub use reqwest_transport::ReqwestTransport;
+#[cfg(feature = "ureq")]
+pub use ureq_transport::UreqTransport;
+
+/// Sends prepared requests over HTTP.
+///
+/// Implement this trait to plug another HTTP client into [`HttpApiClient`][client].
+///
+/// A transport only fails when no response could be obtained at all.
+/// Responses with an error status are returned as responses, the client decides what to do with them.
+///
+/// [client]: ../struct.HttpApiClient.html
+pub trait Transport: Send + Sync {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError>;
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        (**self).send(request)
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        (**self).send(request)
+    }
+}
+
+/// A request ready to be sent, with the query, the Heroku headers and the credentials already applied.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: http::Method,
+    pub url: Url,
+    pub headers: http::HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    pub fn new(method: http::Method, url: Url) -> HttpRequest {
+        HttpRequest {
+            method,
+            url,
+            headers: http::HeaderMap::new(),
+            body: None,
+        }
+    }
+}
+
+/// The response of a transport. The body is read lazily, so it can be streamed.
+pub struct HttpResponse {
+    pub status: http::StatusCode,
+    pub headers: http::HeaderMap,
+    pub body: Box<dyn Read + Send>,
+}
+
+impl HttpResponse {
+    pub fn new<R: Read + Send + 'static>(
+        status: http::StatusCode,
+        headers: http::HeaderMap,
+        body: R,
+    ) -> HttpResponse {
+        HttpResponse {
+            status,
+            headers,
+            body: Box::new(body),
+        }
+    }
+
+    /// A response with an in-memory body and no headers.
+    pub fn from_bytes<B: Into<Vec<u8>>>(status: http::StatusCode, body: B) -> HttpResponse {
+        HttpResponse::new(
+            status,
+            http::HeaderMap::new(),
+            std::io::Cursor::new(body.into()),
+        )
+    }
+
+    /// Read the whole body.
+    pub fn bytes(mut self) -> std::io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.body.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Read the whole body as text.
+    pub fn text(self) -> ApiResponse<String> {
+        let bytes = self.bytes()?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Read the whole body and deserialize it from JSON.
+    pub fn json<T: DeserializeOwned>(self) -> ApiResponse<T> {
+        let bytes = self.bytes()?;
+        serde_json::from_slice(&bytes).map_err(HerokuApiFailure::Parse)
+    }
+}
+
+impl fmt::Debug for HttpResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish()
+    }
+}
+
+/// The error of a transport which could not obtain a response.
+#[derive(Debug)]
+pub struct TransportError {
+    inner: Box<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    url: Url,
+    message: String,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl TransportError {
+    pub fn new<E>(url: Url, error: E) -> TransportError
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let error = error.into();
+        TransportError::with_message(url, error.to_string(), Some(error))
+    }
+
+    fn with_message(
+        url: Url,
+        message: String,
+        source: Option<Box<dyn Error + Send + Sync>>,
+    ) -> TransportError {
+        TransportError {
+            inner: Box::new(Inner {
+                url,
+                message,
+                source,
+            }),
+        }
+    }
+
+    /// The URL of the request which failed.
+    pub fn url(&self) -> &Url {
+        &self.inner.url
+    }
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error sending request for url ({}): {}",
+            self.inner.url, self.inner.message
+        )
+    }
+}
+
+impl Error for TransportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.inner
+            .source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
+
+/// Add the default headers which the request doesn't set itself.
+#[cfg(any(feature = "ureq", test))]
+fn with_defaults(headers: &http::HeaderMap, defaults: &http::HeaderMap) -> http::HeaderMap {
+    let mut merged = headers.clone();
+    for name in defaults.keys() {
+        if !merged.contains_key(name) {
+            for value in defaults.get_all(name) {
+                merged.append(name.clone(), value.clone());
+            }
+        }
+    }
+    merged
+}
+
+#[test]
+fn defaults_do_not_override_request_headers() {
+    let mut headers = http::HeaderMap::new();
+    headers.insert(http::header::ACCEPT, "text/plain".parse().unwrap());
+    let mut defaults = http::HeaderMap::new();
+    defaults.insert(http::header::ACCEPT, "application/json".parse().unwrap());
+    defaults.insert(http::header::FROM, "me@example.com".parse().unwrap());
+
+    let merged = with_defaults(&headers, &defaults);
+    assert_eq!(merged[http::header::ACCEPT], "text/plain");
+    assert_eq!(merged[http::header::FROM], "me@example.com");
+}
@@ -0,0 +1,57 @@
+use super::{HttpRequest, HttpResponse, Transport, TransportError};
+use crate::framework::HttpApiClientConfig;
+use std::error::Error;
+
+/// ReqwestTransport
+///
+/// Sends requests with a blocking `reqwest` client. This is the transport used by default.
+pub struct ReqwestTransport {
+    client: reqwest::blocking::Client,
+}
+
+impl ReqwestTransport {
+    /// Create a transport with the timeout and default headers of the client configuration.
+    pub fn new(config: &HttpApiClientConfig) -> Result<ReqwestTransport, reqwest::Error> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(config.http_timeout)
+            .default_headers(config.default_headers.clone())
+            .build()?;
+        Ok(ReqwestTransport { client })
+    }
+
+    /// Create a transport from a `reqwest` client configured by hand, e.g. with a proxy.
+    pub fn from_client(client: reqwest::blocking::Client) -> ReqwestTransport {
+        ReqwestTransport { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        let HttpRequest {
+            method,
+            url,
+            headers,
+            body,
+        } = request;
+
+        let mut builder = self.client.request(method, url.clone()).headers(headers);
+        if let Some(body) = body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send().map_err(|error| {
+            // reqwest already names the url in its message, keep the underlying cause only.
+            let message = error
+                .source()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| error.to_string());
+            TransportError::with_message(url, message, Some(Box::new(error)))
+        })?;
+
+        Ok(HttpResponse::new(
+            response.status(),
+            response.headers().clone(),
+            response,
+        ))
+    }
+}
@@ -0,0 +1,74 @@
+use super::{with_defaults, HttpRequest, HttpResponse, Transport, TransportError};
+use crate::framework::HttpApiClientConfig;
+
+/// UreqTransport
+///
+/// Sends requests with `ureq`, which has far fewer dependencies than `reqwest`.
+pub struct UreqTransport {
+    agent: ureq::Agent,
+    default_headers: http::HeaderMap,
+}
+
+impl UreqTransport {
+    /// Create a transport with the timeout and default headers of the client configuration.
+    pub fn new(config: &HttpApiClientConfig) -> UreqTransport {
+        UreqTransport {
+            agent: ureq::AgentBuilder::new()
+                .timeout(config.http_timeout)
+                .build(),
+            default_headers: config.default_headers.clone(),
+        }
+    }
+
+    /// Create a transport from a `ureq` agent configured by hand, e.g. with a proxy.
+    pub fn from_agent(agent: ureq::Agent) -> UreqTransport {
+        UreqTransport {
+            agent,
+            default_headers: http::HeaderMap::new(),
+        }
+    }
+}
+
+impl Transport for UreqTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        let mut call = self
+            .agent
+            .request_url(request.method.as_str(), &request.url);
+        for (name, value) in &with_defaults(&request.headers, &self.default_headers) {
+            let value = value
+                .to_str()
+                .map_err(|e| TransportError::new(request.url.clone(), e))?;
+            call = call.set(name.as_str(), value);
+        }
+
+        let result = match &request.body {
+            Some(body) => call.send_bytes(body),
+            None => call.call(),
+        };
+        let response = match result {
+            Ok(response) => response,
+            // Error statuses are still responses, the client parses their body.
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(error)) => {
+                return Err(TransportError::new(request.url, error))
+            }
+        };
+
+        let status = http::StatusCode::from_u16(response.status())
+            .map_err(|e| TransportError::new(request.url.clone(), e))?;
+        let mut headers = http::HeaderMap::new();
+        for name in response.headers_names() {
+            let header = match http::header::HeaderName::from_bytes(name.as_bytes()) {
+                Ok(header) => header,
+                Err(_) => continue,
+            };
+            for value in response.all(&name) {
+                if let Ok(value) = http::HeaderValue::from_str(value) {
+                    headers.append(header.clone(), value);
+                }
+            }
+        }
+
+        Ok(HttpResponse::new(status, headers, response.into_reader()))
+    }
+}
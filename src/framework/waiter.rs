@@ -0,0 +1,292 @@
+//! This module contains the polling of long-running resources until they settle.
+use crate::framework::{
+    apiclient::HerokuApiClient,
+    endpoint::HerokuEndpoint,
+    response::{ApiResult, HerokuApiFailure},
+    HttpApiClient,
+};
+use serde::Serialize;
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The longest uninterrupted sleep, so cancellation is noticed quickly.
+const CANCEL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+type Progress<'a, T> = Box<dyn FnMut(&T) + 'a>;
+
+/// Requests a details endpoint until the resource it describes reaches a terminal state.
+///
+/// Ready-made waiters exist for the resources Heroku processes asynchronously, e.g. [`Waiter::for_build`][for_build].
+/// Any other endpoint can be waited on with [`Waiter::new`][new] and a predicate telling whether it is done.
+///
+/// By default, the endpoint is requested every 2 seconds, forever.
+///
+/// # Example:
+///
+/// ```rust,no_run
+/// use heroku_rs::prelude::*;
+/// use std::time::Duration;
+///#    let api_client = HttpApiClient::create("API_KEY").unwrap();
+///
+/// let details = BuildDetails::new("APP_ID", "BUILD_ID");
+/// let build = Waiter::for_build(&api_client, &details)
+///     .timeout(Duration::from_secs(15 * 60))
+///     .on_progress(|build| println!("Build is {}", build.status))
+///     .wait();
+///
+/// match build {
+///     Ok(build) => println!("Build {}", build.status),
+///     Err(e) => println!("Error: {}", e),
+/// }
+/// ```
+///
+/// [for_build]: struct.Waiter.html#method.for_build
+/// [new]: struct.Waiter.html#method.new
+pub struct Waiter<'a, ResultType, QueryType = (), BodyType = ()> {
+    client: &'a HttpApiClient,
+    endpoint: &'a dyn HerokuEndpoint<ResultType, QueryType, BodyType>,
+    done: Box<dyn Fn(&ResultType) -> bool + 'a>,
+    on_progress: Option<Progress<'a, ResultType>>,
+    timeout: Option<Duration>,
+    poll_interval: Duration,
+    backoff: f64,
+    max_poll_interval: Duration,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl<'a, ResultType, QueryType, BodyType> Waiter<'a, ResultType, QueryType, BodyType>
+where
+    ResultType: ApiResult,
+    QueryType: Serialize,
+    BodyType: Serialize,
+{
+    /// Wait on `endpoint` until `done` returns `true` for its response.
+    pub fn new<F>(
+        client: &'a HttpApiClient,
+        endpoint: &'a dyn HerokuEndpoint<ResultType, QueryType, BodyType>,
+        done: F,
+    ) -> Self
+    where
+        F: Fn(&ResultType) -> bool + 'a,
+    {
+        Waiter {
+            client,
+            endpoint,
+            done: Box::new(done),
+            on_progress: None,
+            timeout: None,
+            poll_interval: Duration::from_secs(2),
+            backoff: 1.0,
+            max_poll_interval: Duration::from_secs(2),
+            cancel: None,
+        }
+    }
+
+    /// # timeout: how long to wait at most, forever by default
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// # poll_interval: how long to wait between two requests, 2 seconds by default
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self.max_poll_interval = self.max_poll_interval.max(poll_interval);
+        self
+    }
+
+    /// # backoff: multiply the poll interval by `factor` after every request, up to `max_poll_interval`
+    pub fn backoff(mut self, factor: f64, max_poll_interval: Duration) -> Self {
+        self.backoff = factor.max(1.0);
+        self.max_poll_interval = max_poll_interval.max(self.poll_interval);
+        self
+    }
+
+    /// # cancel: stop waiting once `cancel` is set, e.g. from another thread or a signal handler
+    pub fn cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// # on_progress: called with every response, including the terminal one
+    pub fn on_progress<F: FnMut(&ResultType) + 'a>(mut self, on_progress: F) -> Self {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    /// Request the endpoint until the resource is done, and return its terminal state.
+    ///
+    /// A failed request ends the wait, so does the timeout or a cancellation.
+    pub fn wait(mut self) -> Result<ResultType, WaitError<ResultType>> {
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let mut poll_interval = self.poll_interval;
+        let mut last = None;
+        loop {
+            if self.cancelled() {
+                return Err(WaitError::Cancelled(last));
+            }
+            let value = self.client.request(self.endpoint)?;
+            if let Some(on_progress) = self.on_progress.as_mut() {
+                on_progress(&value);
+            }
+            if (self.done)(&value) {
+                return Ok(value);
+            }
+
+            let mut sleep = poll_interval;
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err(WaitError::TimedOut(value));
+                }
+                sleep = sleep.min(remaining);
+            }
+            last = Some(value);
+            self.sleep(sleep);
+            poll_interval = Duration::from_secs_f64(
+                (poll_interval.as_secs_f64() * self.backoff)
+                    .min(self.max_poll_interval.as_secs_f64()),
+            );
+        }
+    }
+
+    fn cancelled(&self) -> bool {
+        match &self.cancel {
+            Some(cancel) => cancel.load(Ordering::SeqCst),
+            None => false,
+        }
+    }
+
+    fn sleep(&self, duration: Duration) {
+        if self.cancel.is_none() {
+            return thread::sleep(duration);
+        }
+        let until = Instant::now() + duration;
+        while !self.cancelled() {
+            let remaining = until.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            thread::sleep(remaining.min(CANCEL_CHECK_INTERVAL));
+        }
+    }
+}
+
+#[cfg(feature = "builds")]
+impl<'a> Waiter<'a, crate::endpoints::builds::Build> {
+    /// Wait until a build is no longer `pending`.
+    pub fn for_build(
+        client: &'a HttpApiClient,
+        endpoint: &'a crate::endpoints::builds::BuildDetails<'a>,
+    ) -> Self {
+        Waiter::new(client, endpoint, |build| build.status != "pending")
+    }
+}
+
+#[cfg(feature = "releases")]
+impl<'a> Waiter<'a, crate::endpoints::releases::Release> {
+    /// Wait until a release, and its release phase, is no longer `pending`.
+    pub fn for_release(
+        client: &'a HttpApiClient,
+        endpoint: &'a crate::endpoints::releases::ReleaseInfo<'a>,
+    ) -> Self {
+        Waiter::new(client, endpoint, |release| release.status != "pending")
+    }
+}
+
+#[cfg(feature = "apps")]
+impl<'a> Waiter<'a, crate::endpoints::apps::AppSetup> {
+    /// Wait until an app setup is no longer `pending`, i.e. it `succeeded` or `failed`.
+    pub fn for_app_setup(
+        client: &'a HttpApiClient,
+        endpoint: &'a crate::endpoints::apps::AppSetupDetails<'a>,
+    ) -> Self {
+        Waiter::new(client, endpoint, |setup| setup.status != "pending")
+    }
+}
+
+#[cfg(feature = "addons")]
+impl<'a> Waiter<'a, crate::endpoints::addons::Addon> {
+    /// Wait until an add-on is done `provisioning` or `deprovisioning`.
+    pub fn for_addon(
+        client: &'a HttpApiClient,
+        endpoint: &'a crate::endpoints::addons::AddonDetails<'a>,
+    ) -> Self {
+        Waiter::new(client, endpoint, |addon| {
+            addon.state != "provisioning" && addon.state != "deprovisioning"
+        })
+    }
+}
+
+#[cfg(feature = "testing")]
+impl<'a> Waiter<'a, crate::endpoints::testing::TestRun> {
+    /// Wait until a test run `succeeded`, `failed`, `errored` or was `cancelled`.
+    pub fn for_test_run(
+        client: &'a HttpApiClient,
+        endpoint: &'a crate::endpoints::testing::TestRunDetails<'a>,
+    ) -> Self {
+        Waiter::new(client, endpoint, |run| {
+            matches!(
+                run.status.as_str(),
+                "succeeded" | "failed" | "errored" | "cancelled"
+            )
+        })
+    }
+}
+
+#[cfg(feature = "pipelines")]
+impl<'a> Waiter<'a, crate::endpoints::pipelines::PipelinePromotion> {
+    /// Wait until a pipeline promotion is no longer `pending`.
+    pub fn for_promotion(
+        client: &'a HttpApiClient,
+        endpoint: &'a crate::endpoints::pipelines::PipelinePromotionDetails<'a>,
+    ) -> Self {
+        Waiter::new(client, endpoint, |promotion| promotion.status != "pending")
+    }
+}
+
+#[cfg(feature = "space")]
+impl<'a> Waiter<'a, crate::endpoints::space::Space> {
+    /// Wait until a space is done `allocating`.
+    pub fn for_space(
+        client: &'a HttpApiClient,
+        endpoint: &'a crate::endpoints::space::SpaceDetails<'a>,
+    ) -> Self {
+        Waiter::new(client, endpoint, |space| space.state != "allocating")
+    }
+}
+
+/// Why a [`Waiter`][waiter] stopped before the resource was done.
+///
+/// [waiter]: struct.Waiter.html
+#[derive(Debug)]
+pub enum WaitError<T> {
+    /// A request failed
+    Api(HerokuApiFailure),
+    /// The timeout elapsed, with the last state of the resource
+    TimedOut(T),
+    /// The wait was cancelled, with the last state of the resource if it was requested already
+    Cancelled(Option<T>),
+}
+
+impl<T> From<HerokuApiFailure> for WaitError<T> {
+    fn from(error: HerokuApiFailure) -> Self {
+        WaitError::Api(error)
+    }
+}
+
+impl<T> fmt::Display for WaitError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WaitError::Api(err) => write!(f, "{}", err),
+            WaitError::TimedOut(_) => write!(f, "Timed out waiting for the resource"),
+            WaitError::Cancelled(_) => write!(f, "Cancelled waiting for the resource"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> Error for WaitError<T> {}
@@ -2,7 +2,9 @@
 
 //! # heroku_rs
 //!
-//! The `heroku_rs` crate provides convenient Rust bindings for the [Heroku V3 API][v3api], with a [`client`][client] built on top of [`Reqwest`][reqwest].
+//! The `heroku_rs` crate provides convenient Rust bindings for the [Heroku V3 API][v3api], with a [`client`][client] built on top of [`Reqwest`][reqwest] by default.
+//!
+//! The HTTP backend is pluggable: enable the `ureq` feature for a lighter one, or bring your own [`Transport`][transport].
 //!
 //! ## Heroku Client
 //!
@@ -185,6 +187,7 @@
 //!
 //! [reqwest]: https://docs.rs/reqwest/0.10.4/reqwest/
 //! [client]: framework/struct.HttpApiClient.html
+//! [transport]: framework/transport/trait.Transport.html
 //! [v3api]: https://devcenter.heroku.com/articles/platform-api-reference#
 //! [app]: endpoints/apps/struct.App.html
 //! [appcreate]: endpoints/apps/post/struct.AppCreate.html
@@ -194,6 +197,7 @@
 //! [tomlfeature]: https://github.com/bensadiku/heroku_rs/blob/master/docs/FEATURES.md
 
 extern crate chrono;
+#[cfg(feature = "reqwest")]
 extern crate reqwest;
 #[macro_use]
 extern crate serde;
@@ -201,6 +205,9 @@ extern crate serde_json;
 #[macro_use]
 pub mod macros;
 
+#[cfg(all(feature = "extra_fields", feature = "strict"))]
+compile_error!("features `extra_fields` and `strict` are mutually exclusive");
+
 pub mod endpoints;
 pub mod framework;
 
@@ -261,7 +268,7 @@ pub mod prelude {
     pub use crate::framework::endpoint::Method;
     #[doc(no_inline)]
     pub use crate::framework::{
-        apiclient::HerokuApiClient, auth::Credentials, ApiEnvironment, HttpApiClient,
-        HttpApiClientConfig,
+        apiclient::HerokuApiClient, auth::Credentials, ApiEnvironment, BatchConfig,
+        HttpApiClient, HttpApiClientConfig, Waiter,
     };
 }
@@ -21,4 +21,13 @@ mod tests {
         let endpoint = format!("{}{}", "apps/", app_id);
         assert_valid_url(response, endpoint)
     }
+
+    #[test]
+    fn assert_valid_url_stream_app_list() {
+        let client = util::get_client();
+        let mut stream = client.request_stream(&apps::AppList {});
+        let endpoint = String::from("apps");
+        assert_valid_url(stream.next().unwrap(), endpoint);
+        assert!(stream.next().is_none());
+    }
 }
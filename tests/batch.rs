@@ -0,0 +1,38 @@
+use heroku_rs::endpoints::apps;
+use heroku_rs::framework::BatchConfig;
+use util::assert_valid_url;
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    // run with `cargo test -- --nocapture` for  the logs
+
+    #[test]
+    fn assert_valid_url_batch_in_order() {
+        let app_ids = ["123xyz", "456xyz", "789xyz", "abcxyz", "defxyz"];
+        let endpoints: Vec<apps::AppDetails> = app_ids
+            .iter()
+            .map(|app_id| apps::AppDetails { app_id })
+            .collect();
+        let config = BatchConfig {
+            concurrency: 2,
+            ..BatchConfig::default()
+        };
+
+        let responses = util::get_client().request_batch(&endpoints, &config);
+
+        assert_eq!(responses.len(), app_ids.len());
+        for (app_id, response) in app_ids.iter().zip(responses) {
+            let endpoint = format!("{}{}", "apps/", app_id);
+            assert_valid_url(response, endpoint)
+        }
+    }
+
+    #[test]
+    fn batch_without_endpoints() {
+        let endpoints: Vec<apps::AppDetails> = vec![];
+        let responses = util::get_client().request_batch(&endpoints, &BatchConfig::default());
+        assert!(responses.is_empty());
+    }
+}
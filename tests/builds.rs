@@ -0,0 +1,297 @@
+use heroku_rs::endpoints::builds::BuildOutputStream;
+use heroku_rs::framework::{
+    response::HerokuApiFailure,
+    transport::{HttpRequest, HttpResponse, MemoryTransport},
+};
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const OUTPUT_STREAM_URL: &str = "https://build-output.heroku.com/streams/01234567";
+
+mod util;
+#[cfg(feature = "deploy")]
+use util::{ok, text, Routes};
+
+/// A body which drops the connection once its data has been read.
+struct Disconnecting(io::Cursor<Vec<u8>>);
+
+impl Read for Disconnecting {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.0.read(buf)? {
+            0 => Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset")),
+            read => Ok(read),
+        }
+    }
+}
+
+fn range(request: &HttpRequest) -> Option<String> {
+    request
+        .headers
+        .get(http::header::RANGE)
+        .map(|range| range.to_str().unwrap().to_string())
+}
+
+/// The Platform API and a blob store, for an app whose build is pending during the first status check
+/// and succeeded afterwards. Uploaded sources are kept in `store`.
+#[cfg(feature = "deploy")]
+fn platform(store: Arc<std::sync::Mutex<Vec<u8>>>) -> Arc<MemoryTransport> {
+    use serde_json::json;
+    fn build(status: &str) -> serde_json::Value {
+        json!({
+            "app": {"id": "app-id"},
+            "buildpacks": null,
+            "created_at": "2020-01-01T12:00:00Z",
+            "id": "build-id",
+            "output_stream_url": "https://build-output.heroku.com/streams/build-id",
+            "source_blob": {"checksum": null, "url": "https://blobs.example.com/get", "version": "v1.4.2"},
+            "release": if status == "pending" { json!(null) } else { json!({"id": "release-id"}) },
+            "slug": null,
+            "stack": "heroku-20",
+            "status": status,
+            "updated_at": "2020-01-01T12:00:00Z",
+            "user": {"id": "user-id", "email": "user@example.com"},
+        })
+    }
+    let checks = AtomicUsize::new(0);
+    Routes::new()
+        .post("/sources", |_| {
+            ok(json!({"source_blob": {
+                "get_url": "https://blobs.example.com/get",
+                "put_url": "https://blobs.example.com/put",
+            }}))
+        })
+        .put("/put", move |request| {
+            *store.lock().unwrap() = request.body.clone().unwrap();
+            text("")
+        })
+        .post("/apps/app-id/builds", |request| {
+            let params: serde_json::Value =
+                serde_json::from_slice(request.body.as_ref().unwrap()).unwrap();
+            assert_eq!(
+                params["source_blob"]["url"],
+                "https://blobs.example.com/get"
+            );
+            assert_eq!(params["source_blob"]["version"], "v1.4.2");
+            ok(build("pending"))
+        })
+        .get("/apps/app-id/builds/build-id", move |_| {
+            match checks.fetch_add(1, Ordering::SeqCst) {
+                0 => ok(build("pending")),
+                _ => ok(build("succeeded")),
+            }
+        })
+        .get("/apps/app-id/releases/release-id", |_| {
+            ok(json!({
+                "addon_plan_names": [],
+                "app": {"id": "app-id", "name": "example"},
+                "created_at": "2020-01-01T12:00:00Z",
+                "current": true,
+                "description": "Deploy v1.4.2",
+                "id": "release-id",
+                "output_stream_url": "https://build-output.heroku.com/streams/release-id",
+                "slug": null,
+                "status": "succeeded",
+                "updated_at": "2020-01-01T12:00:00Z",
+                "user": {"id": "user-id", "email": "user@example.com"},
+                "version": 7,
+            }))
+        })
+        .get("/streams/build-id", |_| text("-----> Building\n"))
+        .get("/streams/release-id", |_| text("Running migrations\n"))
+        .transport()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resumes_after_disconnect() {
+        let calls = AtomicUsize::new(0);
+        let transport = Arc::new(MemoryTransport::new(move |_| {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => HttpResponse::new(
+                    http::StatusCode::OK,
+                    http::HeaderMap::new(),
+                    Disconnecting(io::Cursor::new(b"-----> Building\nli".to_vec())),
+                ),
+                _ => {
+                    HttpResponse::from_bytes(http::StatusCode::PARTIAL_CONTENT, "ne 2\r\nline 3\n")
+                }
+            }
+        }));
+        let client = util::memory_client(transport.clone());
+
+        let lines: Vec<String> = BuildOutputStream::new(&client, OUTPUT_STREAM_URL)
+            .retry_delay(Duration::from_millis(0))
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(lines, vec!["-----> Building", "line 2", "line 3"]);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].url.as_str(), OUTPUT_STREAM_URL);
+        assert_eq!(range(&requests[0]), None);
+        assert_eq!(range(&requests[1]), Some(String::from("bytes=18-")));
+    }
+
+    #[test]
+    fn skips_output_when_range_is_ignored() {
+        let calls = AtomicUsize::new(0);
+        let transport = Arc::new(MemoryTransport::new(move |_| {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => HttpResponse::new(
+                    http::StatusCode::OK,
+                    http::HeaderMap::new(),
+                    Disconnecting(io::Cursor::new(b"one\n".to_vec())),
+                ),
+                _ => HttpResponse::from_bytes(http::StatusCode::OK, "one\ntwo\n"),
+            }
+        }));
+        let client = util::memory_client(transport);
+
+        let lines: Vec<String> = BuildOutputStream::new(&client, OUTPUT_STREAM_URL)
+            .retry_delay(Duration::from_millis(0))
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(lines, vec!["one", "two"]);
+    }
+
+    #[test]
+    fn follows_until_finished() {
+        let calls = AtomicUsize::new(0);
+        let transport = Arc::new(MemoryTransport::new(move |_| {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => HttpResponse::from_bytes(http::StatusCode::OK, "compiling\n"),
+                1 => HttpResponse::from_bytes(http::StatusCode::RANGE_NOT_SATISFIABLE, ""),
+                _ => HttpResponse::from_bytes(http::StatusCode::PARTIAL_CONTENT, "done"),
+            }
+        }));
+        let client = util::memory_client(transport.clone());
+        let checks = AtomicUsize::new(0);
+
+        let mut lines = Vec::new();
+        BuildOutputStream::new(&client, OUTPUT_STREAM_URL)
+            .retry_delay(Duration::from_millis(0))
+            .until(|| Ok(checks.fetch_add(1, Ordering::SeqCst) == 2))
+            .for_each_line(|line| lines.push(line.to_string()))
+            .unwrap();
+
+        assert_eq!(lines, vec!["compiling", "done"]);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(range(&requests[2]), Some(String::from("bytes=10-")));
+    }
+
+    #[test]
+    fn gives_up_on_client_errors() {
+        let transport = Arc::new(MemoryTransport::responding(
+            http::StatusCode::NOT_FOUND,
+            r#"{"id": "not_found", "message": "Stream not found."}"#,
+        ));
+        let client = util::memory_client(transport.clone());
+
+        let mut stream = BuildOutputStream::new(&client, OUTPUT_STREAM_URL);
+
+        match stream.next() {
+            Some(Err(HerokuApiFailure::Error(status, _))) => {
+                assert_eq!(status, http::StatusCode::NOT_FOUND)
+            }
+            other => panic!("Expected a not found error, got {:?}", other),
+        }
+        assert!(stream.next().is_none());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn gives_up_on_invalid_urls() {
+        let transport = Arc::new(MemoryTransport::responding(http::StatusCode::OK, ""));
+        let client = util::memory_client(transport.clone());
+
+        let mut stream =
+            BuildOutputStream::new(&client, "not a url").retry_delay(Duration::from_millis(0));
+
+        match stream.next() {
+            Some(Err(HerokuApiFailure::Parse(e))) => {
+                assert!(e
+                    .to_string()
+                    .starts_with("invalid stream URL \"not a url\""))
+            }
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+        assert!(stream.next().is_none());
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "deploy")]
+    fn deploys_directory() {
+        use heroku_rs::endpoints::builds::{deploy_directory, DeployOptions};
+        use std::fs;
+        use std::sync::Mutex;
+
+        let dir = std::env::temp_dir().join(format!("heroku-rs-deploy-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(dir.join("Procfile"), "web: ./server\n").unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.join("target/server"), "binary").unwrap();
+        fs::write(dir.join("notes.md"), "todo").unwrap();
+        fs::write(dir.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(dir.join(".gitignore"), "target/\n").unwrap();
+        fs::write(dir.join(".slugignore"), "*.md\n").unwrap();
+        let store = Arc::new(Mutex::new(Vec::new()));
+        let transport = platform(store.clone());
+        let client = util::memory_client(transport.clone());
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let output = lines.clone();
+
+        let options = DeployOptions::new()
+            .version("v1.4.2")
+            .buildpack("heroku/ruby")
+            .poll_interval(Duration::from_millis(0))
+            .on_output(move |line| output.lock().unwrap().push(line.to_string()));
+        let deploy = deploy_directory(&client, "app-id", &dir, options).unwrap();
+
+        assert_eq!(deploy.build.status, "succeeded");
+        assert_eq!(deploy.release.unwrap().version, 7);
+        let created = transport
+            .requests()
+            .into_iter()
+            .find(|request| {
+                request.method == http::Method::POST && request.url.path() == "/apps/app-id/builds"
+            })
+            .unwrap();
+        let created: serde_json::Value = serde_json::from_slice(&created.body.unwrap()).unwrap();
+        assert_eq!(
+            created["buildpacks"],
+            serde_json::json!([{"url": "heroku/ruby"}])
+        );
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec!["-----> Building", "Running migrations"]
+        );
+        let tarball = store.lock().unwrap().clone();
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(&tarball[..]));
+        let paths: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ".gitignore",
+                ".slugignore",
+                "Procfile",
+                "src",
+                "src/main.rs"
+            ]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,283 @@
+use heroku_rs::endpoints::apps::{AppCloner, CloneAction, CloneItem};
+use heroku_rs::framework::transport::{HttpRequest, HttpResponse, MemoryTransport};
+use serde_json::json;
+use std::sync::Arc;
+
+mod util;
+use util::{ok, Routes};
+
+fn addon(name: &str, owner: &str, plan: &str, config_vars: &[&str]) -> serde_json::Value {
+    json!({
+        "actions": {"id": "action-id", "label": "Open", "action": "open", "url": "https://example.com", "requires_owner": false},
+        "addon_service": {"id": "service-id", "name": plan.split(':').next().unwrap()},
+        "billing_entity": {"id": "app-id", "name": owner, "type": "app"},
+        "app": {"id": format!("{}-id", owner), "name": owner},
+        "billed_price": null,
+        "config_vars": config_vars,
+        "created_at": "2020-01-01T12:00:00Z",
+        "id": format!("{}-id", name),
+        "name": name,
+        "plan": {"id": "plan-id", "name": plan},
+        "provider_id": "provider-id",
+        "state": "provisioned",
+        "updated_at": "2020-01-01T12:00:00Z",
+        "web_url": null,
+    })
+}
+
+fn attachment(name: &str, addon: &str, owner: &str) -> serde_json::Value {
+    json!({
+        "addon": {"id": format!("{}-id", addon), "name": addon, "app": {"id": format!("{}-id", owner), "name": owner}},
+        "app": {"id": "staging-id", "name": "staging"},
+        "created_at": "2020-01-01T12:00:00Z",
+        "id": format!("attachment-{}", name),
+        "name": name,
+        "namespace": null,
+        "updated_at": "2020-01-01T12:00:00Z",
+        "web_url": null,
+        "log_input_url": null,
+    })
+}
+
+/// A staging app with a bit of everything, and an empty review app which accepts new add-ons, but no
+/// formation updates since it has not been deployed yet.
+fn heroku() -> Arc<MemoryTransport> {
+    Routes::new()
+        .get("/apps/staging/addons", |_| {
+            ok(json!([
+                addon(
+                    "postgresql-round-12345",
+                    "staging",
+                    "heroku-postgresql:hobby-dev",
+                    &["DATABASE_URL"]
+                ),
+                addon(
+                    "papertrail-shared-1",
+                    "shared",
+                    "papertrail:choklad",
+                    &["PAPERTRAIL_API_TOKEN"]
+                ),
+            ]))
+        })
+        .get("/apps/staging/addon-attachments", |_| {
+            ok(json!([
+                attachment("DATABASE", "postgresql-round-12345", "staging"),
+                attachment(
+                    "HEROKU_POSTGRESQL_PINK",
+                    "postgresql-round-12345",
+                    "staging"
+                ),
+                attachment("PAPERTRAIL", "papertrail-shared-1", "shared"),
+            ]))
+        })
+        .get("/apps/staging/config-vars", |_| {
+            ok(json!({
+                "DATABASE_URL": "postgres://db",
+                "HEROKU_POSTGRESQL_PINK_URL": "postgres://db",
+                "LEGACY_FLAG": null,
+                "PAPERTRAIL_API_TOKEN": "token",
+                "RAILS_ENV": "staging",
+                "SECRET_KEY_BASE": "secret",
+            }))
+        })
+        .get("/apps/staging/buildpack-installations", |_| {
+            ok(json!([
+                {"ordinal": 1, "buildpack": {"url": "heroku/ruby", "name": "heroku/ruby"}},
+                {"ordinal": 0, "buildpack": {"url": "heroku/nodejs", "name": "heroku/nodejs"}},
+            ]))
+        })
+        .get("/apps/staging/features", |_| {
+            ok(json!([
+                {
+                    "created_at": "2020-01-01T12:00:00Z", "description": "Runtime metrics", "doc_url": "https://devcenter.heroku.com",
+                    "enabled": true, "id": "feature-1", "name": "runtime-heroku-metrics", "state": "general",
+                    "updated_at": "2020-01-01T12:00:00Z", "display_name": null, "feedback_email": null,
+                },
+                {
+                    "created_at": "2020-01-01T12:00:00Z", "description": "Preboot", "doc_url": "https://devcenter.heroku.com",
+                    "enabled": false, "id": "feature-2", "name": "preboot", "state": "general",
+                    "updated_at": "2020-01-01T12:00:00Z", "display_name": null, "feedback_email": null,
+                },
+            ]))
+        })
+        .get("/apps/staging/formation", |_| {
+            ok(json!([{
+                "app": {"id": "staging-id", "name": "staging"}, "command": "bin/web", "created_at": "2020-01-01T12:00:00Z",
+                "id": "formation-id", "quantity": 2, "size": "standard-1X", "type": "web", "updated_at": "2020-01-01T12:00:00Z",
+            }]))
+        })
+        .get("/apps/staging/domains", |_| {
+            ok(json!([
+                {
+                    "acm_status": null, "acm_status_reason": null, "app": {"id": "staging-id", "name": "staging"}, "cname": null,
+                    "created_at": "2020-01-01T12:00:00Z", "hostname": "staging.herokuapp.com", "id": "domain-1",
+                    "kind": "heroku", "updated_at": "2020-01-01T12:00:00Z", "status": "succeeded",
+                },
+                {
+                    "acm_status": null, "acm_status_reason": null, "app": {"id": "staging-id", "name": "staging"},
+                    "cname": "staging.example.com.herokudns.com", "created_at": "2020-01-01T12:00:00Z",
+                    "hostname": "staging.example.com", "id": "domain-2", "kind": "custom",
+                    "updated_at": "2020-01-01T12:00:00Z", "status": "succeeded",
+                },
+            ]))
+        })
+        .get("/apps/staging/log-drains", |_| {
+            ok(json!([
+                {
+                    "addon": {"id": "papertrail-shared-1-id", "name": "papertrail-shared-1"}, "created_at": "2020-01-01T12:00:00Z",
+                    "id": "drain-1", "token": "d.1", "updated_at": "2020-01-01T12:00:00Z", "url": "syslog+tls://logs.papertrailapp.com:1234",
+                },
+                {
+                    "addon": null, "created_at": "2020-01-01T12:00:00Z", "id": "drain-2", "token": "d.2",
+                    "updated_at": "2020-01-01T12:00:00Z", "url": "https://logs.example.com/drain",
+                },
+            ]))
+        })
+        .get("/apps/staging/webhooks", |_| {
+            ok(json!([{
+                "app": {"id": "staging-id", "name": "staging"}, "created_at": "2020-01-01T12:00:00Z", "id": "webhook-id",
+                "include": ["api:release"], "level": "notify", "updated_at": "2020-01-01T12:00:00Z",
+                "url": "https://hooks.example.com/heroku",
+            }]))
+        })
+        .patch("/apps/review/formation/web", |_| {
+            HttpResponse::from_bytes(
+                http::StatusCode::NOT_FOUND,
+                r#"{"id": "not_found", "message": "Couldn't find that process type (web)."}"#,
+            )
+        })
+        .post("/apps/review/addons", |_| {
+            ok(addon(
+                "postgresql-flat-67890",
+                "review",
+                "heroku-postgresql:hobby-dev",
+                &["DATABASE_URL"],
+            ))
+        })
+        .post("/addon-attachments", |_| {
+            ok(json!({
+                "addon": {"id": "papertrail-shared-1-id", "name": "papertrail-shared-1", "app": {"id": "shared-id", "name": "shared"}},
+                "app": {"id": "review-id", "name": "review"},
+                "created_at": "2020-01-01T12:00:00Z",
+                "id": "attachment-id",
+                "name": "PAPERTRAIL",
+                "namespace": null,
+                "updated_at": "2020-01-01T12:00:00Z",
+                "web_url": null,
+                "log_input_url": null,
+            }))
+        })
+        .transport()
+}
+
+fn body(request: &HttpRequest) -> serde_json::Value {
+    serde_json::from_slice(request.body.as_ref().unwrap()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plans_clone() {
+        let client = util::memory_client(heroku());
+
+        let plan = AppCloner::new(&client, "staging", "review").plan().unwrap();
+
+        let actions: Vec<String> = plan.actions.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            actions,
+            vec![
+                "set config vars RAILS_ENV, SECRET_KEY_BASE",
+                "provision add-on heroku-postgresql:hobby-dev",
+                "attach add-on papertrail-shared-1",
+                "set buildpacks heroku/nodejs, heroku/ruby",
+                "enable feature runtime-heroku-metrics",
+                "scale web to 2:standard-1X",
+                "add domain staging.example.com",
+                "add log drain https://logs.example.com/drain",
+                "add notify webhook https://hooks.example.com/heroku for api:release",
+            ]
+        );
+        let skipped: Vec<(CloneItem, &str)> = plan
+            .skipped
+            .iter()
+            .map(|skipped| (skipped.item, skipped.name.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                (CloneItem::ConfigVars, "DATABASE_URL"),
+                (CloneItem::ConfigVars, "HEROKU_POSTGRESQL_PINK_URL"),
+                (CloneItem::ConfigVars, "LEGACY_FLAG"),
+                (CloneItem::ConfigVars, "PAPERTRAIL_API_TOKEN"),
+                (CloneItem::Domains, "staging.herokuapp.com"),
+                (
+                    CloneItem::LogDrains,
+                    "syslog+tls://logs.papertrailapp.com:1234"
+                ),
+            ]
+        );
+        assert_eq!(
+            plan.skipped[1].reason,
+            "set by add-on postgresql-round-12345"
+        );
+        assert_eq!(plan.skipped[2].reason, "has no value");
+    }
+
+    #[test]
+    fn applies_clone() {
+        let transport = heroku();
+        let client = util::memory_client(transport.clone());
+
+        let report = AppCloner::new(&client, "staging", "review")
+            .only(&[CloneItem::ConfigVars, CloneItem::Formation])
+            .include(CloneItem::Addons)
+            .exclude(CloneItem::ConfigVars)
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            report.copied,
+            vec![
+                CloneAction::CreateAddon {
+                    plan: String::from("heroku-postgresql:hobby-dev")
+                },
+                CloneAction::AttachAddon {
+                    addon: String::from("papertrail-shared-1")
+                },
+            ]
+        );
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0.to_string(), "scale web to 2:standard-1X");
+        assert_eq!(report.skipped.len(), 6);
+        assert!(report
+            .skipped
+            .iter()
+            .all(|skipped| skipped.reason == "excluded"));
+
+        let requests = transport.requests();
+        let writes: Vec<&HttpRequest> = requests
+            .iter()
+            .filter(|request| request.method != http::Method::GET)
+            .collect();
+        assert_eq!(
+            writes
+                .iter()
+                .map(|request| (request.method.as_str(), request.url.path()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("POST", "/apps/review/addons"),
+                ("POST", "/addon-attachments"),
+                ("PATCH", "/apps/review/formation/web"),
+            ]
+        );
+        assert_eq!(body(writes[0])["plan"], "heroku-postgresql:hobby-dev");
+        assert_eq!(body(writes[1])["addon"], "papertrail-shared-1");
+        assert_eq!(body(writes[1])["app"], "review");
+        assert_eq!(
+            body(writes[2]),
+            json!({"quantity": 2, "size": "standard-1X"})
+        );
+    }
+}
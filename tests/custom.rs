@@ -0,0 +1,91 @@
+use heroku_rs::endpoints::{apps, custom};
+use heroku_rs::framework::endpoint::{HerokuEndpoint, Method};
+use heroku_rs::framework::response::HerokuApiFailure;
+use serde::Serialize;
+use util::assert_valid_url;
+mod util;
+
+#[derive(Serialize, Clone)]
+struct Search {
+    name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heroku_rs::framework::apiclient::HerokuApiClient;
+    // run with `cargo test -- --nocapture` for  the logs
+
+    #[test]
+    fn assert_valid_url_custom_typed() {
+        let app_id = "123xyz";
+        let feature_id = "xyz123";
+        let response = util::get_client().request(
+            &custom::CustomEndpointTyped::<apps::AppFeature>::new(
+                Method::Get,
+                "apps/{app_id}/features/{feature_id}",
+            )
+            .path_param("app_id", app_id)
+            .path_param("feature_id", feature_id)
+            .build(),
+        );
+        let endpoint = format!("{}{}{}{}", "apps/", app_id, "/features/", feature_id);
+        assert_valid_url(response, endpoint)
+    }
+
+    #[test]
+    fn custom_typed_query_headers_and_variant() {
+        let endpoint =
+            custom::CustomEndpointTyped::<Vec<apps::App>, Search>::new(Method::Get, "apps")
+                .query_params(Search {
+                    name: String::from("foo"),
+                })
+                .header(
+                    http::header::RANGE,
+                    http::HeaderValue::from_static("id ..; max=10"),
+                )
+                .variant("pipelines")
+                .build();
+
+        assert_eq!(endpoint.path(), "apps");
+        assert_eq!(endpoint.query().unwrap().name, "foo");
+        assert_eq!(endpoint.headers()[http::header::RANGE], "id ..; max=10");
+        assert_eq!(
+            endpoint.version(),
+            "application/vnd.heroku+json; version=3.pipelines"
+        );
+    }
+
+    #[test]
+    fn custom_typed_encodes_path_params() {
+        let mut endpoint = custom::CustomEndpointTyped::<apps::AppFeature>::new(
+            Method::Get,
+            "apps/{app_id}/features/{feature_id}",
+        );
+        endpoint.path_params = vec![
+            (String::from("app_id"), String::from("my app/1")),
+            (String::from("feature_id"), String::from("50%?")),
+        ];
+
+        assert_eq!(endpoint.path(), "apps/my%20app%2F1/features/50%25%3F");
+        assert_eq!(endpoint.validate(), Ok(()));
+    }
+
+    #[test]
+    fn custom_typed_refuses_unresolved_path_params() {
+        let endpoint = custom::CustomEndpointTyped::<apps::AppFeature>::new(
+            Method::Get,
+            "apps/{app_id}/features/{feature_id}",
+        )
+        .path_param("app_id", "123xyz")
+        .build();
+
+        assert_eq!(endpoint.unresolved_params(), vec!["feature_id"]);
+        match util::get_client().request(&endpoint) {
+            Err(HerokuApiFailure::Invalid(e)) => assert!(e.to_string().ends_with(
+                "The path apps/{app_id}/features/{feature_id} has no value for {feature_id}"
+            )),
+            other => panic!("Expected an invalid request, got {:?}", other),
+        }
+    }
+}
@@ -1,7 +1,145 @@
-use heroku_rs::endpoints::dynos;
-use util::assert_valid_url;
+use heroku_rs::endpoints::dynos::{self, attach, DetachedRun, Rendezvous};
+use heroku_rs::framework::response::HerokuApiFailure;
+use heroku_rs::framework::transport::{HttpResponse, MemoryTransport};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use util::{assert_valid_url, ok, text, Routes};
 mod util;
 
+const RUN_LOGS: &str = "2019-12-31T10:00:00+00:00 app[run.1234]: output of an earlier run.1234
+2020-10-16T10:00:00+00:00 heroku[run.1234]: Starting process with command `rake db:migrate`
+2020-10-16T10:00:01+00:00 heroku[run.1234]: State changed from starting to up
+2020-10-16T10:00:02+00:00 app[run.1234]: == CreateUsers: migrating
+2020-10-16T10:00:03+00:00 app[run.1234]: == CreateUsers: migrated (0.0021s)
+2020-10-16T10:00:04+00:00 heroku[run.1234]: Process exited with status 0
+2020-10-16T10:00:04+00:00 heroku[run.1234]: State changed from up to complete
+";
+
+/// The logs of a run whose exit Heroku never logged.
+const RUN_LOGS_WITHOUT_EXIT: &str =
+    "2020-10-16T10:00:00+00:00 heroku[run.1234]: Starting process with command `rake db:migrate`
+2020-10-16T10:00:02+00:00 app[run.1234]: == CreateUsers: migrating
+";
+
+/// A transport running a one-off dyno, which is gone after `polls` checks, and whose logs are `RUN_LOGS`.
+fn platform(polls: usize) -> Arc<MemoryTransport> {
+    platform_with(polls, RUN_LOGS, http::StatusCode::NOT_FOUND)
+}
+
+/// A transport running a one-off dyno whose logs are `logs`. After `polls` checks of the dyno, they fail with
+/// `status`.
+fn platform_with(
+    polls: usize,
+    logs: &'static str,
+    status: http::StatusCode,
+) -> Arc<MemoryTransport> {
+    let dyno = serde_json::json!({
+        "app": {"id": "01234567-89ab-cdef-0123-456789abcdef", "name": "example"},
+        "attach_url": null,
+        "command": "rake db:migrate",
+        "created_at": "2020-01-01T12:00:00Z",
+        "id": "run-dyno-id",
+        "name": "run.1234",
+        "release": {"id": "01234567-89ab-cdef-0123-456789abcdef", "version": 11},
+        "size": "standard-1X",
+        "state": "up",
+        "type": "run",
+        "updated_at": "2020-01-01T12:00:00Z",
+    });
+    let checks = AtomicUsize::new(0);
+    let created = dyno.clone();
+    let stopped = dyno.clone();
+    Routes::new()
+        .post("/apps/example/dynos", move |_| ok(created.clone()))
+        .post("/apps/example/log-sessions", |_| {
+            ok(serde_json::json!({
+                "created_at": "2020-01-01T12:00:00Z",
+                "id": "01234567-89ab-cdef-0123-456789abcdef",
+                "logplex_url": "https://logplex.example.com/sessions/1",
+                "updated_at": "2020-01-01T12:00:00Z",
+            }))
+        })
+        .get("/sessions/1", move |_| text(logs))
+        .get("/apps/example/dynos/run-dyno-id", move |_| {
+            if checks.fetch_add(1, Ordering::SeqCst) < polls {
+                ok(dyno.clone())
+            } else {
+                HttpResponse::from_bytes(
+                    status,
+                    r#"{"id": "not_found", "message": "Couldn't find that process."}"#,
+                )
+            }
+        })
+        .post("/apps/example/dynos/run-dyno-id/actions/stop", move |_| {
+            ok(stopped.clone())
+        })
+        .transport()
+}
+
+/// Wait for a detached run of `transport`'s dyno, failing instead of hanging.
+fn wait_detached(
+    transport: Arc<MemoryTransport>,
+) -> heroku_rs::framework::response::ApiResponse<dynos::RunOutput> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let client = util::memory_client(transport);
+        let create = dynos::DynoCreate::new("example", "rake db:migrate").build();
+        let run = DetachedRun::new(&client, &create)
+            .poll_interval(Duration::from_millis(10))
+            .grace(Duration::from_millis(100))
+            .wait();
+        sender.send(run).unwrap();
+    });
+    receiver
+        .recv_timeout(Duration::from_secs(10))
+        .expect("the detached run hangs")
+}
+
+fn paths(transport: &MemoryTransport) -> Vec<String> {
+    transport
+        .requests()
+        .iter()
+        .map(|request| format!("{} {}", request.method, request.url.path()))
+        .collect()
+}
+
+/// A stand-in rendezvous server: checks the secret, acknowledges it, reads one line of input
+/// and answers with `output`, a few bytes at a time. Returns the input it read.
+fn rendezvous(
+    secret: &'static str,
+    output: &'static str,
+) -> (TcpStream, String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let url = format!("rendezvous://{}/{}", addr, secret);
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut stream = stream;
+
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line != format!("{}\r\n", secret) {
+            return String::new();
+        }
+        stream.write_all(b"rendezvous\r\n").unwrap();
+
+        let mut input = String::new();
+        reader.read_line(&mut input).unwrap();
+        for chunk in output.as_bytes().chunks(5) {
+            stream.write_all(chunk).unwrap();
+            stream.flush().unwrap();
+            thread::sleep(Duration::from_millis(1));
+        }
+        input
+    });
+    (TcpStream::connect(addr).unwrap(), url, server)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,4 +187,137 @@ mod tests {
         let endpoint = format!("{}{}{}", "apps/", app_id, "/dynos");
         assert_valid_url(response, endpoint)
     }
+
+    #[test]
+    fn attaches_to_dyno() {
+        let output = "Migrating...\r\ndone\r\n\u{FFFF} heroku-command-exit-status: 3\r\n";
+        let (stream, url, server) = rendezvous("s3cr3t", output);
+
+        let mut rendezvous = Rendezvous::handshake(stream, &url).unwrap();
+        rendezvous.write_all(b"yes\n").unwrap();
+        let mut read = String::new();
+        rendezvous.read_to_string(&mut read).unwrap();
+
+        assert_eq!(read, "Migrating...\r\ndone\r\n");
+        assert_eq!(rendezvous.exit_status(), Some(3));
+        assert_eq!(server.join().unwrap(), "yes\n");
+        assert_eq!(
+            attach::with_exit_status("rake db:migrate"),
+            "rake db:migrate; echo \"\u{FFFF} heroku-command-exit-status: $?\""
+        );
+    }
+
+    #[test]
+    fn rejects_unacknowledged_secret() {
+        let (stream, url, server) = rendezvous("s3cr3t", "");
+        let wrong = url.replace("s3cr3t", "wrong");
+
+        let attached = Rendezvous::handshake(stream, &wrong);
+
+        assert!(attached.is_err());
+        server.join().unwrap();
+        assert!(Rendezvous::handshake(
+            std::io::Cursor::new(Vec::new()),
+            "https://example.com/s3cr3t"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn waits_for_detached_run() {
+        let transport = platform(2);
+        let client = util::memory_client(transport.clone());
+        let create = dynos::DynoCreate::new("example", "rake db:migrate")
+            .attach(true)
+            .build();
+
+        let run = DetachedRun::new(&client, &create)
+            .poll_interval(Duration::from_millis(50))
+            .wait()
+            .unwrap();
+
+        assert_eq!(
+            run.output,
+            vec![
+                "== CreateUsers: migrating",
+                "== CreateUsers: migrated (0.0021s)"
+            ]
+        );
+        assert_eq!(run.exit_status(), Some(0));
+        assert!(!run.timed_out);
+        let requests = transport.requests();
+        let created: serde_json::Value =
+            serde_json::from_slice(requests[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!(created["attach"], false);
+        let paths = paths(&transport);
+        let session = paths
+            .iter()
+            .position(|path| path == "POST /apps/example/log-sessions")
+            .unwrap();
+        let session_params: serde_json::Value =
+            serde_json::from_slice(requests[session].body.as_ref().unwrap()).unwrap();
+        assert_eq!(session_params["dyno"], "run.1234");
+        assert_eq!(session_params["tail"], true);
+        // The logs are streamed while the dyno runs, not read once it is gone.
+        let last_poll = paths
+            .iter()
+            .rposition(|path| path == "GET /apps/example/dynos/run-dyno-id")
+            .unwrap();
+        assert!(session < last_poll, "{:?}", paths);
+        assert_eq!(
+            paths
+                .iter()
+                .filter(|path| *path == "GET /apps/example/dynos/run-dyno-id")
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn stops_detached_run_after_timeout() {
+        let transport = platform(3);
+        let client = util::memory_client(transport.clone());
+        let create = dynos::DynoCreate::new("example", "rake db:migrate").build();
+
+        let run = DetachedRun::new(&client, &create)
+            .poll_interval(Duration::from_millis(0))
+            .timeout(Duration::from_millis(0))
+            .wait()
+            .unwrap();
+
+        assert!(run.timed_out);
+        let stops = paths(&transport)
+            .into_iter()
+            .filter(|path| path == "POST /apps/example/dynos/run-dyno-id/actions/stop")
+            .count();
+        assert_eq!(stops, 1);
+    }
+
+    #[test]
+    fn detached_run_ends_without_exit_line() {
+        let transport = platform_with(1, RUN_LOGS_WITHOUT_EXIT, http::StatusCode::NOT_FOUND);
+
+        let run = wait_detached(transport).unwrap();
+
+        assert_eq!(run.output, vec!["== CreateUsers: migrating"]);
+        assert!(run.exit_line.is_none());
+        assert_eq!(run.exit_status(), None);
+    }
+
+    #[test]
+    fn detached_run_fails_when_the_dyno_check_fails() {
+        let transport = platform_with(
+            1,
+            RUN_LOGS_WITHOUT_EXIT,
+            http::StatusCode::INTERNAL_SERVER_ERROR,
+        );
+
+        match wait_detached(transport) {
+            Err(HerokuApiFailure::Error(status, _)) => assert_eq!(status, 500),
+            other => panic!(
+                "expected the failed check, got {:?}",
+                other.map(|run| run.output)
+            ),
+        }
+    }
 }
@@ -0,0 +1,279 @@
+use heroku_rs::endpoints::formations::{
+    FormationBatchUpdate, FormationIssue, FormationReconciler, FormationUpdate, Procfile,
+    ReconcileError,
+};
+use heroku_rs::framework::{
+    apiclient::HerokuApiClient,
+    transport::{HttpRequest, MemoryTransport},
+};
+use serde_json::json;
+use std::sync::Arc;
+
+mod util;
+use util::{ok, Routes};
+
+fn formation(process_type: &str, quantity: i32, size: &str) -> serde_json::Value {
+    json!({
+        "app": {"id": "app-id", "name": "app"}, "command": "bin/run", "created_at": "2020-01-01T12:00:00Z",
+        "id": format!("{}-id", process_type), "quantity": quantity, "size": size, "type": process_type,
+        "updated_at": "2020-01-01T12:00:00Z",
+    })
+}
+
+fn release(version: i64, current: bool, slug_id: &str) -> serde_json::Value {
+    json!({
+        "addon_plan_names": [], "app": {"id": "app-id", "name": "app"}, "created_at": "2020-01-01T12:00:00Z",
+        "current": current, "description": "Deploy", "id": format!("release-{}", version), "output_stream_url": null,
+        "slug": {"id": slug_id}, "status": "succeeded", "updated_at": "2020-01-01T12:00:00Z",
+        "user": {"id": "user-id", "email": "user@example.com"}, "version": version,
+    })
+}
+
+/// An app whose current release runs a slug with `web`, `worker` and `clock` process types, where only `web`
+/// is scaled, and whose formation still has a `legacy` process type.
+fn heroku() -> Arc<MemoryTransport> {
+    Routes::new()
+        .get("/apps/app/releases", |_| {
+            ok(json!([
+                release(3, false, "failed-slug-id"),
+                release(2, true, "slug-id"),
+            ]))
+        })
+        .get("/apps/app/slugs/slug-id", |_| {
+            ok(json!({
+                "blob": {"method": "get", "url": "https://example.com/slug.tgz"},
+                "buildpack_provided_description": "Ruby", "checksum": null, "commit": null, "commit_description": null,
+                "created_at": "2020-01-01T12:00:00Z", "id": "slug-id",
+                "process_types": {"web": "bundle exec puma", "worker": "bundle exec sidekiq", "clock": "bin/clock"},
+                "size": 1024, "stack": {"id": "stack-id", "name": "heroku-20"}, "updated_at": "2020-01-01T12:00:00Z",
+            }))
+        })
+        .get("/apps/app/formation", |_| {
+            ok(json!([
+                formation("web", 1, "Standard-1X"),
+                formation("worker", 0, "Standard-1X"),
+                formation("clock", 0, "Standard-1X"),
+                formation("legacy", 0, "Standard-1X"),
+            ]))
+        })
+        .patch("/apps/app/formation", |request| {
+            let body: serde_json::Value =
+                serde_json::from_slice(request.body.as_ref().unwrap()).unwrap();
+            let updated: Vec<serde_json::Value> = body["updates"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|update| {
+                    formation(
+                        update["type"].as_str().unwrap(),
+                        update["quantity"].as_i64().unwrap() as i32,
+                        update["size"].as_str().unwrap_or("Standard-1X"),
+                    )
+                })
+                .collect();
+            ok(json!(updated))
+        })
+        .transport()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_procfiles() {
+        let procfile: Procfile =
+            "# processes\n\nweb: bundle exec puma -C config/puma.rb\nrelease:bin/migrate\n"
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            procfile.iter().collect::<Vec<_>>(),
+            vec![
+                ("web", "bundle exec puma -C config/puma.rb"),
+                ("release", "bin/migrate"),
+            ]
+        );
+        let error = "web: bin/web\nweb worker: bin/worker"
+            .parse::<Procfile>()
+            .unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            "web: bin/web\nweb: bin/other"
+                .parse::<Procfile>()
+                .unwrap_err()
+                .to_string(),
+            "Line 2: process type web is declared twice"
+        );
+        assert!("web:".parse::<Procfile>().is_err());
+    }
+
+    #[test]
+    fn plans_and_applies_formation() {
+        let transport = heroku();
+        let client = util::memory_client(transport.clone());
+        let procfile: Procfile = "web: bundle exec puma -C config/puma.rb\nworker: bundle exec sidekiq\nurgent: bin/urgent"
+            .parse()
+            .unwrap();
+
+        let reconciler = FormationReconciler::new(&client, "app")
+            .procfile(&procfile)
+            .scale("web", 1)
+            .size("web", "standard-2X")
+            .scale("worker", 2)
+            .size("clock", "standard-1x");
+        let plan = reconciler.plan().unwrap();
+        let formation = reconciler.apply(&plan).unwrap();
+
+        assert_eq!(
+            plan.changes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "scale web from 1 to 1, resize from Standard-1X to standard-2X",
+                "scale worker from 0 to 2",
+            ]
+        );
+        assert_eq!(
+            plan.issues,
+            vec![
+                FormationIssue::CommandChanged {
+                    process_type: String::from("web"),
+                    procfile: String::from("bundle exec puma -C config/puma.rb"),
+                    slug: String::from("bundle exec puma"),
+                },
+                FormationIssue::MissingFromSlug {
+                    process_type: String::from("urgent"),
+                },
+                FormationIssue::Undeclared {
+                    process_type: String::from("clock"),
+                },
+                FormationIssue::NotInSlug {
+                    process_type: String::from("legacy"),
+                },
+            ]
+        );
+
+        let requests = transport.requests();
+        assert_eq!(
+            requests[0].headers[http::header::RANGE],
+            "version ..; order=desc"
+        );
+        let writes: Vec<&HttpRequest> = requests
+            .iter()
+            .filter(|request| request.method == http::Method::PATCH)
+            .collect();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].url.path(), "/apps/app/formation");
+        let body: serde_json::Value =
+            serde_json::from_slice(writes[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            json!({"updates": [
+                {"type": "web", "quantity": 1, "size": "standard-2X"},
+                {"type": "worker", "quantity": 2},
+            ]})
+        );
+        assert_eq!(formation.len(), 2);
+        assert_eq!(formation[1].quantity, 2);
+    }
+
+    #[test]
+    fn flags_formation_missing_from_slug_without_procfile() {
+        let client = util::memory_client(heroku());
+
+        let plan = FormationReconciler::new(&client, "app")
+            .scale("worker", 1)
+            .plan()
+            .unwrap();
+
+        assert_eq!(
+            plan.issues
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["process type legacy is in the formation, but not in the slug"]
+        );
+    }
+
+    #[test]
+    fn applying_empty_plan_returns_current_formation() {
+        let transport = heroku();
+        let client = util::memory_client(transport.clone());
+
+        let reconciler = FormationReconciler::new(&client, "app")
+            .scale("web", 1)
+            .size("web", "standard-1x");
+        let plan = reconciler.plan().unwrap();
+        let formation = reconciler.apply(&plan).unwrap();
+
+        assert!(plan.is_empty());
+        assert_eq!(formation, plan.formation);
+        assert_eq!(formation.len(), 4);
+        assert!(transport
+            .requests()
+            .iter()
+            .all(|request| request.method == http::Method::GET));
+    }
+
+    #[test]
+    fn refuses_process_types_missing_from_slug() {
+        let transport = heroku();
+        let client = util::memory_client(transport.clone());
+
+        let result = FormationReconciler::new(&client, "app")
+            .slug("slug-id")
+            .scale("web", 2)
+            .scale("urgent", 1)
+            .run();
+
+        match result {
+            Err(ReconcileError::UndefinedProcessTypes(process_types)) => {
+                assert_eq!(process_types, vec!["urgent"])
+            }
+            other => panic!("Expected undefined process types, got {:?}", other),
+        }
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn builds_formation_update_for_the_process_type() {
+        let update = FormationUpdate::new("app", "web").quantity(2).build();
+
+        assert_eq!(update.app_id, "app");
+        assert_eq!(update.formation_id, "web");
+    }
+
+    #[test]
+    fn batch_updates_in_a_single_call() {
+        let transport = heroku();
+        let client = util::memory_client(transport.clone());
+
+        let formation = client
+            .request(
+                &FormationBatchUpdate::new("app")
+                    .update("web", Some(2), None)
+                    .update("worker", Some(1), Some("standard-2X"))
+                    .build(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            formation
+                .iter()
+                .map(|formation| (formation.r#type.as_str(), formation.quantity))
+                .collect::<Vec<_>>(),
+            vec![("web", 2), ("worker", 1)]
+        );
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, http::Method::PATCH);
+        let body: serde_json::Value =
+            serde_json::from_slice(requests[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            body["updates"][1],
+            json!({"type": "worker", "quantity": 1, "size": "standard-2X"})
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use heroku_rs::endpoints::logs::{DrainBatch, ErrorCode, LogLine, LogTail, RouterLog};
+use heroku_rs::framework::{
+    response::HerokuApiFailure,
+    transport::{HttpRequest, HttpResponse, MemoryTransport},
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod util;
+
+/// A transport creating log sessions, whose streams return `bodies` in turn.
+fn logplex(bodies: Vec<&'static str>) -> Arc<MemoryTransport> {
+    let sessions = AtomicUsize::new(0);
+    Arc::new(MemoryTransport::new(move |request: &HttpRequest| {
+        if request.method == http::Method::POST {
+            let session = sessions.fetch_add(1, Ordering::SeqCst);
+            let body = serde_json::json!({
+                "created_at": "2020-01-01T12:00:00Z",
+                "id": "01234567-89ab-cdef-0123-456789abcdef",
+                "logplex_url": format!("https://logplex.example.com/sessions/{}?srv=1", session),
+                "updated_at": "2020-01-01T12:00:00Z",
+            });
+            return HttpResponse::from_bytes(http::StatusCode::CREATED, body.to_string());
+        }
+        let session: usize = request
+            .url
+            .path()
+            .rsplit('/')
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        HttpResponse::from_bytes(http::StatusCode::OK, bodies[session.min(bodies.len() - 1)])
+    }))
+}
+
+/// A batch recorded from Logplex, with a router line and a runtime error.
+const DRAIN_BODY: &str = "215 <158>1 2020-10-16T10:00:00.123456+00:00 host heroku router - at=info method=GET path=\"/\" host=example.herokuapp.com request_id=1 fwd=\"10.0.0.1\" dyno=web.1 connect=1ms service=18ms status=200 bytes=13 protocol=https\n\
+     86 <45>1 2020-10-16T10:00:01+00:00 host heroku web.1 - Error R14 (Memory quota exceeded)\n";
+
+fn drain_headers(count: &str, token: &str) -> http::HeaderMap {
+    let mut headers = http::HeaderMap::new();
+    headers.insert("Content-Type", "application/logplex-1".parse().unwrap());
+    headers.insert("Logplex-Msg-Count", count.parse().unwrap());
+    headers.insert(
+        "Logplex-Frame-Id",
+        "09C557EAFCFB6CF2740EE62F62971098".parse().unwrap(),
+    );
+    headers.insert("Logplex-Drain-Token", token.parse().unwrap());
+    headers
+}
+
+fn session_params(transport: &MemoryTransport) -> Vec<serde_json::Value> {
+    transport
+        .requests()
+        .iter()
+        .filter(|request| request.method == http::Method::POST)
+        .map(|request| serde_json::from_slice(request.body.as_ref().unwrap()).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resumes_without_repeating_lines() {
+        let transport = logplex(vec!["a\nb\nc\n", "b\nc\nd\n"]);
+        let client = util::memory_client(transport.clone());
+
+        let lines: Vec<String> = LogTail::new(&client, "123xyz")
+            .dyno("web.1")
+            .lines(3)
+            .retry_delay(Duration::from_millis(0))
+            .take(4)
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(lines, vec!["a", "b", "c", "d"]);
+        let params = session_params(&transport);
+        assert_eq!(params.len(), 2);
+        assert_eq!(
+            params[0],
+            serde_json::json!({"dyno": "web.1", "lines": 3, "tail": true})
+        );
+        assert_eq!(params[1]["lines"], 100);
+        assert_eq!(
+            transport.requests()[0].url.as_str(),
+            "https://api.heroku.com/apps/123xyz/log-sessions"
+        );
+    }
+
+    #[test]
+    fn keeps_replayed_lines_after_a_gap() {
+        let transport = logplex(vec!["a\nb\n", "x\ny\nz\n"]);
+        let client = util::memory_client(transport);
+
+        let lines: Vec<String> = LogTail::new(&client, "123xyz")
+            .resume_lines(2)
+            .retry_delay(Duration::from_millis(0))
+            .take(5)
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(lines, vec!["a", "b", "x", "y", "z"]);
+    }
+
+    #[test]
+    fn stops_without_tail() {
+        let transport = logplex(vec!["a\nlast without newline"]);
+        let client = util::memory_client(transport.clone());
+
+        let lines: Vec<String> = LogTail::new(&client, "123xyz")
+            .source("app")
+            .tail(false)
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(lines, vec!["a", "last without newline"]);
+        let params = session_params(&transport);
+        assert_eq!(
+            params,
+            vec![serde_json::json!({"source": "app", "tail": false})]
+        );
+    }
+
+    #[test]
+    fn gives_up_on_invalid_logplex_urls() {
+        let transport = Arc::new(MemoryTransport::responding(
+            http::StatusCode::CREATED,
+            r#"{"created_at": "2020-01-01T12:00:00Z", "id": "session-id", "logplex_url": "not a url", "updated_at": "2020-01-01T12:00:00Z"}"#,
+        ));
+        let client = util::memory_client(transport.clone());
+
+        let mut logs = LogTail::new(&client, "123xyz").retry_delay(Duration::from_millis(0));
+
+        match logs.next() {
+            Some(Err(HerokuApiFailure::Parse(_))) => {}
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+        assert!(logs.next().is_none());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn parses_app_line() {
+        let line = LogLine::parse(
+            "2020-10-16T10:00:00.123456+00:00 app[web.1]: Started GET \"/\" for 10.1.2.3\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            line.timestamp.to_rfc3339(),
+            "2020-10-16T10:00:00.123456+00:00"
+        );
+        assert_eq!(line.source, "app");
+        assert_eq!(line.dyno, "web.1");
+        assert_eq!(line.message, "Started GET \"/\" for 10.1.2.3");
+        assert!(line.fields.is_empty());
+    }
+
+    #[test]
+    fn parses_fields() {
+        let line: LogLine = "2020-10-16T10:00:00+00:00 heroku[web.1]: source=web.1 dyno=heroku.1234.abcd sample#memory_total=21.00MB sample#load_avg_1m=0.00 desc=\"Request \\\"timeout\\\"\" empty= flag"
+            .parse()
+            .unwrap();
+
+        assert_eq!(line.source, "heroku");
+        assert_eq!(line.field("source"), Some("web.1"));
+        assert_eq!(line.field("dyno"), Some("heroku.1234.abcd"));
+        assert_eq!(line.field("sample#memory_total"), Some("21.00MB"));
+        assert_eq!(line.field("sample#load_avg_1m"), Some("0.00"));
+        assert_eq!(line.field("desc"), Some("Request \"timeout\""));
+        assert_eq!(line.field("empty"), Some(""));
+        assert_eq!(line.field("flag"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert!(LogLine::parse("not a log line").is_err());
+        assert!(LogLine::parse("2020-10-16T10:00:00+00:00 no process").is_err());
+        assert!(LogLine::parse("yesterday app[web.1]: hello").is_err());
+    }
+
+    #[test]
+    fn parses_router_line() {
+        let request: RouterLog = "2020-10-16T10:00:00+00:00 heroku[router]: at=info method=GET path=\"/users?page=2\" host=example.herokuapp.com request_id=8601b555-6a83-4c12-8269-97c8e32cdb22 fwd=\"204.204.204.204\" dyno=web.1 connect=1ms service=18ms status=200 bytes=13 protocol=https"
+            .parse()
+            .unwrap();
+
+        assert_eq!(request.at, "info");
+        assert_eq!(request.method.as_deref(), Some("GET"));
+        assert_eq!(request.path.as_deref(), Some("/users?page=2"));
+        assert_eq!(request.dyno.as_deref(), Some("web.1"));
+        assert_eq!(request.connect, Some(Duration::from_millis(1)));
+        assert_eq!(request.service, Some(Duration::from_millis(18)));
+        assert_eq!(request.status, Some(200));
+        assert_eq!(request.bytes, Some(13));
+        assert_eq!(request.code, None);
+        assert!(!request.is_error());
+    }
+
+    #[test]
+    fn parses_router_error() {
+        let request: RouterLog = "2020-10-16T10:00:30+00:00 heroku[router]: at=error code=H12 desc=\"Request timeout\" method=GET path=\"/\" host=example.herokuapp.com request_id=1 fwd=\"10.0.0.1\" dyno=web.1 connect=0ms service=30000ms status=503 bytes=0 protocol=https"
+            .parse()
+            .unwrap();
+
+        assert!(request.is_error());
+        assert_eq!(request.code, Some(ErrorCode::H12));
+        assert_eq!(request.desc.as_deref(), Some("Request timeout"));
+        assert_eq!(request.service, Some(Duration::from_secs(30)));
+        assert_eq!(ErrorCode::H12.to_string(), "H12 (Request timeout)");
+        assert_eq!(
+            "H42".parse::<ErrorCode>().unwrap(),
+            ErrorCode::Other(String::from("H42"))
+        );
+    }
+
+    #[test]
+    fn finds_error_codes_of_other_lines() {
+        let runtime: LogLine =
+            "2020-10-16T10:00:00+00:00 heroku[web.1]: Error R14 (Memory quota exceeded)"
+                .parse()
+                .unwrap();
+        assert_eq!(ErrorCode::from_line(&runtime), Some(ErrorCode::R14));
+        assert_eq!(RouterLog::from_line(&runtime), None);
+
+        let app: LogLine = "2020-10-16T10:00:00+00:00 app[web.1]: Error handling request"
+            .parse()
+            .unwrap();
+        assert_eq!(ErrorCode::from_line(&app), None);
+        assert!(app.to_string().parse::<RouterLog>().is_err());
+    }
+
+    #[test]
+    fn decodes_drain_batch() {
+        let batch =
+            DrainBatch::decode(&drain_headers("2", "d.1234"), DRAIN_BODY.as_bytes()).unwrap();
+
+        assert_eq!(batch.msg_count, Some(2));
+        assert_eq!(
+            batch.frame_id.as_deref(),
+            Some("09C557EAFCFB6CF2740EE62F62971098")
+        );
+        assert!(batch.verify_token("d.1234"));
+        assert!(!batch.verify_token("d.4321"));
+        assert_eq!(batch.messages.len(), 2);
+
+        let router = &batch.messages[0];
+        assert_eq!(router.priority, 158);
+        assert_eq!(router.facility(), 19);
+        assert_eq!(router.severity(), 6);
+        assert_eq!(router.app_name, "heroku");
+        assert_eq!(router.proc_id, "router");
+        assert_eq!(router.msg_id, None);
+        let request = RouterLog::from_line(&router.to_log_line()).unwrap();
+        assert_eq!(request.status, Some(200));
+
+        let runtime = &batch.messages[1];
+        assert_eq!(runtime.message, "Error R14 (Memory quota exceeded)");
+        assert_eq!(
+            ErrorCode::from_line(&runtime.to_log_line()),
+            Some(ErrorCode::R14)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_drain_batches() {
+        let headers = drain_headers("3", "d.1234");
+        assert!(DrainBatch::decode(&headers, DRAIN_BODY.as_bytes()).is_err());
+
+        let headers = drain_headers("1", "d.1234");
+        assert!(DrainBatch::decode(
+            &headers,
+            b"500 <40>1 2020-10-16T10:00:00+00:00 host app web.1 - truncated"
+        )
+        .is_err());
+        assert!(DrainBatch::decode(&headers, b"12 not syslog").is_err());
+        assert!(DrainBatch::decode(
+            &headers,
+            b"<40>1 2020-10-16T10:00:00+00:00 host app web.1 - no length"
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "drain_server")]
+    #[test]
+    fn receives_drain_batches() {
+        use heroku_rs::endpoints::logs::DrainServer;
+        use std::io::{Read, Write};
+        use std::net::TcpStream;
+
+        let server = DrainServer::bind("127.0.0.1:0")
+            .unwrap()
+            .verify_token("d.1234");
+        let addr = server.local_addr().unwrap();
+
+        let post = move |token: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(
+                stream,
+                "POST /logs HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/logplex-1\r\nLogplex-Msg-Count: 2\r\nLogplex-Drain-Token: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                token,
+                DRAIN_BODY.len(),
+                DRAIN_BODY
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let client = std::thread::spawn(move || (post("d.4321"), post("d.1234")));
+        let batch = server.recv().unwrap();
+        let (rejected, accepted) = client.join().unwrap();
+
+        assert!(rejected.starts_with("HTTP/1.1 403"), "{}", rejected);
+        assert!(accepted.starts_with("HTTP/1.1 204"), "{}", accepted);
+        assert_eq!(batch.drain_token.as_deref(), Some("d.1234"));
+        assert_eq!(batch.messages.len(), 2);
+    }
+
+    #[cfg(feature = "drain_server")]
+    #[test]
+    fn keeps_receiving_after_a_failed_request() {
+        use heroku_rs::endpoints::logs::DrainServer;
+        use std::io::{Read, Write};
+        use std::net::TcpStream;
+
+        let server = DrainServer::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+        let post = move |framing: &str, body: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(
+                stream,
+                "POST /logs HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/logplex-1\r\nLogplex-Msg-Count: 2\r\n{}\r\nConnection: close\r\n\r\n{}",
+                framing, body
+            )
+            .unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            let mut response = String::new();
+            let _ = stream.read_to_string(&mut response);
+            response
+        };
+
+        let client = std::thread::spawn(move || {
+            // The chunk size is not hexadecimal, so reading the body fails.
+            post(
+                "Transfer-Encoding: chunked",
+                "zz\r\nnot a chunk\r\n0\r\n\r\n",
+            );
+            post(&format!("Content-Length: {}", DRAIN_BODY.len()), DRAIN_BODY)
+        });
+        let batch = server.incoming().next();
+        let accepted = client.join().unwrap();
+
+        assert!(accepted.starts_with("HTTP/1.1 204"), "{}", accepted);
+        assert_eq!(batch.unwrap().messages.len(), 2);
+    }
+}
@@ -0,0 +1,197 @@
+use heroku_rs::endpoints::apps::{AppManifest, ManifestAddon, ManifestEnv, ManifestError};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn manifest() -> AppManifest {
+    serde_json::from_value(json!({
+        "name": "Small Sharp Tool",
+        "repository": "https://github.com/jane-doe/small-sharp-tool",
+        "keywords": ["productivity", "HTML5"],
+        "scripts": {"postdeploy": "bundle exec rake bootstrap"},
+        "env": {
+            "RAILS_ENV": "production",
+            "SECRET_TOKEN": {"description": "A secret key for verifying cookies", "generator": "secret"},
+            "WEB_CONCURRENCY": {"value": "5", "required": false},
+            "API_TOKEN": {"description": "Token of the upstream API"},
+        },
+        "formation": {"web": {"quantity": 1, "size": "standard-1X"}},
+        "addons": [
+            "openredis",
+            {"plan": "heroku-postgresql:hobby-dev", "as": "DATABASE", "options": {"version": "12"}},
+        ],
+        "buildpacks": [{"url": "heroku/ruby"}],
+        "environments": {
+            "test": {"scripts": {"test": "bundle exec rake test"}, "addons": ["heroku-redis:in-dyno"]},
+        },
+        "stack": "heroku-20",
+    }))
+    .unwrap()
+}
+
+fn error(path: &str, message: &str) -> ManifestError {
+    ManifestError {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_shorthand_and_full_forms() {
+        let manifest = manifest();
+
+        assert_eq!(
+            manifest.env["RAILS_ENV"].value.as_deref(),
+            Some("production")
+        );
+        assert!(!manifest.env["SECRET_TOKEN"].needs_value());
+        assert!(!manifest.env["WEB_CONCURRENCY"].needs_value());
+        assert!(manifest.env["API_TOKEN"].needs_value());
+        assert_eq!(
+            manifest.addons[0],
+            ManifestAddon {
+                plan: String::from("openredis"),
+                attachment: None,
+                options: None,
+            }
+        );
+        assert_eq!(manifest.addons[1].attachment.as_deref(), Some("DATABASE"));
+        assert_eq!(
+            manifest.scripts.postdeploy.as_ref().unwrap().command,
+            "bundle exec rake bootstrap"
+        );
+        assert_eq!(
+            manifest.environments["test"].addons[0].plan,
+            "heroku-redis:in-dyno"
+        );
+        assert_eq!(manifest.validate(), Ok(()));
+
+        let path = std::env::temp_dir().join("heroku_rs-manifest-test-app.json");
+        std::fs::write(&path, serde_json::to_string(&manifest).unwrap()).unwrap();
+        let read = AppManifest::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.unwrap(), manifest);
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let manifest: AppManifest = json!({
+            "website": "not a url",
+            "env": {
+                "1ST_KEY": "value",
+                "SECRET": {"generator": "uuid"},
+                "BOTH": {"value": "a", "generator": "secret"},
+            },
+            "formation": {"web worker": {"quantity": 1}},
+            "addons": [":hobby", {"plan": "heroku-redis", "as": "cache"}, {"plan": "papertrail", "as": "LOGS"}, {"plan": "logdna", "as": "LOGS"}],
+            "buildpacks": [{"url": ""}],
+            "environments": {"staging": {}, "review": {"scripts": {"postdeploy": " "}}},
+        })
+        .to_string()
+        .parse()
+        .unwrap();
+
+        let errors = manifest.validate().unwrap_err();
+
+        let paths: Vec<&str> = errors.iter().map(|error| error.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "website",
+                "env.1ST_KEY",
+                "env.BOTH",
+                "env.SECRET.generator",
+                "formation.web worker",
+                "addons[0].plan",
+                "addons[1].as",
+                "addons[3].as",
+                "buildpacks[0].url",
+                "environments.review.scripts.postdeploy",
+                "environments.staging",
+            ]
+        );
+        assert_eq!(
+            errors[3].to_string(),
+            "env.SECRET.generator: unknown generator \"uuid\", only \"secret\" is supported"
+        );
+        assert_eq!(
+            errors[7],
+            error("addons[3].as", "attachment name LOGS is already used")
+        );
+    }
+
+    #[test]
+    fn reports_schema_errors_with_their_path() {
+        let document = json!({
+            "addons": [5, {"plan": "heroku-redis", "as": null}],
+            "buildpack": "heroku/ruby",
+            "env": {
+                "DEBUG": {"required": "yes"},
+                "TOKEN": {"description": "API token", "requried": false},
+            },
+            "formation": {"web": {"quantity": -1}},
+            "scripts": {"postdeploy": {"size": "standard-2X"}},
+        });
+
+        let errors = AppManifest::from_value(document.clone()).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                error(
+                    "addons[0]",
+                    "expected a string or an object, got a number"
+                ),
+                error(
+                    "buildpack",
+                    "unknown key, expected one of name, description, keywords, website, repository, logo, success_url, scripts, env, formation, addons, buildpacks, environments, stack"
+                ),
+                error("env.DEBUG.required", "expected true or false, got a string"),
+                error(
+                    "env.TOKEN.requried",
+                    "unknown key, expected one of description, value, required, generator"
+                ),
+                error(
+                    "formation.web.quantity",
+                    "expected a non-negative integer, got a number"
+                ),
+                error("scripts.postdeploy.command", "is required"),
+            ]
+        );
+        let parsed = document.to_string().parse::<AppManifest>().unwrap_err();
+        assert!(parsed
+            .to_string()
+            .contains("; env.DEBUG.required: expected true or false, got a string; "));
+        let env = serde_json::from_value::<ManifestEnv>(json!({"value": "1", "requried": true}))
+            .unwrap_err();
+        assert!(env
+            .to_string()
+            .starts_with("requried: unknown key, expected one of "));
+    }
+
+    #[test]
+    fn converts_into_overrides() {
+        let manifest = manifest();
+
+        assert_eq!(
+            manifest.overrides(&HashMap::new()).unwrap_err(),
+            vec![error("env.API_TOKEN", "is required, but has no value")]
+        );
+
+        let mut env = HashMap::new();
+        env.insert("API_TOKEN", "token");
+        env.insert("RAILS_ENV", "staging");
+        let overrides = manifest.overrides(&env).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&overrides).unwrap(),
+            json!({
+                "buildpacks": [{"url": "heroku/ruby"}],
+                "env": {"API_TOKEN": "token", "RAILS_ENV": "staging", "WEB_CONCURRENCY": "5"},
+            })
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use heroku_rs::endpoints::formations;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A formation with the `generation` field Heroku added after the struct was written.
+    const FORMATION: &str = r#"{
+        "app": {"id": "01234567-89ab-cdef-0123-456789abcdef", "name": "example"},
+        "command": "bundle exec rails server -p $PORT",
+        "created_at": "2012-01-01T12:00:00Z",
+        "id": "01234567-89ab-cdef-0123-456789abcdef",
+        "quantity": 1,
+        "size": "standard-1X",
+        "type": "web",
+        "updated_at": "2012-01-01T12:00:00Z",
+        "generation": {"id": "01234567-89ab-cdef-0123-456789abcdef", "name": "cedar"}
+    }"#;
+
+    #[cfg(not(any(feature = "extra_fields", feature = "strict")))]
+    #[test]
+    fn unknown_fields_are_ignored() {
+        let formation: formations::Formation = serde_json::from_str(FORMATION).unwrap();
+        assert_eq!(formation.r#type, "web");
+    }
+
+    #[cfg(feature = "extra_fields")]
+    #[test]
+    fn unknown_fields_are_preserved() {
+        let formation: formations::Formation = serde_json::from_str(FORMATION).unwrap();
+        assert_eq!(formation.r#type, "web");
+        assert_eq!(formation.extra.len(), 1);
+        assert_eq!(formation.extra["generation"]["name"], "cedar");
+        assert!(formation.app.extra.is_empty());
+
+        let serialized = serde_json::to_value(&formation).unwrap();
+        assert_eq!(serialized["generation"]["name"], "cedar");
+    }
+
+    #[cfg(feature = "strict")]
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let error = serde_json::from_str::<formations::Formation>(FORMATION).unwrap_err();
+        assert!(error.to_string().contains("generation"));
+    }
+}
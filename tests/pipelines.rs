@@ -1,6 +1,111 @@
 use heroku_rs::endpoints::pipelines;
+use heroku_rs::framework::transport::{HttpRequest, HttpResponse, MemoryTransport, Transport};
+use serde_json::json;
+use std::sync::Arc;
 use util::assert_valid_url;
 mod util;
+use util::{ok, Routes};
+
+fn coupling(app: &str, stage: &str) -> serde_json::Value {
+    json!({
+        "app": {"id": app},
+        "created_at": "2020-01-01T12:00:00Z",
+        "id": format!("coupling-{}", app),
+        "pipeline": {"id": "pipeline-id"},
+        "stage": stage,
+        "updated_at": "2020-01-01T12:00:00Z",
+    })
+}
+
+fn release(app: &str, version: i64, status: &str) -> serde_json::Value {
+    json!({
+        "addon_plan_names": [],
+        "app": {"id": app, "name": app},
+        "created_at": "2020-01-01T12:00:00Z",
+        "current": true,
+        "description": "Promote",
+        "id": format!("release-{}", app),
+        "output_stream_url": null,
+        "slug": null,
+        "status": status,
+        "updated_at": "2020-01-01T12:00:00Z",
+        "user": {"id": "user-id", "email": "user@example.com"},
+        "version": version,
+    })
+}
+
+/// A pipeline with `staging` in staging, and `prod-eu` and `prod-us` in production.
+/// The promotion to `prod-us` fails, and the release of `prod-eu` succeeds.
+/// The latest release of `staging` has the given status.
+fn pipeline(source_status: &'static str) -> Arc<MemoryTransport> {
+    fn promotion(status: &str) -> serde_json::Value {
+        json!({
+            "created_at": "2020-01-01T12:00:00Z",
+            "id": "promotion-id",
+            "pipeline": {"id": "pipeline-id"},
+            "source": {"app": {"id": "staging"}, "release": {"id": "release-staging"}},
+            "status": status,
+            "updated_at": null,
+        })
+    }
+    Routes::new()
+        .get("/pipelines/pipeline-id/latest-releases", move |_| {
+            ok(json!([
+                release("staging", 12, source_status),
+                release("prod-eu", 9, "succeeded"),
+            ]))
+        })
+        .get("/pipelines/pipeline-id/pipeline-couplings", |_| {
+            ok(json!([
+                coupling("dev", "development"),
+                coupling("staging", "staging"),
+                coupling("prod-eu", "production"),
+                coupling("prod-us", "production"),
+            ]))
+        })
+        .post("/pipeline-promotions", |_| ok(promotion("pending")))
+        .get("/pipeline-promotions/promotion-id", |_| {
+            ok(promotion("completed"))
+        })
+        .get(
+            "/pipeline-promotions/promotion-id/promotion-targets",
+            |_| {
+                ok(json!([
+                    {
+                        "app": {"id": "prod-eu"},
+                        "error_message": null,
+                        "id": "target-eu",
+                        "pipeline_promotion": {"id": "promotion-id"},
+                        "release": {"id": "release-prod-eu"},
+                        "status": "succeeded",
+                    },
+                    {
+                        "app": {"id": "prod-us"},
+                        "error_message": "Couldn't copy the slug.",
+                        "id": "target-us",
+                        "pipeline_promotion": {"id": "promotion-id"},
+                        "release": null,
+                        "status": "failed",
+                    },
+                ]))
+            },
+        )
+        .get("/apps/prod-eu/releases/release-prod-eu", |_| {
+            ok(release("prod-eu", 10, "succeeded"))
+        })
+        .transport()
+}
+
+/// The `pipeline` of a given source status, where `overrides` answers some requests instead.
+fn pipeline_with<F>(source_status: &'static str, overrides: F) -> Arc<MemoryTransport>
+where
+    F: Fn(&HttpRequest) -> Option<HttpResponse> + Send + Sync + 'static,
+{
+    let base = pipeline(source_status);
+    Arc::new(MemoryTransport::new(move |request: &HttpRequest| {
+        overrides(request).unwrap_or_else(|| base.send(request.clone()).unwrap())
+    }))
+}
 
 #[cfg(test)]
 mod tests {
@@ -40,4 +145,156 @@ mod tests {
         let endpoint = format!("{}", "pipeline-couplings");
         assert_valid_url(response, endpoint)
     }
+
+    #[test]
+    fn promotes_to_next_stage() {
+        let transport = pipeline("succeeded");
+        let client = util::memory_client(transport.clone());
+
+        let summary = pipelines::Promoter::new(&client, "pipeline-id", "staging")
+            .require_successful_source()
+            .poll_interval(std::time::Duration::from_millis(0))
+            .run()
+            .unwrap();
+
+        assert_eq!(summary.promotion.status, "completed");
+        assert_eq!(summary.succeeded.len(), 1);
+        assert_eq!(summary.succeeded[0].app_id, "prod-eu");
+        assert_eq!(summary.succeeded[0].release.as_ref().unwrap().version, 10);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].app_id, "prod-us");
+        assert_eq!(
+            summary.failed[0].error_message.as_deref(),
+            Some("Couldn't copy the slug.")
+        );
+        let promotion = transport
+            .requests()
+            .into_iter()
+            .find(|request| request.url.path() == "/pipeline-promotions")
+            .unwrap();
+        let params: serde_json::Value = serde_json::from_slice(&promotion.body.unwrap()).unwrap();
+        assert_eq!(
+            params["targets"],
+            json!([{"app": {"id": "prod-eu"}}, {"app": {"id": "prod-us"}}])
+        );
+    }
+
+    #[test]
+    fn refuses_failed_source() {
+        let transport = pipeline("failed");
+        let client = util::memory_client(transport.clone());
+
+        let result = pipelines::Promoter::new(&client, "pipeline-id", "staging")
+            .require_successful_source()
+            .run();
+
+        match result {
+            Err(pipelines::PromotionError::SourceReleaseFailed(release)) => {
+                assert_eq!(release.version, 12)
+            }
+            other => panic!("Expected a failed source, got {:?}", other),
+        }
+        assert_eq!(transport.requests().len(), 1);
+
+        let result = pipelines::promote(&client, "pipeline-id", "prod-eu", &[]);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            pipelines::PromotionError::NoTargets.to_string()
+        );
+    }
+
+    #[test]
+    fn reports_targets_whose_release_cannot_be_waited_for() {
+        let transport = pipeline_with("succeeded", |request| {
+            match request.url.path() == "/apps/prod-eu/releases/release-prod-eu" {
+                true => Some(HttpResponse::from_bytes(
+                    http::StatusCode::INTERNAL_SERVER_ERROR,
+                    r#"{"id": "internal_server_error", "message": "Internal server error."}"#,
+                )),
+                false => None,
+            }
+        });
+        let client = util::memory_client(transport);
+
+        let summary = pipelines::Promoter::new(&client, "pipeline-id", "staging")
+            .poll_interval(std::time::Duration::from_millis(0))
+            .run()
+            .unwrap();
+
+        assert!(summary.succeeded.is_empty());
+        let failed: Vec<&str> = summary
+            .failed
+            .iter()
+            .map(|target| target.app_id.as_str())
+            .collect();
+        assert_eq!(failed, vec!["prod-eu", "prod-us"]);
+        assert!(summary.failed[0].release.is_none());
+        assert!(summary.failed[0]
+            .error_message
+            .as_deref()
+            .unwrap()
+            .starts_with("Waiting for release release-prod-eu failed: "));
+    }
+
+    #[test]
+    fn promotes_from_test_stage_to_review() {
+        let transport = pipeline_with("succeeded", |request| {
+            match request.url.path() == "/pipelines/pipeline-id/pipeline-couplings" {
+                true => Some(ok(json!([
+                    coupling("ci", "test"),
+                    coupling("pr-1", "review")
+                ]))),
+                false => None,
+            }
+        });
+        let client = util::memory_client(transport.clone());
+
+        pipelines::Promoter::new(&client, "pipeline-id", "ci")
+            .poll_interval(std::time::Duration::from_millis(0))
+            .run()
+            .unwrap();
+
+        let promotion = transport
+            .requests()
+            .into_iter()
+            .find(|request| request.url.path() == "/pipeline-promotions")
+            .unwrap();
+        let params: serde_json::Value = serde_json::from_slice(&promotion.body.unwrap()).unwrap();
+        assert_eq!(params["targets"], json!([{"app": {"id": "pr-1"}}]));
+    }
+
+    #[test]
+    fn promotion_timeout_keeps_its_id() {
+        let transport = pipeline_with("succeeded", |request| {
+            match request.url.path() == "/pipeline-promotions/promotion-id" {
+                true => Some(ok(json!({
+                    "created_at": "2020-01-01T12:00:00Z",
+                    "id": "promotion-id",
+                    "pipeline": {"id": "pipeline-id"},
+                    "source": {"app": {"id": "staging"}, "release": {"id": "release-staging"}},
+                    "status": "pending",
+                    "updated_at": null,
+                }))),
+                false => None,
+            }
+        });
+        let client = util::memory_client(transport);
+
+        let error = pipelines::Promoter::new(&client, "pipeline-id", "staging")
+            .poll_interval(std::time::Duration::from_millis(0))
+            .timeout(std::time::Duration::from_millis(0))
+            .run()
+            .unwrap_err();
+
+        match &error {
+            pipelines::PromotionError::TimedOut { promotion_id } => {
+                assert_eq!(promotion_id, "promotion-id")
+            }
+            other => panic!("Expected a timeout, got {:?}", other),
+        }
+        assert_eq!(
+            error.to_string(),
+            "Timed out waiting for the promotion promotion-id"
+        );
+    }
 }
@@ -0,0 +1,126 @@
+#![cfg(feature = "deploy")]
+use heroku_rs::endpoints::slugs::{self, release_slug};
+use heroku_rs::framework::transport::MemoryTransport;
+use serde_json::json;
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+mod util;
+use util::{ok, text, Routes};
+
+/// A gzipped tarball of `files`, with their paths as given.
+fn tarball(files: &[(&str, &str)]) -> Vec<u8> {
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut tar = tar::Builder::new(encoder);
+    for (path, content) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        tar.append_data(&mut header, path, content.as_bytes())
+            .unwrap();
+    }
+    tar.into_inner().unwrap().finish().unwrap()
+}
+
+/// The Platform API and a blob store. Uploaded slugs are kept in `store`.
+fn platform(store: Arc<Mutex<Vec<u8>>>) -> Arc<MemoryTransport> {
+    Routes::new()
+        .post("/apps/app-id/slugs", |request| {
+            let params: serde_json::Value =
+                serde_json::from_slice(request.body.as_ref().unwrap()).unwrap();
+            ok(json!({
+                "blob": {"method": "put", "url": "https://blobs.example.com/slug"},
+                "buildpack_provided_description": null,
+                "checksum": params["checksum"],
+                "commit": null,
+                "commit_description": null,
+                "created_at": "2020-01-01T12:00:00Z",
+                "id": "slug-id",
+                "process_types": params["process_types"],
+                "size": null,
+                "stack": {"id": "stack-id", "name": params["stack"]},
+                "updated_at": "2020-01-01T12:00:00Z",
+            }))
+        })
+        .put("/slug", move |request| {
+            *store.lock().unwrap() = request.body.clone().unwrap();
+            text("")
+        })
+        .post("/apps/app-id/releases", |request| {
+            let params: serde_json::Value =
+                serde_json::from_slice(request.body.as_ref().unwrap()).unwrap();
+            assert_eq!(params["slug"], "slug-id");
+            ok(json!({
+                "addon_plan_names": [],
+                "app": {"id": "app-id", "name": "example"},
+                "created_at": "2020-01-01T12:00:00Z",
+                "current": true,
+                "description": "Deploy slug-id",
+                "id": "release-id",
+                "output_stream_url": null,
+                "slug": {"id": "slug-id"},
+                "status": "succeeded",
+                "updated_at": "2020-01-01T12:00:00Z",
+                "user": {"id": "user-id", "email": "user@example.com"},
+                "version": 8,
+            }))
+        })
+        .transport()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn releases_slug() {
+        let slug = tarball(&[("./app/bin/web", "#!/bin/sh\n"), ("./app/Procfile", "")]);
+        let store = Arc::new(Mutex::new(Vec::new()));
+        let transport = platform(store.clone());
+        let client = util::memory_client(transport.clone());
+        let mut process_types = HashMap::new();
+        process_types.insert("web", "./bin/web -p $PORT");
+
+        let released =
+            release_slug(&client, "app-id", &slug, process_types, Some("heroku-20")).unwrap();
+
+        assert_eq!(released.release.version, 8);
+        assert_eq!(released.slug.stack.name, "heroku-20");
+        assert_eq!(*store.lock().unwrap(), slug);
+        assert_eq!(
+            released.slug.checksum,
+            Some(slugs::release::checksum(&slug))
+        );
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1].headers.get("Authorization"), None);
+    }
+
+    #[test]
+    fn computes_checksum() {
+        assert_eq!(
+            slugs::release::checksum(b""),
+            "SHA256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_slugs() {
+        let validate = slugs::release::validate_slug;
+
+        assert!(validate(&tarball(&[("app/bin/web", "")])).is_ok());
+        let error = validate(&tarball(&[("./app/bin/web", ""), ("./bin/web", "")])).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("bin/web"));
+        assert!(validate(&tarball(&[])).is_err());
+        assert!(validate(b"not a tarball").is_err());
+
+        let transport = Arc::new(MemoryTransport::responding(http::StatusCode::OK, "{}"));
+        let client = util::memory_client(transport.clone());
+        let slug = tarball(&[("web", "")]);
+        assert!(release_slug(&client, "app-id", &slug, HashMap::new(), None).is_err());
+        assert!(transport.requests().is_empty());
+    }
+}
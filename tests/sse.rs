@@ -0,0 +1,148 @@
+use heroku_rs::endpoints::apps;
+use heroku_rs::framework::{
+    response::HerokuApiFailure,
+    sse::EventStream,
+    transport::{HttpRequest, HttpResponse, MemoryTransport},
+};
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const OUTPUT_STREAM_URL: &str = "https://build-output.heroku.com/streams/01234567";
+
+mod util;
+
+/// A body which drops the connection once its data has been read.
+struct Disconnecting(io::Cursor<Vec<u8>>);
+
+impl Read for Disconnecting {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.0.read(buf)? {
+            0 => Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset")),
+            read => Ok(read),
+        }
+    }
+}
+
+fn header(request: &HttpRequest, name: &str) -> Option<String> {
+    request
+        .headers
+        .get(name)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heroku_rs::framework::sse::Event;
+
+    #[test]
+    fn resumes_with_last_event_id() {
+        let calls = AtomicUsize::new(0);
+        let transport = Arc::new(MemoryTransport::new(move |_| {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => HttpResponse::new(
+                    http::StatusCode::OK,
+                    http::HeaderMap::new(),
+                    Disconnecting(io::Cursor::new(
+                        b"retry: 0\nid: 1\ndata: -----> Building\n\nid: 2\ndata: par".to_vec(),
+                    )),
+                ),
+                1 => HttpResponse::from_bytes(
+                    http::StatusCode::OK,
+                    "id: 2\ndata: partial, sent again\n\n",
+                ),
+                _ => HttpResponse::from_bytes(
+                    http::StatusCode::OK,
+                    "event: done\nid: 3\ndata: {\"status\": \"succeeded\"}\n\n",
+                ),
+            }
+        }));
+        let client = util::memory_client(transport.clone());
+        let mut polls = 0;
+
+        let events: Vec<Event> = EventStream::new(&client, OUTPUT_STREAM_URL)
+            .until(|| {
+                polls += 1;
+                Ok(polls > 1)
+            })
+            .map(Result::unwrap)
+            .collect();
+
+        let data: Vec<&str> = events.iter().map(|event| event.data.as_str()).collect();
+        assert_eq!(
+            data,
+            vec![
+                "-----> Building",
+                "partial, sent again",
+                "{\"status\": \"succeeded\"}"
+            ]
+        );
+        assert_eq!(events[2].event, "done");
+        let status: serde_json::Value = events[2].json().unwrap();
+        assert_eq!(status["status"], "succeeded");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            header(&requests[0], "Accept").as_deref(),
+            Some("text/event-stream")
+        );
+        assert_eq!(header(&requests[0], "Last-Event-Id"), None);
+        assert_eq!(header(&requests[0], "Authorization"), None);
+        assert_eq!(header(&requests[1], "Last-Event-Id").as_deref(), Some("1"));
+        assert_eq!(header(&requests[2], "Last-Event-Id").as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn gives_up_on_invalid_urls() {
+        let transport = Arc::new(MemoryTransport::responding(http::StatusCode::OK, ""));
+        let client = util::memory_client(transport.clone());
+
+        let mut events =
+            EventStream::new(&client, "not a url").retry_delay(Duration::from_millis(0));
+
+        match events.next() {
+            Some(Err(HerokuApiFailure::Parse(_))) => {}
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+        assert!(events.next().is_none());
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn streams_endpoint_events() {
+        let transport = Arc::new(MemoryTransport::new(|request: &HttpRequest| {
+            match header(request, "Last-Event-Id") {
+                None => HttpResponse::from_bytes(http::StatusCode::OK, "id: 9\ndata: hello\n\n"),
+                Some(_) => HttpResponse::from_bytes(http::StatusCode::NO_CONTENT, ""),
+            }
+        }));
+        let client = util::memory_client(transport.clone());
+
+        let events: Vec<Event> = client
+            .request_events(&apps::AppDetails { app_id: "123xyz" })
+            .unwrap()
+            .retry_delay(Duration::from_millis(0))
+            .until(|| Ok(false))
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(events.len(), 1);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].url.as_str(),
+            "https://api.heroku.com/apps/123xyz"
+        );
+        assert_eq!(
+            header(&requests[0], "Accept").as_deref(),
+            Some("text/event-stream")
+        );
+        assert_eq!(
+            header(&requests[1], "Authorization").as_deref(),
+            Some("Bearer TOKEN_HERE")
+        );
+    }
+}
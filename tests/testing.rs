@@ -0,0 +1,231 @@
+use heroku_rs::endpoints::testing::{report, NodeStream, TestCase, TestRunFollower};
+use heroku_rs::framework::transport::MemoryTransport;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use util::{ok, text, Routes};
+
+mod util;
+
+fn test_run(status: &str) -> serde_json::Value {
+    json!({
+        "actor_email": "user@example.com",
+        "clear_cache": false,
+        "commit_branch": "main",
+        "commit_message": "Fix login",
+        "commit_sha": "2d5a5a5",
+        "debug": false,
+        "app_setup": null,
+        "created_at": "2020-01-01T12:00:00Z",
+        "dyno": {"size": "standard-1X"},
+        "id": "run-id",
+        "message": null,
+        "number": 42,
+        "organization": null,
+        "pipeline": {"id": "pipeline-id"},
+        "status": status,
+        "source_blob_url": "https://example.com/source.tgz",
+        "updated_at": "2020-01-01T12:00:00Z",
+        "user": {"id": "user-id"},
+        "warning_message": null,
+    })
+}
+
+fn test_node(index: i64, status: &str) -> serde_json::Value {
+    json!({
+        "created_at": "2020-01-01T12:00:00Z",
+        "dyno": null,
+        "error_status": null,
+        "exit_code": null,
+        "id": format!("node-{}", index),
+        "index": index,
+        "message": null,
+        "output_stream_url": "https://streams.example.com/output",
+        "pipeline": {"id": "pipeline-id"},
+        "setup_stream_url": "https://streams.example.com/setup",
+        "status": status,
+        "updated_at": "2020-01-01T12:00:00Z",
+        "test_run": {"id": "run-id"},
+    })
+}
+
+fn test_case(
+    number: i64,
+    description: &str,
+    passed: bool,
+    directive: &str,
+    diagnostic: &str,
+) -> TestCase {
+    serde_json::from_value(json!({
+        "id": format!("case-{}", number),
+        "created_at": "2020-01-01T12:00:00Z",
+        "updated_at": "2020-01-01T12:00:00Z",
+        "description": description,
+        "diagnostic": diagnostic,
+        "directive": directive,
+        "passed": passed,
+        "number": number,
+        "test_node": {"id": "node-id"},
+        "test_run": {"id": "run-id"},
+    }))
+    .unwrap()
+}
+
+fn cases() -> Vec<TestCase> {
+    vec![
+        test_case(
+            2,
+            "rejects <invalid> passwords",
+            false,
+            "",
+            "expected: 401\n     got: 200\n",
+        ),
+        test_case(1, "logs in", true, "", ""),
+        test_case(3, "logs in with SSO", true, "SKIP no identity provider", ""),
+    ]
+}
+
+/// A test run whose node is building during the first status check, then running during the
+/// next one, and succeeded afterwards.
+fn heroku_ci() -> Arc<MemoryTransport> {
+    let checks = AtomicUsize::new(0);
+    Routes::new()
+        .get("/test-runs/run-id/test-nodes", move |_| {
+            let status = match checks.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => "building",
+                2 => "running",
+                _ => "succeeded",
+            };
+            ok(json!([test_node(0, status)]))
+        })
+        .get("/test-runs/run-id", |_| ok(test_run("succeeded")))
+        .get("/test-runs/run-id/test-cases", |_| {
+            ok(serde_json::to_value(cases()).unwrap())
+        })
+        .get("/setup", |_| text("-----> Building on Heroku\n"))
+        .get("/output", |_| text("1..3\nok 1 - logs in\n"))
+        .transport()
+}
+
+/// A test run which is running during the first two checks, and succeeded afterwards, whose second node is
+/// only listed once the run is done.
+fn heroku_ci_late_node() -> Arc<MemoryTransport> {
+    let checks = Arc::new(AtomicUsize::new(0));
+    let listed = checks.clone();
+    Routes::new()
+        .get("/test-runs/run-id", move |_| {
+            match checks.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => ok(test_run("running")),
+                _ => ok(test_run("succeeded")),
+            }
+        })
+        .get("/test-runs/run-id/test-nodes", move |_| {
+            match listed.load(Ordering::SeqCst) {
+                0..=2 => ok(json!([test_node(0, "succeeded")])),
+                _ => ok(json!([
+                    test_node(1, "succeeded"),
+                    test_node(0, "succeeded")
+                ])),
+            }
+        })
+        .get("/test-runs/run-id/test-cases", |_| ok(json!([])))
+        .get("/setup", |_| text("-----> Building\n"))
+        .get("/output", |_| text("1..0\n"))
+        .transport()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_test_run() {
+        let transport = heroku_ci();
+        let client = util::memory_client(transport);
+        let mut lines = Vec::new();
+
+        let result = TestRunFollower::new(&client, "run-id")
+            .poll_interval(Duration::from_millis(0))
+            .follow(|node, stream, line| lines.push((node.index, stream, line.to_string())))
+            .unwrap();
+
+        assert!(result.succeeded());
+        assert_eq!(result.cases.len(), 3);
+        assert_eq!(
+            lines[0],
+            (
+                0,
+                NodeStream::Setup,
+                String::from("-----> Building on Heroku")
+            )
+        );
+        assert!(lines.contains(&(0, NodeStream::Output, String::from("ok 1 - logs in"))));
+        assert!(result.junit().contains(
+            "<testsuites name=\"pipeline-id #42\" tests=\"3\" failures=\"1\" skipped=\"1\">"
+        ));
+    }
+
+    #[test]
+    fn follows_nodes_listed_later() {
+        let client = util::memory_client(heroku_ci_late_node());
+        let mut lines = Vec::new();
+
+        let result = TestRunFollower::new(&client, "run-id")
+            .poll_interval(Duration::from_millis(0))
+            .follow(|node, stream, _| lines.push((node.index, stream)))
+            .unwrap();
+
+        assert!(result.succeeded());
+        assert_eq!(result.nodes.len(), 2);
+        assert_eq!(
+            lines,
+            vec![
+                (0, NodeStream::Setup),
+                (0, NodeStream::Output),
+                (1, NodeStream::Setup),
+                (1, NodeStream::Output),
+            ]
+        );
+    }
+
+    #[test]
+    fn exports_junit() {
+        let xml = report::junit("heroku-ci", &cases());
+
+        assert_eq!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="heroku-ci" tests="3" failures="1" skipped="1">
+  <testsuite name="heroku-ci" tests="3" failures="1" skipped="1">
+    <testcase name="logs in" classname="heroku-ci"/>
+    <testcase name="rejects &lt;invalid&gt; passwords" classname="heroku-ci">
+      <failure message="expected: 401">expected: 401
+     got: 200
+</failure>
+    </testcase>
+    <testcase name="logs in with SSO" classname="heroku-ci">
+      <skipped message="SKIP no identity provider"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn exports_tap() {
+        let tap = report::tap(&cases());
+
+        assert_eq!(
+            tap,
+            "1..3
+ok 1 - logs in
+not ok 2 - rejects <invalid> passwords
+# expected: 401
+#      got: 200
+ok 3 - logs in with SSO # SKIP no identity provider
+"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use heroku_rs::endpoints::{apps, custom};
+use heroku_rs::framework::endpoint::Method;
+use heroku_rs::framework::{
+    auth::Credentials,
+    response::HerokuApiFailure,
+    transport::{HttpResponse, MemoryTransport},
+    ApiEnvironment, HttpApiClient,
+};
+use std::sync::Arc;
+#[cfg(feature = "ureq")]
+use util::assert_valid_url;
+mod util;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heroku_rs::framework::apiclient::HerokuApiClient;
+
+    #[test]
+    fn sends_prepared_request() {
+        let transport = Arc::new(MemoryTransport::responding(
+            http::StatusCode::OK,
+            r#"{"name": "foo"}"#,
+        ));
+        let _ = util::memory_client(transport.clone())
+            .request(&apps::AppUpdate::new("123xyz").name("foo").build());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        assert_eq!(request.method, http::Method::PATCH);
+        assert_eq!(request.url.as_str(), "https://api.heroku.com/apps/123xyz");
+        assert_eq!(
+            request.headers[http::header::ACCEPT],
+            "application/vnd.heroku+json; version=3"
+        );
+        assert_eq!(
+            request.headers[http::header::CONTENT_TYPE],
+            "application/json"
+        );
+        assert_eq!(
+            request.headers[http::header::AUTHORIZATION],
+            "Bearer TOKEN_HERE"
+        );
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["name"], "foo");
+    }
+
+    #[test]
+    fn parses_error_response() {
+        let transport = Arc::new(MemoryTransport::responding(
+            http::StatusCode::NOT_FOUND,
+            r#"{"id": "not_found", "message": "Couldn't find that app."}"#,
+        ));
+        let response = util::memory_client(transport).request(&apps::AppDetails::new("123xyz"));
+
+        match response {
+            Err(HerokuApiFailure::Error(status, error)) => {
+                assert_eq!(status, http::StatusCode::NOT_FOUND);
+                assert_eq!(error.id, "not_found");
+            }
+            other => panic!("Expected a not found error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn raw_response_keeps_headers_and_body() {
+        let transport = Arc::new(MemoryTransport::new(|_| {
+            let mut headers = http::HeaderMap::new();
+            headers.insert("RateLimit-Remaining", "42".parse().unwrap());
+            HttpResponse::new(http::StatusCode::OK, headers, "[]".as_bytes())
+        }));
+        let response = util::memory_client(transport)
+            .request_raw(&apps::AppList::new())
+            .unwrap();
+
+        assert_eq!(response.status, http::StatusCode::OK);
+        assert_eq!(response.headers["RateLimit-Remaining"], "42");
+        assert_eq!(response.text().unwrap(), "[]");
+    }
+
+    #[test]
+    fn invalid_headers_fail_the_request() {
+        let transport = Arc::new(MemoryTransport::responding(http::StatusCode::OK, "[]"));
+        let endpoint = custom::CustomEndpointTyped::<Vec<apps::App>>::new(Method::Get, "apps")
+            .variant("pipelines\n")
+            .build();
+
+        let response = util::memory_client(transport.clone()).request(&endpoint);
+
+        match response {
+            Err(HerokuApiFailure::Invalid(e)) => {
+                assert!(e.to_string().contains("invalid header value"))
+            }
+            other => panic!("Expected an invalid request, got {:?}", other),
+        }
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn invalid_credentials_fail_the_request() {
+        let transport = Arc::new(MemoryTransport::responding(http::StatusCode::OK, "[]"));
+        let client = HttpApiClient::with_transport(
+            Credentials::UserAuthToken {
+                token: String::from("TOKEN\nHERE"),
+            },
+            ApiEnvironment::Production,
+            transport.clone(),
+        );
+
+        match client.request(&apps::AppList::new()) {
+            Err(HerokuApiFailure::Invalid(e)) => {
+                let message = e.to_string();
+                assert!(message.contains("invalid Authorization header value"));
+                assert!(!message.contains("TOKEN"));
+            }
+            other => panic!("Expected an invalid request, got {:?}", other),
+        }
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn credentials_replace_endpoint_authorization() {
+        let transport = Arc::new(MemoryTransport::responding(http::StatusCode::OK, "[]"));
+        let endpoint = custom::CustomEndpointTyped::<Vec<apps::App>>::new(Method::Get, "apps")
+            .header(
+                http::header::AUTHORIZATION,
+                http::HeaderValue::from_static("Bearer OTHER"),
+            )
+            .build();
+
+        util::memory_client(transport.clone())
+            .request(&endpoint)
+            .unwrap();
+
+        let requests = transport.requests();
+        let authorization: Vec<_> = requests[0]
+            .headers
+            .get_all(http::header::AUTHORIZATION)
+            .iter()
+            .collect();
+        assert_eq!(authorization, vec!["Bearer TOKEN_HERE"]);
+    }
+
+    #[cfg(feature = "ureq")]
+    #[test]
+    fn assert_valid_url_ureq_transport() {
+        use heroku_rs::framework::{transport::UreqTransport, HttpApiClientConfig};
+
+        let credentials = Credentials::UserAuthToken {
+            token: String::from("TOKEN_HERE"),
+        };
+        let client = HttpApiClient::with_transport(
+            credentials,
+            ApiEnvironment::Custom(url::Url::parse(util::TEST_ENDPOINT).unwrap()),
+            UreqTransport::new(&HttpApiClientConfig::default()),
+        );
+        let response = client.request(&apps::AppList::new());
+        assert_valid_url(response, String::from("apps"))
+    }
+}
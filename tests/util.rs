@@ -1,10 +1,14 @@
+// Shared by the test crates, which each use only some of the helpers.
+#![allow(dead_code)]
 extern crate heroku_rs;
 
 use heroku_rs::framework::{
     auth::Credentials,
     response::{ApiResponse, ApiResult},
+    transport::{HttpRequest, HttpResponse, MemoryTransport},
     ApiEnvironment, HttpApiClient, HttpApiClientConfig,
 };
+use std::sync::Arc;
 use std::time::Duration;
 
 pub const INVALID_ENDPOINT: &'static str =
@@ -28,6 +32,95 @@ pub fn get_client() -> HttpApiClient {
     api_client
 }
 
+/// A client whose requests are answered by the given in-memory transport.
+pub fn memory_client(transport: Arc<MemoryTransport>) -> HttpApiClient {
+    let credentials = Credentials::UserAuthToken {
+        token: String::from("TOKEN_HERE"),
+    };
+    HttpApiClient::with_transport(credentials, ApiEnvironment::Production, transport)
+}
+
+/// A 200 response with the given JSON body.
+pub fn ok(body: serde_json::Value) -> HttpResponse {
+    HttpResponse::from_bytes(http::StatusCode::OK, body.to_string())
+}
+
+/// A 200 response with the given text, e.g. a streamed output.
+pub fn text(body: &str) -> HttpResponse {
+    HttpResponse::from_bytes(http::StatusCode::OK, body)
+}
+
+type Handler = Box<dyn Fn(&HttpRequest) -> HttpResponse + Send + Sync>;
+
+/// A route table for an in-memory transport: each request is answered by the handler of its method and path,
+/// and any other request panics.
+#[derive(Default)]
+pub struct Routes(Vec<(http::Method, &'static str, Handler)>);
+
+impl Routes {
+    pub fn new() -> Routes {
+        Routes::default()
+    }
+
+    /// Answer `method` requests of `path` with `handler`.
+    pub fn route<F>(mut self, method: http::Method, path: &'static str, handler: F) -> Routes
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        self.0.push((method, path, Box::new(handler)));
+        self
+    }
+
+    pub fn get<F>(self, path: &'static str, handler: F) -> Routes
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        self.route(http::Method::GET, path, handler)
+    }
+
+    pub fn post<F>(self, path: &'static str, handler: F) -> Routes
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        self.route(http::Method::POST, path, handler)
+    }
+
+    pub fn put<F>(self, path: &'static str, handler: F) -> Routes
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        self.route(http::Method::PUT, path, handler)
+    }
+
+    pub fn patch<F>(self, path: &'static str, handler: F) -> Routes
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        self.route(http::Method::PATCH, path, handler)
+    }
+
+    pub fn delete<F>(self, path: &'static str, handler: F) -> Routes
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        self.route(http::Method::DELETE, path, handler)
+    }
+
+    pub fn transport(self) -> Arc<MemoryTransport> {
+        Arc::new(MemoryTransport::new(move |request: &HttpRequest| {
+            let path = request.url.path();
+            match self
+                .0
+                .iter()
+                .find(|(method, route, _)| *method == request.method && *route == path)
+            {
+                Some((_, _, handler)) => handler(request),
+                None => panic!("unexpected request {} {}", request.method, path),
+            }
+        }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,127 @@
+use heroku_rs::endpoints::{apps, builds};
+use heroku_rs::framework::{
+    response::HerokuApiFailure,
+    transport::{HttpResponse, MemoryTransport},
+    waiter::{WaitError, Waiter},
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod util;
+
+fn build(status: &str) -> serde_json::Value {
+    json!({
+        "app": {"id": "app-id"},
+        "buildpacks": null,
+        "created_at": "2020-01-01T12:00:00Z",
+        "id": "build-id",
+        "output_stream_url": "https://build-output.heroku.com/streams/build-id",
+        "source_blob": {"checksum": null, "url": "https://example.com/source.tgz", "version": null},
+        "release": null,
+        "slug": null,
+        "stack": "heroku-20",
+        "status": status,
+        "updated_at": "2020-01-01T12:00:00Z",
+        "user": {"id": "user-id", "email": "user@example.com"},
+    })
+}
+
+/// A build which is pending during the first `pending` requests, and succeeded afterwards.
+fn builds(pending: usize) -> Arc<MemoryTransport> {
+    let requests = AtomicUsize::new(0);
+    Arc::new(MemoryTransport::new(move |_| {
+        let status = match requests.fetch_add(1, Ordering::SeqCst) < pending {
+            true => "pending",
+            false => "succeeded",
+        };
+        HttpResponse::from_bytes(http::StatusCode::OK, build(status).to_string())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DETAILS: builds::BuildDetails = builds::BuildDetails {
+        app_id: "app-id",
+        build_id: "build-id",
+    };
+
+    #[test]
+    fn waits_until_done() {
+        let transport = builds(2);
+        let client = util::memory_client(transport.clone());
+        let mut progress = Vec::new();
+
+        let build = Waiter::for_build(&client, &DETAILS)
+            .poll_interval(Duration::from_millis(0))
+            .on_progress(|build| progress.push(build.status.clone()))
+            .wait()
+            .unwrap();
+
+        assert_eq!(build.status, "succeeded");
+        assert_eq!(progress, vec!["pending", "pending", "succeeded"]);
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[test]
+    fn times_out_with_last_state() {
+        let transport = builds(usize::MAX);
+        let client = util::memory_client(transport.clone());
+
+        let result = Waiter::for_build(&client, &DETAILS)
+            .poll_interval(Duration::from_millis(5))
+            .backoff(2.0, Duration::from_millis(20))
+            .timeout(Duration::from_millis(60))
+            .wait();
+
+        match result {
+            Err(WaitError::TimedOut(build)) => assert_eq!(build.status, "pending"),
+            other => panic!("Expected a timeout, got {:?}", other),
+        }
+        // 5, 10, 20, 20 then 5 ms
+        assert!(transport.requests().len() <= 6);
+    }
+
+    #[test]
+    fn stops_when_cancelled() {
+        let transport = builds(usize::MAX);
+        let client = util::memory_client(transport);
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancelled = cancel.clone();
+
+        let result = Waiter::for_build(&client, &DETAILS)
+            .poll_interval(Duration::from_secs(60))
+            .cancel(cancel)
+            .on_progress(move |_| cancelled.store(true, Ordering::SeqCst))
+            .wait();
+
+        match result {
+            Err(WaitError::Cancelled(Some(build))) => assert_eq!(build.status, "pending"),
+            other => panic!("Expected a cancellation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn fails_on_request_errors() {
+        let transport = Arc::new(MemoryTransport::responding(
+            http::StatusCode::NOT_FOUND,
+            r#"{"id": "not_found", "message": "Couldn't find that app setup."}"#,
+        ));
+        let client = util::memory_client(transport);
+        let details = apps::AppSetupDetails {
+            setup_id: "setup-id",
+        };
+
+        let result = Waiter::for_app_setup(&client, &details).wait();
+
+        match result {
+            Err(WaitError::Api(HerokuApiFailure::Error(status, _))) => {
+                assert_eq!(status, http::StatusCode::NOT_FOUND)
+            }
+            other => panic!("Expected a not found error, got {:?}", other),
+        }
+    }
+}
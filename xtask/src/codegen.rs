@@ -0,0 +1,464 @@
+//! Emitting endpoint modules in the layout of `src/endpoints`: models in `mod.rs`, endpoints split by HTTP method.
+use crate::schema::{Kind, Link, Property, Resource, Target};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const HEADER: &str = "// Generated from Heroku's platform API schema by `cargo xtask codegen`. Do not edit by hand.\n";
+const METHODS: [(&str, &str); 5] = [
+    ("DELETE", "delete"),
+    ("GET", "get"),
+    ("PATCH", "patch"),
+    ("POST", "post"),
+    ("PUT", "put"),
+];
+
+/// A generated module, as file names relative to the module directory and their contents.
+pub struct Module {
+    pub name: String,
+    pub files: BTreeMap<String, String>,
+}
+
+/// `app-webhook` becomes `AppWebhook`.
+pub fn type_name(name: &str) -> String {
+    name.split(['-', '_', ' '])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// `app-webhook` becomes `app_webhook`.
+pub fn module_name(resource: &str) -> String {
+    resource.replace('-', "_")
+}
+
+/// The name of the endpoint struct, following the naming convention of the crate.
+pub fn endpoint_name(resource: &Resource, link: &Link) -> String {
+    let action = match link.title.as_str() {
+        "Info" => String::from("Details"),
+        title => type_name(title),
+    };
+    format!("{}{}", type_name(&resource.id), action)
+}
+
+fn field_name(name: &str) -> String {
+    match name {
+        "type" | "ref" | "use" | "match" | "override" => format!("r#{}", name),
+        name => name.replace('-', "_"),
+    }
+}
+
+fn doc_anchor(resource: &Resource, link: &Link) -> String {
+    format!(
+        "{}-{}",
+        resource.id,
+        link.title.to_lowercase().replace(' ', "-")
+    )
+}
+
+fn needs_lifetime(properties: &[Property]) -> bool {
+    fn kind_needs_lifetime(kind: &Kind) -> bool {
+        match kind {
+            Kind::String | Kind::DateTime => true,
+            Kind::Object(properties) => needs_lifetime(properties),
+            Kind::Array(kind) => kind_needs_lifetime(kind),
+            _ => false,
+        }
+    }
+    properties
+        .iter()
+        .any(|property| kind_needs_lifetime(&property.kind))
+}
+
+fn uses_date_time(properties: &[Property]) -> bool {
+    fn kind_uses_date_time(kind: &Kind) -> bool {
+        match kind {
+            Kind::DateTime => true,
+            Kind::Object(properties) => uses_date_time(properties),
+            Kind::Array(kind) => kind_uses_date_time(kind),
+            _ => false,
+        }
+    }
+    properties
+        .iter()
+        .any(|property| kind_uses_date_time(&property.kind))
+}
+
+/// Structs emitted so far in one file, so that nested objects with the same name are only emitted once.
+#[derive(Default)]
+struct Structs {
+    names: Vec<String>,
+    code: String,
+}
+
+impl Structs {
+    /// Emit a response struct and its nested structs, and return its name.
+    fn model(&mut self, name: &str, doc: &str, properties: &[Property]) -> String {
+        let name = self.unique(name);
+        let mut fields = String::new();
+        for property in properties {
+            let mut rust_type = self.model_type(&property.name, &property.kind);
+            if property.nullable {
+                rust_type = format!("Option<{}>", rust_type);
+            }
+            write_field(&mut fields, property, &rust_type);
+        }
+        write!(
+            self.code,
+            "\n{}#[derive(Deserialize, Serialize, Debug, Clone)]\npub struct {} {{\n{}}}\n",
+            doc, name, fields
+        )
+        .unwrap();
+        name
+    }
+
+    fn model_type(&mut self, name: &str, kind: &Kind) -> String {
+        match kind {
+            Kind::String => String::from("String"),
+            Kind::DateTime => String::from("DateTime<Utc>"),
+            Kind::Integer => String::from("i64"),
+            Kind::Number => String::from("f64"),
+            Kind::Boolean => String::from("bool"),
+            Kind::Any => String::from("serde_json::Value"),
+            Kind::Array(kind) => format!("Vec<{}>", self.model_type(name, kind)),
+            Kind::Object(properties) => {
+                let doc = format!("/// {}\n", type_name(name));
+                self.model(&type_name(name), &doc, properties)
+            }
+        }
+    }
+
+    /// Emit a parameters struct and its nested structs, and return its name including the lifetime.
+    fn params(&mut self, name: &str, doc: &str, properties: &[Property]) -> String {
+        let name = self.unique(name);
+        let lifetime = if needs_lifetime(properties) {
+            "<'a>"
+        } else {
+            ""
+        };
+        let mut fields = String::new();
+        for property in properties {
+            let mut rust_type = self.param_type(&property.name, &property.kind);
+            if !property.required || property.nullable {
+                rust_type = format!("Option<{}>", rust_type);
+            }
+            write_field(&mut fields, property, &rust_type);
+        }
+        write!(
+            self.code,
+            "\n{}#[serde_with::skip_serializing_none]\n#[derive(Serialize, Clone, Debug)]\npub struct {}{} {{\n{}}}\n",
+            doc, name, lifetime, fields
+        )
+        .unwrap();
+        format!("{}{}", name, lifetime)
+    }
+
+    fn param_type(&mut self, name: &str, kind: &Kind) -> String {
+        match kind {
+            Kind::String | Kind::DateTime => String::from("&'a str"),
+            Kind::Integer => String::from("i64"),
+            Kind::Number => String::from("f64"),
+            Kind::Boolean => String::from("bool"),
+            Kind::Any => String::from("serde_json::Value"),
+            Kind::Array(kind) => format!("Vec<{}>", self.param_type(name, kind)),
+            Kind::Object(properties) => {
+                let doc = format!("/// {} parameters\n", type_name(name));
+                self.params(&format!("{}Param", type_name(name)), &doc, properties)
+            }
+        }
+    }
+
+    fn unique(&mut self, name: &str) -> String {
+        let mut unique = name.to_string();
+        let mut counter = 2;
+        while self.names.contains(&unique) {
+            unique = format!("{}{}", name, counter);
+            counter += 1;
+        }
+        self.names.push(unique.clone());
+        unique
+    }
+}
+
+fn write_field(fields: &mut String, property: &Property, rust_type: &str) {
+    if !property.description.is_empty() {
+        writeln!(
+            fields,
+            "    /// {}",
+            property.description.replace('\n', " ")
+        )
+        .unwrap();
+    }
+    let name = field_name(&property.name);
+    if name != property.name && !name.starts_with("r#") {
+        writeln!(fields, "    #[serde(rename = \"{}\")]", property.name).unwrap();
+    }
+    writeln!(fields, "    pub {}: {},", name, rust_type).unwrap();
+}
+
+/// Generate the module of a single resource.
+pub fn generate(resource: &Resource) -> Module {
+    let model = type_name(&resource.id);
+    let mut files = BTreeMap::new();
+    let mut exports: Vec<(String, Vec<String>)> = Vec::new();
+
+    for (method, file) in METHODS.iter() {
+        let links: Vec<&Link> = resource
+            .links
+            .iter()
+            .filter(|link| link.method == *method)
+            .collect();
+        if links.is_empty() {
+            continue;
+        }
+
+        let mut structs = Structs::default();
+        let mut names = Vec::new();
+        for link in links {
+            names.extend(endpoint(&mut structs, resource, link, &model));
+        }
+
+        let mut code = String::from(HEADER);
+        writeln!(
+            code,
+            "use super::*;\n\nuse crate::framework::endpoint::{{HerokuEndpoint, Method}};"
+        )
+        .unwrap();
+        code.push_str(&structs.code);
+        files.insert(format!("{}.rs", file), code);
+        exports.push((file.to_string(), names));
+    }
+
+    let mut structs = Structs::default();
+    let doc = format!(
+        "/// {}\n///\n/// Stability: {}\n///\n/// {}\n///\n/// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#{})\n",
+        model, resource.stability, resource.description, resource.id
+    );
+    structs.model(&model, &doc, &resource.properties);
+
+    let mut code = String::from(HEADER);
+    code.push_str("use crate::framework::response::ApiResult;\n");
+    if uses_date_time(&resource.properties) {
+        code.push_str("use chrono::offset::Utc;\nuse chrono::DateTime;\n");
+    }
+    code.push('\n');
+    for (file, _) in &exports {
+        writeln!(code, "pub mod {};", file).unwrap();
+    }
+    code.push('\n');
+    for (file, names) in &exports {
+        writeln!(code, "pub use {}::{{{}}};", file, names.join(", ")).unwrap();
+    }
+    writeln!(code, "\nimpl ApiResult for {} {{}}", model).unwrap();
+    code.push_str(&structs.code);
+    files.insert(String::from("mod.rs"), code);
+
+    Module {
+        name: module_name(&resource.id),
+        files,
+    }
+}
+
+/// Emit one endpoint, its parameters and its builder. Returns the public names to re-export.
+fn endpoint(structs: &mut Structs, resource: &Resource, link: &Link, model: &str) -> Vec<String> {
+    let name = endpoint_name(resource, link);
+    let mut exports = vec![name.clone()];
+
+    let params = if link.params.is_empty() {
+        None
+    } else {
+        let doc = format!(
+            "/// {} parameters\n///\n/// [See Heroku documentation for more information about these parameters](https://devcenter.heroku.com/articles/platform-api-reference#{}-optional-parameters)\n",
+            name,
+            doc_anchor(resource, link)
+        );
+        let params = structs.params(&format!("{}Params", name), &doc, &link.params);
+        exports.push(format!("{}Params", name));
+        Some(params)
+    };
+
+    let lifetime = !link.path_params.is_empty()
+        || params
+            .as_ref()
+            .map(|params| params.ends_with("<'a>"))
+            .unwrap_or(false);
+    let generics = if lifetime { "<'a>" } else { "" };
+    let result = match link.target {
+        Target::List => format!("Vec<{}>", model),
+        Target::Resource => model.to_string(),
+    };
+
+    let mut code = String::new();
+    writeln!(
+        code,
+        "\n/// {} {}\n///\n/// {}\n///\n/// [See Heroku documentation for more information about this endpoint](https://devcenter.heroku.com/articles/platform-api-reference#{})",
+        type_name(&resource.id),
+        link.title,
+        link.description,
+        doc_anchor(resource, link)
+    )
+    .unwrap();
+    writeln!(code, "pub struct {}{} {{", name, generics).unwrap();
+    for param in &link.path_params {
+        writeln!(
+            code,
+            "    /// unique identifier or name\n    pub {}: &'a str,",
+            param
+        )
+        .unwrap();
+    }
+    if let Some(params) = &params {
+        writeln!(
+            code,
+            "    /// The parameters to pass to the Heroku API\n    pub params: {},",
+            params
+        )
+        .unwrap();
+    }
+    code.push_str("}\n");
+
+    // Builder
+    let required: Vec<&Property> = link.params.iter().filter(|p| p.required).collect();
+    let optional: Vec<&Property> = link.params.iter().filter(|p| !p.required).collect();
+    let mut arguments: Vec<String> = link
+        .path_params
+        .iter()
+        .map(|param| format!("{}: &'a str", param))
+        .collect();
+    let mut builder = Structs::default();
+    for property in &required {
+        let rust_type = param_type_name(&mut builder, property);
+        arguments.push(format!("{}: {}", field_name(&property.name), rust_type));
+    }
+
+    writeln!(
+        code,
+        "\n#[cfg(feature = \"builder\")]\nimpl{} {}{} {{",
+        generics, name, generics
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "    pub fn new({}) -> {}{} {{",
+        arguments.join(", "),
+        name,
+        generics
+    )
+    .unwrap();
+    writeln!(code, "        {} {{", name).unwrap();
+    for param in &link.path_params {
+        writeln!(code, "            {},", param).unwrap();
+    }
+    if let Some(params) = &params {
+        let params_name = params.trim_end_matches("<'a>");
+        writeln!(code, "            params: {} {{", params_name).unwrap();
+        for property in &link.params {
+            let field = field_name(&property.name);
+            if property.required && !property.nullable {
+                writeln!(code, "                {},", field).unwrap();
+            } else if property.required {
+                writeln!(code, "                {}: Some({}),", field, field).unwrap();
+            } else {
+                writeln!(code, "                {}: None,", field).unwrap();
+            }
+        }
+        code.push_str("            },\n");
+    }
+    code.push_str("        }\n    }\n");
+
+    for property in &optional {
+        let field = field_name(&property.name);
+        let setter = field.trim_start_matches("r#");
+        let rust_type = param_type_name(&mut builder, property);
+        writeln!(
+            code,
+            "\n    /// # {}: {}\n    pub fn {}(&mut self, {}: {}) -> &mut Self {{\n        self.params.{} = Some({});\n        self\n    }}",
+            setter, property.description, setter, setter, rust_type, field, setter
+        )
+        .unwrap();
+    }
+    if !optional.is_empty() {
+        writeln!(
+            code,
+            "\n    pub fn build(&self) -> {}{} {{\n        {} {{",
+            name, generics, name
+        )
+        .unwrap();
+        for param in &link.path_params {
+            writeln!(code, "            {}: self.{},", param, param).unwrap();
+        }
+        code.push_str("            params: self.params.clone(),\n        }\n    }\n");
+    }
+    code.push_str("}\n");
+
+    // HerokuEndpoint
+    let body_type = params.clone().unwrap_or_else(|| String::from("()"));
+    let path_arguments: String = link
+        .path_params
+        .iter()
+        .map(|param| format!(", self.{}", param))
+        .collect();
+    writeln!(
+        code,
+        "\nimpl{} HerokuEndpoint<{}, (), {}> for {}{} {{",
+        generics, result, body_type, name, generics
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "    fn method(&self) -> Method {{\n        Method::{}\n    }}",
+        type_name(&link.method.to_lowercase())
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "    fn path(&self) -> String {{\n        format!(\"{}\"{})\n    }}",
+        link.path, path_arguments
+    )
+    .unwrap();
+    if params.is_some() {
+        writeln!(
+            code,
+            "    fn body(&self) -> Option<{}> {{\n        Some(self.params.clone())\n    }}",
+            body_type
+        )
+        .unwrap();
+    }
+    code.push_str("}\n");
+
+    // Nested parameter structs were emitted into `builder` while naming the builder arguments, they already exist.
+    structs.code.push_str(&code);
+    exports
+}
+
+/// The Rust type of a builder argument, reusing the names of the already emitted parameter structs.
+fn param_type_name(structs: &mut Structs, property: &Property) -> String {
+    match &property.kind {
+        Kind::Object(properties) if needs_lifetime(properties) => {
+            format!("{}Param<'a>", type_name(&property.name))
+        }
+        Kind::Object(_) => format!("{}Param", type_name(&property.name)),
+        Kind::Array(kind) => match kind.as_ref() {
+            Kind::Object(properties) if needs_lifetime(properties) => {
+                format!("Vec<{}Param<'a>>", type_name(&property.name))
+            }
+            Kind::Object(_) => format!("Vec<{}Param>", type_name(&property.name)),
+            _ => structs.param_type(&property.name, &property.kind),
+        },
+        _ => structs.param_type(&property.name, &property.kind),
+    }
+}
+
+/// The root `mod.rs` declaring every generated module.
+pub fn root_module(modules: &[Module]) -> String {
+    let mut code = String::from(HEADER);
+    for module in modules {
+        writeln!(code, "pub mod {};", module.name).unwrap();
+    }
+    code
+}
@@ -0,0 +1,383 @@
+//! Comparing the schema with the hand-written modules of `src/endpoints`.
+use crate::codegen::{endpoint_name, type_name};
+use crate::schema::Resource;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An endpoint implemented by hand, e.g. `PATCH apps/{}/formation/{}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoint {
+    pub name: String,
+    /// the endpoint module the endpoint lives in, e.g. `formations`
+    pub module: String,
+    pub method: String,
+    pub path: String,
+}
+
+/// A response struct written by hand, with its fields and whether they are optional.
+#[derive(Debug, Clone)]
+pub struct Model {
+    pub name: String,
+    /// the endpoint module the struct lives in, e.g. `formations`
+    pub module: String,
+    pub fields: Vec<(String, bool)>,
+}
+
+/// A difference between the schema and the hand-written modules.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+    /// the schema has an endpoint which is not implemented
+    MissingEndpoint {
+        resource: String,
+        name: String,
+        method: String,
+        path: String,
+    },
+    /// an endpoint is implemented which the schema doesn't have
+    UnknownEndpoint {
+        module: String,
+        name: String,
+        method: String,
+        path: String,
+    },
+    /// the schema has a field which the model doesn't have
+    MissingField { resource: String, field: String },
+    /// the model has a field which the schema doesn't have
+    UnknownField { resource: String, field: String },
+    /// the schema and the model disagree on whether a field can be null
+    Nullability {
+        resource: String,
+        field: String,
+        nullable: bool,
+    },
+}
+
+impl Difference {
+    /// The schema resource the difference belongs to, if any.
+    pub fn resource(&self) -> Option<&str> {
+        match self {
+            Difference::MissingEndpoint { resource, .. }
+            | Difference::MissingField { resource, .. }
+            | Difference::UnknownField { resource, .. }
+            | Difference::Nullability { resource, .. } => Some(resource),
+            Difference::UnknownEndpoint { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Difference::MissingEndpoint {
+                resource,
+                name,
+                method,
+                path,
+            } => write!(
+                f,
+                "{}: missing endpoint {} {} ({})",
+                resource, method, path, name
+            ),
+            Difference::UnknownEndpoint {
+                name, method, path, ..
+            } => {
+                write!(f, "endpoint not in schema {} {} ({})", method, path, name)
+            }
+            Difference::MissingField { resource, field } => {
+                write!(f, "{}: missing field `{}`", resource, field)
+            }
+            Difference::UnknownField { resource, field } => {
+                write!(f, "{}: field `{}` not in schema", resource, field)
+            }
+            Difference::Nullability {
+                resource,
+                field,
+                nullable: true,
+            } => write!(
+                f,
+                "{}: field `{}` is nullable but not an Option",
+                resource, field
+            ),
+            Difference::Nullability {
+                resource,
+                field,
+                nullable: false,
+            } => write!(
+                f,
+                "{}: field `{}` is an Option but never null",
+                resource, field
+            ),
+        }
+    }
+}
+
+/// Read every endpoint implemented in the `src/endpoints` directory.
+pub fn endpoints(source: &Path) -> Result<Vec<Endpoint>, String> {
+    let mut endpoints = Vec::new();
+    for module in modules(source)? {
+        for contents in files(&source.join(&module))? {
+            endpoints.extend(parse_endpoints(&module, &contents));
+        }
+    }
+    Ok(endpoints)
+}
+
+/// Read every response struct defined in the `src/endpoints` directory.
+pub fn models(source: &Path) -> Result<Vec<Model>, String> {
+    let mut models = Vec::new();
+    for module in modules(source)? {
+        for contents in files(&source.join(&module))? {
+            models.extend(parse_models(&module, &contents));
+        }
+    }
+    Ok(models)
+}
+
+fn modules(source: &Path) -> Result<Vec<String>, String> {
+    let entries =
+        fs::read_dir(source).map_err(|e| format!("could not read {}: {}", source.display(), e))?;
+    let mut modules: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    modules.sort();
+    Ok(modules)
+}
+
+/// The contents of every `.rs` file of a module, `mod.rs` first and the others by name.
+fn files(module: &Path) -> Result<Vec<String>, String> {
+    let entries =
+        fs::read_dir(module).map_err(|e| format!("could not read {}: {}", module.display(), e))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|extension| extension.to_str()) == Some("rs"))
+        .collect();
+    paths.sort_by_key(|path| (!path.ends_with("mod.rs"), path.clone()));
+    paths
+        .iter()
+        .map(|path| {
+            fs::read_to_string(path)
+                .map_err(|e| format!("could not read {}: {}", path.display(), e))
+        })
+        .collect()
+}
+
+/// Find the `impl HerokuEndpoint<..> for Name` blocks of a file, with their method and path.
+pub fn parse_endpoints(module: &str, contents: &str) -> Vec<Endpoint> {
+    let mut endpoints = Vec::new();
+    let mut rest = contents;
+
+    while let Some(start) = rest.find("HerokuEndpoint<") {
+        let block = &rest[start..];
+        let line_end = block.find('{').unwrap_or(block.len());
+        let name = block[..line_end]
+            .rsplit(" for ")
+            .next()
+            .unwrap_or("")
+            .trim()
+            .split('<')
+            .next()
+            .unwrap_or("")
+            .to_string();
+        let next = block[1..]
+            .find("HerokuEndpoint<")
+            .map(|i| i + 1)
+            .unwrap_or(block.len());
+        let body = &block[..next];
+
+        let method = body
+            .find("Method::")
+            .map(|i| {
+                body[i + 8..]
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric())
+                    .collect::<String>()
+                    .to_uppercase()
+            })
+            .unwrap_or_default();
+        let path = body
+            .find("fn path")
+            .and_then(|i| {
+                let after = &body[i..];
+                let open = after.find('"')? + 1;
+                let close = after[open..].find('"')? + open;
+                Some(after[open..close].to_string())
+            })
+            .unwrap_or_default();
+
+        // Custom endpoints take their method and path at runtime.
+        if !name.is_empty() && !method.is_empty() && !path.is_empty() && path != "{}" {
+            endpoints.push(Endpoint {
+                name,
+                module: module.to_string(),
+                method,
+                path,
+            });
+        }
+        rest = &block[next..];
+        if next == block.len() {
+            break;
+        }
+    }
+    endpoints
+}
+
+/// Find the `#[derive(Deserialize ..)] pub struct Name { .. }` blocks of a file, with their fields.
+pub fn parse_models(module: &str, contents: &str) -> Vec<Model> {
+    let mut models = Vec::new();
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut index = 0;
+
+    while index < lines.len() {
+        if !lines[index]
+            .trim_start()
+            .starts_with("#[derive(Deserialize")
+        {
+            index += 1;
+            continue;
+        }
+        while index < lines.len() && !lines[index].trim_start().starts_with("pub struct ") {
+            index += 1;
+        }
+        if index >= lines.len() {
+            break;
+        }
+        let name = lines[index]
+            .trim_start()
+            .trim_start_matches("pub struct ")
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or("")
+            .to_string();
+
+        let mut fields = Vec::new();
+        let mut rename: Option<String> = None;
+        let mut cfg = false;
+        index += 1;
+        while index < lines.len() && !lines[index].trim_start().starts_with('}') {
+            let line = lines[index].trim();
+            if line.starts_with("#[serde(rename = \"") {
+                rename = line.split('"').nth(1).map(String::from);
+            } else if line.starts_with("#[cfg(") {
+                cfg = true;
+            } else if line.starts_with("pub ") {
+                let mut parts = line.trim_start_matches("pub ").splitn(2, ':');
+                let field = parts.next().unwrap_or("").trim().trim_start_matches("r#");
+                let optional = parts.next().unwrap_or("").trim().starts_with("Option<");
+                if !cfg {
+                    fields.push((rename.take().unwrap_or_else(|| field.to_string()), optional));
+                }
+                rename = None;
+                cfg = false;
+            }
+            index += 1;
+        }
+        models.push(Model {
+            name,
+            module: module.to_string(),
+            fields,
+        });
+    }
+    models
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Compare the schema resources with the hand-written endpoints and models.
+pub fn compare(
+    resources: &[Resource],
+    endpoints: &[Endpoint],
+    models: &[Model],
+) -> Vec<Difference> {
+    let mut differences = Vec::new();
+
+    for resource in resources {
+        for link in &resource.links {
+            let implemented = endpoints
+                .iter()
+                .any(|endpoint| endpoint.method == link.method && endpoint.path == link.path);
+            if !implemented {
+                differences.push(Difference::MissingEndpoint {
+                    resource: resource.id.clone(),
+                    name: endpoint_name(resource, link),
+                    method: link.method.clone(),
+                    path: link.path.clone(),
+                });
+            }
+        }
+
+        if resource.properties.is_empty() {
+            continue;
+        }
+        // Prefer the struct of the resource's own module over nested structs of the same name.
+        let wanted = normalize(&type_name(&resource.id));
+        let model = models
+            .iter()
+            .filter(|model| normalize(&model.name) == wanted)
+            .max_by_key(|model| normalize(&model.module).starts_with(&wanted));
+        let model = match model {
+            Some(model) => model,
+            None => continue,
+        };
+
+        for property in &resource.properties {
+            match model
+                .fields
+                .iter()
+                .find(|(field, _)| *field == property.name)
+            {
+                None => differences.push(Difference::MissingField {
+                    resource: resource.id.clone(),
+                    field: property.name.clone(),
+                }),
+                Some((_, optional)) if *optional != property.nullable => {
+                    differences.push(Difference::Nullability {
+                        resource: resource.id.clone(),
+                        field: property.name.clone(),
+                        nullable: property.nullable,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for (field, _) in &model.fields {
+            if !resource
+                .properties
+                .iter()
+                .any(|property| property.name == *field)
+            {
+                differences.push(Difference::UnknownField {
+                    resource: resource.id.clone(),
+                    field: field.clone(),
+                });
+            }
+        }
+    }
+
+    for endpoint in endpoints {
+        let known = resources.iter().any(|resource| {
+            resource
+                .links
+                .iter()
+                .any(|link| link.method == endpoint.method && link.path == endpoint.path)
+        });
+        if !known {
+            differences.push(Difference::UnknownEndpoint {
+                module: endpoint.module.clone(),
+                name: endpoint.name.clone(),
+                method: endpoint.method.clone(),
+                path: endpoint.path.clone(),
+            });
+        }
+    }
+
+    differences
+}
@@ -0,0 +1,8 @@
+//! Development tasks of heroku_rs, run with `cargo xtask <task>`.
+//!
+//! Generates endpoint modules from Heroku's platform JSON hyper-schema,
+//! and compares the schema with the hand-written modules of `src/endpoints`.
+
+pub mod codegen;
+pub mod diff;
+pub mod schema;
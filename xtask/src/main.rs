@@ -0,0 +1,100 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use xtask::{codegen, diff, schema::Schema};
+
+const USAGE: &str = "Usage: cargo xtask <task> [options]
+
+Tasks:
+    codegen    generate endpoint modules from the schema
+               --out <dir>      output directory (default: target/generated)
+    diff       compare the schema with the hand-written modules of src/endpoints
+               --resource <id>  only report differences of one schema resource, e.g. formation
+
+Options:
+    --schema <file>  the platform API schema (default: xtask/schema/platform-api.json)";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let option = |name: &str, default: PathBuf| {
+        args.iter()
+            .position(|arg| arg == name)
+            .and_then(|i| args.get(i + 1))
+            .map(PathBuf::from)
+            .unwrap_or(default)
+    };
+    let schema_path = option("--schema", root.join("xtask/schema/platform-api.json"));
+
+    let result = match args.first().map(String::as_str) {
+        Some("codegen") => codegen(
+            &schema_path,
+            &option("--out", root.join("target/generated")),
+        ),
+        Some("diff") => {
+            let resource = args
+                .iter()
+                .position(|arg| arg == "--resource")
+                .and_then(|i| args.get(i + 1));
+            compare(
+                &schema_path,
+                &root.join("src/endpoints"),
+                resource.map(String::as_str),
+            )
+        }
+        _ => Err(USAGE.to_string()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn codegen(schema_path: &Path, out: &Path) -> Result<(), String> {
+    let schema = Schema::load(schema_path)?;
+    let modules: Vec<codegen::Module> = schema.resources().iter().map(codegen::generate).collect();
+
+    for module in &modules {
+        let dir = out.join(&module.name);
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("could not create {}: {}", dir.display(), e))?;
+        for (file, contents) in &module.files {
+            let path = dir.join(file);
+            fs::write(&path, contents)
+                .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        }
+    }
+    let path = out.join("mod.rs");
+    fs::write(&path, codegen::root_module(&modules))
+        .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+
+    println!("Generated {} modules into {}", modules.len(), out.display());
+    Ok(())
+}
+
+fn compare(schema_path: &Path, source: &Path, resource: Option<&str>) -> Result<(), String> {
+    let schema = Schema::load(schema_path)?;
+    let mut differences = diff::compare(
+        &schema.resources(),
+        &diff::endpoints(source)?,
+        &diff::models(source)?,
+    );
+    if let Some(resource) = resource {
+        differences.retain(|difference| difference.resource() == Some(resource));
+    }
+
+    for difference in &differences {
+        println!("{}", difference);
+    }
+    if differences.is_empty() {
+        println!("The hand-written modules match the schema");
+        Ok(())
+    } else {
+        Err(format!("{} differences with the schema", differences.len()))
+    }
+}
@@ -0,0 +1,245 @@
+//! Reading Heroku's platform JSON hyper-schema into resources, links and properties.
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// A resource of the Heroku platform API, e.g. `formation`.
+#[derive(Debug, Clone)]
+pub struct Resource {
+    /// id of the resource in the schema, e.g. `app-webhook`
+    pub id: String,
+    pub description: String,
+    pub stability: String,
+    pub links: Vec<Link>,
+    pub properties: Vec<Property>,
+}
+
+/// A link is one endpoint of a resource.
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub title: String,
+    pub description: String,
+    /// HTTP method, upper case
+    pub method: String,
+    /// path relative to the API root, with `{}` placeholders, e.g. `apps/{}/formation`
+    pub path: String,
+    /// names of the path placeholders, e.g. `app_id`
+    pub path_params: Vec<String>,
+    /// properties of the request body
+    pub params: Vec<Property>,
+    pub target: Target,
+}
+
+/// What a link responds with.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    Resource,
+    List,
+}
+
+#[derive(Debug, Clone)]
+pub struct Property {
+    pub name: String,
+    pub description: String,
+    pub kind: Kind,
+    pub nullable: bool,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Kind {
+    String,
+    DateTime,
+    Integer,
+    Number,
+    Boolean,
+    Object(Vec<Property>),
+    Array(Box<Kind>),
+    /// Anything that doesn't map onto a single Rust type.
+    Any,
+}
+
+pub struct Schema {
+    root: Value,
+}
+
+impl Schema {
+    pub fn load(path: &Path) -> Result<Schema, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+        Schema::parse(&contents)
+    }
+
+    pub fn parse(contents: &str) -> Result<Schema, String> {
+        let root = serde_json::from_str(contents).map_err(|e| format!("invalid schema: {}", e))?;
+        Ok(Schema { root })
+    }
+
+    /// Every resource of the schema, sorted by id.
+    ///
+    /// Definitions with neither links nor properties are left out: an excerpt of the schema keeps them only for
+    /// the identities other resources refer to, e.g. `app` for the links of `formation`.
+    pub fn resources(&self) -> Vec<Resource> {
+        let definitions = match self.root["definitions"].as_object() {
+            Some(definitions) => definitions,
+            None => return Vec::new(),
+        };
+        let mut resources: Vec<Resource> = definitions
+            .iter()
+            .map(|(id, definition)| self.resource(id, definition))
+            .filter(|resource| !resource.links.is_empty() || !resource.properties.is_empty())
+            .collect();
+        resources.sort_by(|a, b| a.id.cmp(&b.id));
+        resources
+    }
+
+    fn resource(&self, id: &str, definition: &Value) -> Resource {
+        let links = definition["links"]
+            .as_array()
+            .map(|links| links.iter().map(|link| self.link(link)).collect())
+            .unwrap_or_default();
+
+        Resource {
+            id: id.to_string(),
+            description: text(&definition["description"]),
+            stability: text(&definition["stability"]),
+            links,
+            properties: self.properties(definition),
+        }
+    }
+
+    fn link(&self, link: &Value) -> Link {
+        let (path, path_params) = parse_href(&text(&link["href"]));
+        let target = match self.resolve(&link["targetSchema"])["type"] {
+            ref types if has_type(types, "array") => Target::List,
+            _ => Target::Resource,
+        };
+
+        Link {
+            title: text(&link["title"]),
+            description: text(&link["description"]),
+            method: text(&link["method"]).to_uppercase(),
+            path,
+            path_params,
+            params: self.properties(self.resolve(&link["schema"])),
+            target,
+        }
+    }
+
+    fn properties(&self, object: &Value) -> Vec<Property> {
+        let required: Vec<&str> = object["required"]
+            .as_array()
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let mut properties: Vec<Property> = object["properties"]
+            .as_object()
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, property)| {
+                        self.property(name, property, required.contains(&name.as_str()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        properties.sort_by(|a, b| a.name.cmp(&b.name));
+        properties
+    }
+
+    fn property(&self, name: &str, property: &Value, required: bool) -> Property {
+        let resolved = self.resolve(property);
+        // Descriptions on the property itself win over the referenced definition.
+        let description = match text(&property["description"]) {
+            ref description if description.is_empty() => text(&resolved["description"]),
+            description => description,
+        };
+
+        Property {
+            name: name.to_string(),
+            description,
+            kind: self.kind(resolved),
+            nullable: has_type(&resolved["type"], "null"),
+            required,
+        }
+    }
+
+    fn kind(&self, property: &Value) -> Kind {
+        let types: Vec<&str> = property["type"]
+            .as_array()
+            .map(|types| {
+                types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter(|kind| *kind != "null")
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        match types.as_slice() {
+            ["string"] if text(&property["format"]) == "date-time" => Kind::DateTime,
+            ["string"] => Kind::String,
+            ["integer"] => Kind::Integer,
+            ["number"] => Kind::Number,
+            ["boolean"] => Kind::Boolean,
+            ["array"] => Kind::Array(Box::new(self.kind(self.resolve(&property["items"])))),
+            ["object"] if property["properties"].is_object() => {
+                Kind::Object(self.properties(property))
+            }
+            _ => match property["anyOf"].as_array() {
+                Some(any_of) if !any_of.is_empty() => self.kind(self.resolve(&any_of[0])),
+                _ => Kind::Any,
+            },
+        }
+    }
+
+    /// Follow `$ref` pointers until a concrete definition is reached.
+    fn resolve<'a>(&'a self, mut value: &'a Value) -> &'a Value {
+        while let Some(reference) = value["$ref"].as_str() {
+            match self.root.pointer(reference.trim_start_matches('#')) {
+                Some(target) => value = target,
+                None => break,
+            }
+        }
+        value
+    }
+}
+
+/// Turn a schema href into a path with `{}` placeholders and the names of those placeholders.
+///
+/// `/apps/{(%23%2Fdefinitions%2Fapp%2Fdefinitions%2Fidentity)}/formation` becomes `apps/{}/formation` with `app_id`.
+pub fn parse_href(href: &str) -> (String, Vec<String>) {
+    let mut path = String::new();
+    let mut params = Vec::new();
+    let mut rest = href.trim_start_matches('/');
+
+    while let Some(start) = rest.find("{(") {
+        path.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find(")}")
+            .map(|end| start + end)
+            .unwrap_or(rest.len());
+        let pointer = rest[start + 2..end]
+            .replace("%23", "#")
+            .replace("%2F", "/")
+            .replace("%2f", "/");
+        // #/definitions/<resource>/definitions/<attribute>
+        let resource = pointer.split('/').nth(2).unwrap_or("resource");
+        params.push(format!("{}_id", resource.replace('-', "_")));
+        path.push_str("{}");
+        rest = rest.get(end + 2..).unwrap_or("");
+    }
+    path.push_str(rest);
+    (path, params)
+}
+
+fn has_type(types: &Value, kind: &str) -> bool {
+    types
+        .as_array()
+        .map(|types| types.iter().any(|t| t == kind))
+        .unwrap_or(false)
+}
+
+fn text(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}